@@ -0,0 +1,264 @@
+//! Admin console for CRIMSON-REDLINE
+//!
+//! Lets whoever runs a shared install (e.g. an instructor) manage every agent
+//! account. Entered with `sudo console`, open only to admins and, once an
+//! admin has set one, guarded by a master password as well. Quick lookups are
+//! also available from the prompt as `admin <subcommand>`.
+//!
+//! Admin rights are a flag on the account, never a name. Whoever runs the
+//! install grants the first one with `--grant-admin <username>`; admins can
+//! then `admin grant` or `admin revoke` others.
+
+use super::AuthSystem;
+use crate::ui::ColorScheme;
+use crate::ui::menu::{self, MainMenu, MenuAction};
+use crate::utils::audit;
+use anyhow::Result;
+
+/// Usage line for the `admin` command
+pub const COMMAND_USAGE: &str = "admin <stats|users|lock <username>|unlock <username>|grant <username>|revoke <username>>";
+
+/// Run the admin console; returns once the admin exits or authentication fails
+pub async fn run_console(auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Result<()> {
+    let actor = admin_name(auth);
+    if !auth.is_admin() {
+        audit::log_admin_action(&actor, "denied admin console")?;
+        color_scheme.print_error("  [!] Access denied\n")?;
+        return Ok(());
+    }
+    if !authenticate(auth, color_scheme)? {
+        return Ok(());
+    }
+
+    audit::log_admin_action(&actor, "opened admin console")?;
+
+    let mut console = MainMenu::admin_menu();
+    loop {
+        let action = menu::run_menu(&mut console, color_scheme).await?;
+
+        match action {
+            MenuAction::AdminListUsers => show_users(auth, color_scheme)?,
+            MenuAction::AdminStats => show_stats(auth, color_scheme)?,
+            MenuAction::AdminLockUser
+            | MenuAction::AdminUnlockUser
+            | MenuAction::AdminResetReputation
+            | MenuAction::AdminDeleteUser => manage_user(auth, &action, &actor, color_scheme)?,
+            MenuAction::AdminSetPassword => set_master_password(auth, &actor, color_scheme)?,
+            MenuAction::Back | MenuAction::Exit => break,
+            _ => continue,
+        }
+
+        menu::wait_for_key(color_scheme)?;
+    }
+
+    audit::log_admin_action(&actor, "closed admin console")?;
+    Ok(())
+}
+
+/// Grant admin rights from the command line, for whoever runs the install
+pub fn grant_from_operator(auth: &mut AuthSystem, username: &str) -> Result<()> {
+    auth.set_user_admin(username, true)?;
+    audit::log_admin_action("operator", &format!("grant user '{}'", username))
+}
+
+/// Run an `admin` subcommand from the prompt, if the logged-in agent is an admin
+pub fn run_command(auth: &mut AuthSystem, args: &[String], color_scheme: &ColorScheme) -> Result<()> {
+    let actor = admin_name(auth);
+    if !auth.is_admin() {
+        audit::log_admin_action(&actor, &format!("denied admin command '{}'", args.join(" ")))?;
+        color_scheme.print_error("  [!] Access denied\n")?;
+        return Ok(());
+    }
+
+    match (args.first().map(|s| s.as_str()), args.get(1)) {
+        (Some("stats"), _) => show_stats(auth, color_scheme),
+        (Some("users"), _) => list_usernames(auth, color_scheme),
+        (Some(verb @ ("lock" | "unlock" | "grant" | "revoke")), Some(username)) => {
+            let result = match verb {
+                "lock" | "unlock" => auth.set_user_locked(username, verb == "lock"),
+                _ => auth.set_user_admin(username, verb == "grant"),
+            };
+            match result {
+                Ok(()) => {
+                    audit::log_admin_action(&actor, &format!("{} user '{}'", verb, username))?;
+                    color_scheme.print_success(&format!("  [✓] Done: {} '{}'\n", verb, username))?;
+                }
+                Err(e) => {
+                    audit::log_admin_action(&actor, &format!("failed to {} user '{}': {}", verb, username, e))?;
+                    color_scheme.print_error(&format!("  [!] {}\n", e))?;
+                }
+            }
+            Ok(())
+        }
+        _ => {
+            color_scheme.print_error(&format!("  [!] Usage: {}\n", COMMAND_USAGE))?;
+            Ok(())
+        }
+    }
+}
+
+/// List registered usernames, flagging locked accounts
+fn list_usernames(auth: &AuthSystem, color_scheme: &ColorScheme) -> Result<()> {
+    let usernames = auth.list_users()?;
+
+    println!();
+    if usernames.is_empty() {
+        color_scheme.print_dim("  No agents registered\n")?;
+    }
+    for username in &usernames {
+        if auth.is_user_locked(username) {
+            color_scheme.print_error(&format!("  {:<20} [LOCKED]\n", username))?;
+        } else {
+            color_scheme.print_colored(&format!("  {}\n", username))?;
+        }
+    }
+    color_scheme.print_dim(&format!("\n  {} agent(s)\n", usernames.len()))?;
+
+    Ok(())
+}
+
+/// Ask for the master password, if one has been set
+fn authenticate(auth: &AuthSystem, color_scheme: &ColorScheme) -> Result<bool> {
+    println!();
+    if !auth.has_admin_password() {
+        color_scheme.print_warning("  [!] No master password set. Choose one with SET MASTER PASSWORD.\n")?;
+        return Ok(true);
+    }
+
+    let password = menu::read_password("Master password", color_scheme)?;
+    if auth.verify_admin_password(&password) {
+        Ok(true)
+    } else {
+        audit::log_admin_action(&admin_name(auth), "failed admin console authentication")?;
+        color_scheme.print_error("  [!] Access denied\n")?;
+        Ok(false)
+    }
+}
+
+/// Set or change the master password from inside the console
+fn set_master_password(auth: &mut AuthSystem, actor: &str, color_scheme: &ColorScheme) -> Result<()> {
+    println!();
+    let password = menu::read_password("New master password", color_scheme)?;
+    if password.is_empty() {
+        color_scheme.print_dim("  Cancelled\n")?;
+        return Ok(());
+    }
+    let confirm = menu::read_password("Confirm master password", color_scheme)?;
+    if password != confirm {
+        color_scheme.print_error("  [!] Passwords do not match\n")?;
+        return Ok(());
+    }
+
+    match auth.set_admin_password(&password) {
+        Ok(()) => {
+            audit::log_admin_action(actor, "set admin master password")?;
+            color_scheme.print_success("  [✓] Master password set\n")?;
+        }
+        Err(e) => color_scheme.print_error(&format!("  [!] {}\n", e))?,
+    }
+    Ok(())
+}
+
+/// Name recorded in the audit log for the acting admin
+fn admin_name(auth: &AuthSystem) -> String {
+    auth.current_user()
+        .map(|u| u.username.clone())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// List every user with their stats
+fn show_users(auth: &AuthSystem, color_scheme: &ColorScheme) -> Result<()> {
+    let users = auth.all_users();
+
+    println!();
+    color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+    color_scheme.print_bright("                    REGISTERED AGENTS                          \n")?;
+    color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+    println!();
+
+    if users.is_empty() {
+        color_scheme.print_dim("  No agents registered\n")?;
+    }
+
+    for user in &users {
+        let last_login = user.last_login
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        let line = format!(
+            "  {:<20} rep {:>6}  logins {:>4}  last {}",
+            user.username, user.reputation, user.login_count, last_login
+        );
+
+        if user.is_locked() {
+            color_scheme.print_error(&format!("{}  [LOCKED]\n", line))?;
+        } else {
+            color_scheme.print_colored(&format!("{}\n", line))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Show user database statistics
+fn show_stats(auth: &AuthSystem, color_scheme: &ColorScheme) -> Result<()> {
+    let stats = auth.storage_stats();
+
+    println!();
+    color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+    color_scheme.print_bright("                    STORAGE STATISTICS                         \n")?;
+    color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+    println!();
+    color_scheme.print_colored(&format!("  Total users:    {}\n", stats.total_users))?;
+    color_scheme.print_colored(&format!("  Active users:   {}\n", stats.active_users))?;
+    color_scheme.print_colored(&format!("  Locked users:   {}\n", stats.locked_users))?;
+    color_scheme.print_colored(&format!("  Total logins:   {}\n", stats.total_logins))?;
+    color_scheme.print_colored(&format!("  Avg reputation: {}\n", stats.avg_reputation))?;
+
+    Ok(())
+}
+
+/// Apply a per-user admin action after confirmation, recording it in the audit log
+fn manage_user(
+    auth: &mut AuthSystem,
+    action: &MenuAction,
+    actor: &str,
+    color_scheme: &ColorScheme,
+) -> Result<()> {
+    println!();
+    let username = menu::read_line("Username", color_scheme)?;
+    if username.is_empty() {
+        return Ok(());
+    }
+
+    let verb = match action {
+        MenuAction::AdminLockUser => "lock",
+        MenuAction::AdminUnlockUser => "unlock",
+        MenuAction::AdminResetReputation => "reset reputation of",
+        _ => "delete",
+    };
+
+    if !menu::confirm(&format!("Really {} '{}'?", verb, username), color_scheme)? {
+        color_scheme.print_dim("  Cancelled\n")?;
+        return Ok(());
+    }
+
+    let result = match action {
+        MenuAction::AdminLockUser => auth.set_user_locked(&username, true),
+        MenuAction::AdminUnlockUser => auth.set_user_locked(&username, false),
+        MenuAction::AdminResetReputation => auth.reset_user_reputation(&username),
+        _ => auth.delete_user(&username),
+    };
+
+    match result {
+        Ok(()) => {
+            audit::log_admin_action(actor, &format!("{} user '{}'", verb, username))?;
+            color_scheme.print_success(&format!("  [✓] Done: {} '{}'\n", verb, username))?;
+        }
+        Err(e) => {
+            audit::log_admin_action(actor, &format!("failed to {} user '{}': {}", verb, username, e))?;
+            color_scheme.print_error(&format!("  [!] {}\n", e))?;
+        }
+    }
+
+    Ok(())
+}
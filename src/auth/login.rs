@@ -1,7 +1,8 @@
 //! Login interface and logic for CRIMSON-REDLINE
 
 use crate::ui::{ColorScheme, animations};
-use crate::auth::AuthSystem;
+use crate::auth::{AuthSystem, CodeRequired};
+use crate::utils::secret::{password_buffer, PasswordBuffer, MAX_PASSWORD_LEN};
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent},
     terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
@@ -10,15 +11,20 @@ use crossterm::{
 };
 use std::io::{self, Write};
 use anyhow::Result;
+use zeroize::Zeroize;
+
+/// Seconds the login screen holds a player after too many failed attempts
+const LOCKOUT_COOLDOWN_SECS: u64 = 10;
 
 /// Login screen state
 pub struct LoginScreen {
     username: String,
-    password: String,
+    password: PasswordBuffer,
     show_password: bool,
     input_mode: InputMode,
     error_message: Option<String>,
     attempts: u32,
+    locked_out: bool,
 }
 
 #[derive(Debug, PartialEq)]
@@ -27,16 +33,23 @@ enum InputMode {
     Password,
 }
 
+impl Default for LoginScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LoginScreen {
     /// Create a new login screen
     pub fn new() -> Self {
         LoginScreen {
             username: String::new(),
-            password: String::new(),
+            password: password_buffer(),
             show_password: false,
             input_mode: InputMode::Username,
             error_message: None,
             attempts: 0,
+            locked_out: false,
         }
     }
 
@@ -80,18 +93,17 @@ impl LoginScreen {
         let password_display = if self.show_password {
             self.password.clone()
         } else {
-            "*".repeat(self.password.len())
+            PasswordBuffer::new("*".repeat(self.password.chars().count()))
         };
+        let password_cursor = if self.input_mode == InputMode::Password { "█" } else { "" };
         
-        color_scheme.print_colored(&format!(
-            "   {}PASSWORD: {}\n",
+        // The formatted line may hold the plaintext, so it is zeroed too
+        color_scheme.print_colored(&PasswordBuffer::new(format!(
+            "   {}PASSWORD: {}{}\n",
             password_prefix,
-            if self.input_mode == InputMode::Password {
-                format!("{}█", password_display)
-            } else {
-                password_display
-            }
-        ))?;
+            password_display.as_str(),
+            password_cursor
+        )))?;
 
         color_scheme.print_colored("\n")?;
         color_scheme.print_colored("    ───────────────────────────────────────────────────────────\n")?;
@@ -140,10 +152,10 @@ impl LoginScreen {
             KeyCode::Backspace => {
                 match self.input_mode {
                     InputMode::Username => {
-                        self.username.pop();
+                        crate::ui::pop_char(&mut self.username);
                     }
                     InputMode::Password => {
-                        self.password.pop();
+                        crate::ui::pop_char(&mut self.password);
                     }
                 }
                 self.error_message = None;
@@ -157,7 +169,7 @@ impl LoginScreen {
                         }
                     }
                     InputMode::Password => {
-                        if self.password.len() < 50 {
+                        if self.password.len() < MAX_PASSWORD_LEN {
                             self.password.push(c);
                         }
                     }
@@ -170,31 +182,47 @@ impl LoginScreen {
     }
 
     /// Process login attempt
-    pub async fn attempt_login(&mut self, auth: &mut AuthSystem) -> Result<bool> {
+    pub async fn attempt_login(&mut self, auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Result<bool> {
         self.attempts += 1;
         
         // Show loading animation
-        animations::show_processing("AUTHENTICATING", 1500).await?;
+        animations::show_processing("AUTHENTICATING", 1500, color_scheme).await?;
+        
+        let mut result = auth.login(&self.username, &self.password).await;
+        if result.as_ref().is_err_and(|e| e.is::<CodeRequired>()) {
+            result = match read_code(color_scheme)? {
+                Some(code) => auth.login_with_code(&self.username, &self.password, Some(&code)).await,
+                None => Err(anyhow::anyhow!("Two-factor code required")),
+            };
+        }
         
-        match auth.login(&self.username, &self.password).await {
+        match result {
             Ok(user) => {
                 // Clear sensitive data
-                self.password.clear();
+                self.password.zeroize();
                 
                 // Show success message
                 animations::show_success(&format!(
                     "ACCESS GRANTED - Welcome back, Agent {}",
                     user.username
-                )).await?;
+                ), color_scheme).await?;
                 
                 Ok(true)
             }
             Err(e) => {
                 self.error_message = Some(e.to_string());
-                self.password.clear();
+                self.password.zeroize();
+                
+                if auth.is_user_locked(&self.username) {
+                    self.locked_out = true;
+                    animations::show_error("ACCOUNT LOCKED", color_scheme).await?;
+                    lockout_cooldown(&e.to_string()).await?;
+                    return Ok(false);
+                }
                 
                 if self.attempts >= 3 {
-                    animations::show_error("MAXIMUM LOGIN ATTEMPTS EXCEEDED").await?;
+                    animations::show_error("MAXIMUM LOGIN ATTEMPTS EXCEEDED", color_scheme).await?;
+                    lockout_cooldown("Too many failed attempts this session").await?;
                     return Ok(false);
                 }
                 
@@ -211,7 +239,7 @@ impl LoginScreen {
     /// Clear all fields
     pub fn clear(&mut self) {
         self.username.clear();
-        self.password.clear();
+        self.password.zeroize();
         self.show_password = false;
         self.input_mode = InputMode::Username;
         self.error_message = None;
@@ -227,6 +255,46 @@ pub enum LoginAction {
     Cancel,
 }
 
+/// Ask for a two-factor code in raw mode; Esc gives `None`
+fn read_code(color_scheme: &ColorScheme) -> Result<Option<String>> {
+    color_scheme.print_colored("\r\n    Two-factor code: ")?;
+    io::stdout().flush()?;
+
+    let mut code = String::new();
+    loop {
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Enter => break,
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Backspace if code.pop().is_some() => {
+                    print!("\x08 \x08");
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() && code.len() < crate::auth::totp::DIGITS => {
+                    code.push(c);
+                    print!("{}", c);
+                }
+                _ => continue,
+            }
+            io::stdout().flush()?;
+        }
+    }
+    Ok(Some(code))
+}
+
+/// Hold the player on a countdown so a lockout can't be retried instantly
+async fn lockout_cooldown(reason: &str) -> Result<()> {
+    let color_scheme = ColorScheme::new();
+    
+    color_scheme.print_warning(&format!("\r\n  {}\r\n", reason))?;
+    for remaining in (1..=LOCKOUT_COOLDOWN_SECS).rev() {
+        color_scheme.print_dim(&format!("\r  Returning to menu in {:>2}s...", remaining))?;
+        io::stdout().flush()?;
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+    
+    Ok(())
+}
+
 /// Run the login interface
 pub async fn run_login(auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Result<bool> {
     let mut login_screen = LoginScreen::new();
@@ -246,8 +314,11 @@ pub async fn run_login(auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Res
     let result = loop {
         // Wait for a single key event
         let key = loop {
-            if let Ok(Event::Key(k)) = event::read() {
-                break k;
+            match event::read() {
+                Ok(Event::Key(k)) => break k,
+                // The screen clears before drawing, so a redraw fits the new size
+                Ok(Event::Resize(..)) => login_screen.display(color_scheme).await?,
+                _ => {}
             }
         };
         
@@ -258,11 +329,11 @@ pub async fn run_login(auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Res
                 login_screen.display(color_scheme).await?;
             }
             LoginAction::AttemptLogin => {
-                let success = login_screen.attempt_login(auth).await?;
+                let success = login_screen.attempt_login(auth, color_scheme).await?;
                 if success {
                     break true;
                 }
-                if login_screen.attempts >= 3 {
+                if login_screen.attempts >= 3 || login_screen.locked_out {
                     break false;
                 }
                 // Redraw to show error message
@@ -298,7 +369,7 @@ mod tests {
     fn test_login_screen_creation() {
         let screen = LoginScreen::new();
         assert_eq!(screen.username, "");
-        assert_eq!(screen.password, "");
+        assert_eq!(screen.password.as_str(), "");
         assert_eq!(screen.input_mode, InputMode::Username);
         assert!(!screen.show_password);
     }
@@ -323,6 +394,6 @@ mod tests {
         screen.handle_input(KeyEvent::from(KeyCode::Char('p')));
         screen.handle_input(KeyEvent::from(KeyCode::Char('a')));
         screen.handle_input(KeyEvent::from(KeyCode::Backspace));
-        assert_eq!(screen.password, "p");
+        assert_eq!(screen.password.as_str(), "p");
     }
 }
\ No newline at end of file
@@ -1,8 +1,11 @@
 //! Authentication system for CRIMSON-REDLINE
 
+pub mod admin;
 pub mod login;
+pub mod passwd;
 pub mod register;
 pub mod storage;
+pub mod totp;
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
@@ -19,12 +22,76 @@ pub struct User {
     pub reputation: i32,
     pub is_active: bool,
     pub failed_attempts: u32,
+    /// End of the current failed-login cooldown, if one is running
+    #[serde(default)]
+    pub locked_until: Option<DateTime<Utc>>,
+    /// Cooldowns triggered since the last successful login; each one lasts longer
+    #[serde(default)]
+    pub lockouts: u32,
+    /// Where the last successful login came from
+    #[serde(default)]
+    pub last_login_host: Option<String>,
+    /// Hashes of earlier passwords, newest first
+    #[serde(default)]
+    pub password_history: Vec<String>,
+    /// Base32 TOTP secret, set while two-factor login is on
+    #[serde(default)]
+    pub totp_secret: Option<String>,
+    /// Time step of the last two-factor code accepted, so it can't be used twice
+    #[serde(default)]
+    pub totp_last_step: Option<u64>,
+    /// May run `admin` commands; granted by the operator or an existing admin
+    #[serde(default)]
+    pub is_admin: bool,
+}
+
+/// Login error when the password checked out but a two-factor code is still needed
+#[derive(Debug)]
+pub struct CodeRequired;
+
+impl std::fmt::Display for CodeRequired {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Two-factor code required")
+    }
+}
+
+impl std::error::Error for CodeRequired {}
+
+/// When and where an agent last logged in, before the current session
+#[derive(Debug, Clone, PartialEq)]
+pub struct LastLogin {
+    pub at: DateTime<Utc>,
+    pub host: Option<String>,
+}
+
+/// Where this login is coming from: the SSH client's address, the hostname, or "local"
+pub fn login_host() -> String {
+    let ssh_client = std::env::var("SSH_CLIENT").ok();
+    let hostname = std::env::var("HOSTNAME").ok();
+    describe_host(ssh_client.as_deref(), hostname.as_deref())
+}
+
+/// Pick the most telling of the login sources
+fn describe_host(ssh_client: Option<&str>, hostname: Option<&str>) -> String {
+    // SSH_CLIENT is "<address> <client port> <server port>"
+    ssh_client
+        .and_then(|client| client.split_whitespace().next())
+        .or(hostname)
+        .map(str::trim)
+        .filter(|host| !host.is_empty())
+        .unwrap_or("local")
+        .to_string()
 }
 
 impl User {
     /// Create a new user with hashed password
     pub fn new(username: String, password: &str) -> Result<Self> {
-        let password_hash = bcrypt::hash(password, bcrypt::DEFAULT_COST)?;
+        Self::with_cost(username, password, bcrypt::DEFAULT_COST)
+    }
+
+    /// Create a new user, hashing the password with the given bcrypt work factor
+    pub fn with_cost(username: String, password: &str, cost: u32) -> Result<Self> {
+        let password_hash = bcrypt::hash(password, cost)?;
         
         Ok(User {
             username,
@@ -35,6 +102,13 @@ impl User {
             reputation: 0,
             is_active: true,
             failed_attempts: 0,
+            locked_until: None,
+            lockouts: 0,
+            last_login_host: None,
+            password_history: Vec::new(),
+            totp_secret: None,
+            totp_last_step: None,
+            is_admin: false,
         })
     }
 
@@ -43,30 +117,105 @@ impl User {
         bcrypt::verify(password, &self.password_hash).unwrap_or(false)
     }
 
-    /// Update last login timestamp
-    pub fn update_login(&mut self) {
+    /// Work factor the stored hash was generated with, read from the `$2b$<cost>$` prefix
+    pub fn hash_cost(&self) -> Option<u32> {
+        self.password_hash.split('$').nth(2)?.parse().ok()
+    }
+
+    /// Replace the password with a fresh bcrypt hash of `password`
+    pub fn set_password(&mut self, password: &str, cost: u32) -> Result<()> {
+        self.password_hash = bcrypt::hash(password, cost)?;
+        Ok(())
+    }
+
+    /// Whether `password` matches one of the last `limit` passwords
+    pub fn used_password_recently(&self, password: &str, limit: usize) -> bool {
+        self.password_history
+            .iter()
+            .take(limit)
+            .any(|hash| bcrypt::verify(password, hash).unwrap_or(false))
+    }
+
+    /// Replace the password, keeping the old hash in the last `limit` remembered
+    pub fn change_password(&mut self, password: &str, cost: u32, limit: usize) -> Result<()> {
+        let old_hash = std::mem::replace(&mut self.password_hash, bcrypt::hash(password, cost)?);
+        self.password_history.insert(0, old_hash);
+        self.password_history.truncate(limit);
+        Ok(())
+    }
+
+    /// Update last login timestamp and where it came from
+    pub fn update_login(&mut self, host: &str) {
         self.last_login = Some(Utc::now());
+        self.last_login_host = Some(host.to_string());
         self.login_count += 1;
         self.failed_attempts = 0; // Reset failed attempts on successful login
+        self.lockouts = 0;
+        self.locked_until = None;
     }
 
     /// Record a failed login attempt
     pub fn record_failed_attempt(&mut self) {
+        self.record_failed_attempt_with_limit(5);
+    }
+
+    /// Record a failed login attempt, starting a cooldown once `limit` is reached
+    pub fn record_failed_attempt_with_limit(&mut self, limit: u32) {
         self.failed_attempts += 1;
-        if self.failed_attempts >= 5 {
-            self.is_active = false; // Lock account after 5 failed attempts
+        if self.failed_attempts >= limit {
+            let step = (self.lockouts as usize).min(LOCKOUT_COOLDOWNS_SECS.len() - 1);
+            self.locked_until = Some(Utc::now() + chrono::Duration::seconds(LOCKOUT_COOLDOWNS_SECS[step]));
+            self.lockouts += 1;
+            // A fresh set of attempts once the cooldown ends
+            self.failed_attempts = 0;
         }
     }
 
-    /// Check if account is locked
+    /// Time left on a failed-login cooldown, if one is running
+    pub fn lockout_remaining(&self) -> Option<chrono::Duration> {
+        let remaining = self.locked_until? - Utc::now();
+        (remaining > chrono::Duration::zero()).then_some(remaining)
+    }
+
+    /// Check if account is locked, by an admin or a running cooldown
     pub fn is_locked(&self) -> bool {
-        !self.is_active || self.failed_attempts >= 5
+        !self.is_active || self.lockout_remaining().is_some()
+    }
+
+    /// Lock the account until an admin unlocks it
+    pub fn lock(&mut self) {
+        self.is_active = false;
     }
 
     /// Unlock the account
     pub fn unlock(&mut self) {
         self.is_active = true;
         self.failed_attempts = 0;
+        self.lockouts = 0;
+        self.locked_until = None;
+    }
+}
+
+/// Failed-login cooldowns, escalating with each lockout: 30s, 2m, then 10m
+pub const LOCKOUT_COOLDOWNS_SECS: [i64; 3] = [30, 120, 600];
+
+/// Shown when an administrator has locked the account
+pub const LOCKED_MESSAGE: &str =
+    "Account locked by an administrator. Ask them to unlock it";
+
+/// Explain why a login is refused and when it may be retried
+fn lockout_message(user: &User) -> String {
+    match user.lockout_remaining() {
+        Some(remaining) if user.is_active => {
+            let secs = remaining.num_seconds() + 1;
+            let wait = if secs >= 60 {
+                format!("{}m {:02}s", secs / 60, secs % 60)
+            } else {
+                format!("{}s", secs)
+            };
+            format!("Account locked after too many failed attempts. Try again in {}", wait)
+        }
+        _ => LOCKED_MESSAGE.to_string(),
     }
 }
 
@@ -74,6 +223,8 @@ impl User {
 pub struct AuthSystem {
     storage: storage::UserStorage,
     current_user: Option<User>,
+    /// The current agent's login before this one
+    previous_login: Option<LastLogin>,
     config: crate::utils::Config,
 }
 
@@ -82,16 +233,24 @@ impl AuthSystem {
     pub fn new() -> Result<Self> {
         let storage = storage::UserStorage::new()?;
         let config = crate::utils::Config::load()?;
-        
-        Ok(AuthSystem {
+        Ok(Self::with_storage(storage, config))
+    }
+
+    /// Create an authentication system over existing storage
+    pub fn with_storage(storage: storage::UserStorage, config: crate::utils::Config) -> Self {
+        AuthSystem {
             storage,
             current_user: None,
+            previous_login: None,
             config,
-        })
+        }
     }
 
     /// Register a new user
     pub async fn register(&mut self, username: &str, password: &str, confirm_password: &str) -> Result<User> {
+        // Display case is kept; uniqueness ignores case and surrounding spaces
+        let username = username.trim();
+
         // Validate passwords match
         if password != confirm_password {
             anyhow::bail!("Passwords do not match");
@@ -105,7 +264,19 @@ impl AuthSystem {
             anyhow::bail!("Username '{}' already exists", username);
         }
 
-        // Validate username
+        self.validate_username(username)?;
+
+        // Create new user
+        let user = User::with_cost(username.to_string(), password, self.config.security.bcrypt_cost())?;
+        
+        // Save to storage
+        self.storage.save_user(&user)?;
+        
+        Ok(user)
+    }
+
+    /// Check a new username against the registration rules
+    fn validate_username(&self, username: &str) -> Result<()> {
         if username.len() < 3 {
             anyhow::bail!("Username must be at least 3 characters long");
         }
@@ -118,35 +289,76 @@ impl AuthSystem {
             anyhow::bail!("Username can only contain letters, numbers, and underscores");
         }
 
-        // Create new user
-        let user = User::new(username.to_string(), password)?;
-        
-        // Save to storage
-        self.storage.save_user(&user)?;
-        
-        Ok(user)
+        if self.is_reserved(username) {
+            anyhow::bail!("Username '{}' is reserved", username);
+        }
+
+        Ok(())
+    }
+
+    /// Whether a name is kept back from registration
+    fn is_reserved(&self, username: &str) -> bool {
+        let name = storage::canonical_username(username);
+        self.config.security.reserved_usernames.iter().any(|n| storage::canonical_username(n) == name)
     }
 
     /// Login an existing user
     pub async fn login(&mut self, username: &str, password: &str) -> Result<User> {
-        // Load user from storage
-        let mut user = self.storage.load_user(username)?
+        self.login_with_code(username, password, None).await
+    }
+
+    /// Login, checking a two-factor code when the account has 2FA on. Without a
+    /// code such accounts fail with [`CodeRequired`] once the password is right.
+    /// Accounts that turned 2FA on keep needing codes even if `security.enable_2fa`
+    /// is switched off later
+    pub async fn login_with_code(&mut self, username: &str, password: &str, code: Option<&str>) -> Result<User> {
+        // Load user from storage; the lookup ignores case and surrounding spaces
+        let mut user = self.storage.load_user(username.trim())?
             .ok_or_else(|| anyhow::anyhow!("Invalid username or password"))?;
 
         // Check if account is locked
         if user.is_locked() {
-            anyhow::bail!("Account is locked due to multiple failed login attempts");
+            anyhow::bail!("{}", lockout_message(&user));
         }
 
-        // Verify password
+        // Verify password; the attempt count is persisted so restarting doesn't reset it
         if !user.verify_password(password) {
-            user.record_failed_attempt();
+            user.record_failed_attempt_with_limit(self.config.security.max_login_attempts);
             self.storage.save_user(&user)?;
+            if user.is_locked() {
+                anyhow::bail!("{}", lockout_message(&user));
+            }
             anyhow::bail!("Invalid username or password");
         }
 
-        // Update login info
-        user.update_login();
+        if let Some(secret) = &user.totp_secret {
+            let Some(code) = code else {
+                return Err(CodeRequired.into());
+            };
+            // Wrong or reused codes count towards the lockout just like wrong passwords
+            match totp::verify(secret, code, totp::now(), user.totp_last_step) {
+                Some(step) => user.totp_last_step = Some(step),
+                None => {
+                    user.record_failed_attempt_with_limit(self.config.security.max_login_attempts);
+                    self.storage.save_user(&user)?;
+                    if user.is_locked() {
+                        anyhow::bail!("{}", lockout_message(&user));
+                    }
+                    anyhow::bail!("Invalid two-factor code");
+                }
+            }
+        }
+
+        // Bring hashes from before a cost increase up to date while we have the plaintext;
+        // a failure just leaves the old hash, which still verifies
+        let cost = self.config.security.bcrypt_cost();
+        if user.hash_cost().is_some_and(|current| current < cost) {
+            let _ = user.set_password(password, cost);
+        }
+
+        // Update login info, remembering the previous one for the welcome screen
+        self.previous_login = user.last_login.map(|at| LastLogin { at, host: user.last_login_host.clone() });
+        user.update_login(&login_host());
         self.storage.save_user(&user)?;
         
         // Set current user
@@ -155,9 +367,135 @@ impl AuthSystem {
         Ok(user)
     }
 
+    /// Change the logged-in agent's password after checking the current one
+    pub fn change_password(&mut self, current: &str, new: &str, confirm: &str) -> Result<()> {
+        let username = self.current_user.as_ref()
+            .map(|u| u.username.clone())
+            .ok_or_else(|| anyhow::anyhow!("Not logged in"))?;
+        let mut user = self.storage.load_user(&username)?
+            .ok_or_else(|| anyhow::anyhow!("User '{}' not found", username))?;
+
+        if !user.verify_password(current) {
+            anyhow::bail!("Current password is incorrect");
+        }
+        if new != confirm {
+            anyhow::bail!("Passwords do not match");
+        }
+        if new == current {
+            anyhow::bail!("New password must differ from the current one");
+        }
+        let history = self.config.security.password_history;
+        if user.used_password_recently(new, history) {
+            anyhow::bail!("New password must differ from your last {} passwords", history);
+        }
+        self.config.validate_password(new)?;
+
+        user.change_password(new, self.config.security.bcrypt_cost(), history)?;
+        self.storage.save_user(&user)?;
+        self.current_user = Some(user);
+        Ok(())
+    }
+
+    /// Give the logged-in agent a new username, returning the renamed account
+    pub fn rename_current_user(&mut self, new_username: &str) -> Result<User> {
+        let new_username = new_username.trim();
+        let current = self.current_user.as_ref()
+            .map(|u| u.username.clone())
+            .ok_or_else(|| anyhow::anyhow!("Not logged in"))?;
+        let mut user = self.storage.load_user(&current)?
+            .ok_or_else(|| anyhow::anyhow!("User '{}' not found", current))?;
+
+        if new_username == current {
+            anyhow::bail!("You're already '{}'", current);
+        }
+        // Changing only the case of your own name is fine
+        if !self.is_current_user(new_username) && self.storage.user_exists(new_username)? {
+            anyhow::bail!("Username '{}' already exists", new_username);
+        }
+        self.validate_username(new_username)?;
+
+        user.username = new_username.to_string();
+        self.storage.rename_user(&current, &user)?;
+        self.current_user = Some(user.clone());
+        Ok(user)
+    }
+
+    /// Delete the logged-in agent's own account once their password checks out,
+    /// logging them out and returning the deleted username
+    pub fn delete_current_user(&mut self, password: &str) -> Result<String> {
+        let username = self.current_user.as_ref()
+            .map(|u| u.username.clone())
+            .ok_or_else(|| anyhow::anyhow!("Not logged in"))?;
+        let user = self.storage.load_user(&username)?
+            .ok_or_else(|| anyhow::anyhow!("User '{}' not found", username))?;
+
+        if !user.verify_password(password) {
+            anyhow::bail!("Password is incorrect");
+        }
+
+        // Unlike `delete_user`, removing yourself is the point here
+        self.storage.delete_user(&username)?;
+        self.current_user = None;
+        Ok(username)
+    }
+
+    /// Whether this install lets agents use two-factor login
+    pub fn two_factor_available(&self) -> bool {
+        self.config.security.enable_2fa
+    }
+
+    /// Turn on two-factor login for the logged-in agent, once `code` shows their app has `secret`
+    pub fn enable_two_factor(&mut self, secret: &str, code: &str) -> Result<()> {
+        if !self.two_factor_available() {
+            anyhow::bail!("Two-factor login is turned off on this install");
+        }
+        let Some(step) = totp::verify(secret, code, totp::now(), None) else {
+            anyhow::bail!("That code doesn't match; two-factor login is still off");
+        };
+        self.update_current_user(|user| {
+            user.totp_secret = Some(secret.to_string());
+            user.totp_last_step = Some(step);
+        })
+    }
+
+    /// Turn off two-factor login for the logged-in agent after checking their password
+    pub fn disable_two_factor(&mut self, password: &str) -> Result<()> {
+        if !self.current_user.as_ref().is_some_and(|u| u.verify_password(password)) {
+            anyhow::bail!("Password is incorrect");
+        }
+        self.update_current_user(|user| {
+            user.totp_secret = None;
+            user.totp_last_step = None;
+        })
+    }
+
+    /// Apply a change to the stored record of the logged-in agent
+    fn update_current_user(&mut self, change: impl FnOnce(&mut User)) -> Result<()> {
+        let username = self.current_user.as_ref()
+            .map(|u| u.username.clone())
+            .ok_or_else(|| anyhow::anyhow!("Not logged in"))?;
+        let mut user = self.storage.load_user(&username)?
+            .ok_or_else(|| anyhow::anyhow!("User '{}' not found", username))?;
+        change(&mut user);
+        self.storage.save_user(&user)?;
+        self.current_user = Some(user);
+        Ok(())
+    }
+
+    /// Whether a stored account is currently locked
+    pub fn is_user_locked(&self, username: &str) -> bool {
+        self.storage.load_user(username.trim()).ok().flatten().is_some_and(|u| u.is_locked())
+    }
+
     /// Logout current user
     pub fn logout(&mut self) {
         self.current_user = None;
+        self.previous_login = None;
+    }
+
+    /// When and where the current agent logged in before this session; `None` on a first login
+    pub fn previous_login(&self) -> Option<&LastLogin> {
+        self.previous_login.as_ref()
     }
 
     /// Get current logged-in user
@@ -184,17 +522,94 @@ impl AuthSystem {
         Ok(())
     }
 
-    /// Delete a user (admin function)
+    /// Whether an admin console master password has been set
+    pub fn has_admin_password(&self) -> bool {
+        self.config.security.admin_password_hash.is_some()
+    }
+
+    /// Set the admin console master password (admin function)
+    pub fn set_admin_password(&mut self, password: &str) -> Result<()> {
+        self.config.validate_password(password)?;
+        let hash = bcrypt::hash(password, self.config.security.bcrypt_cost())?;
+
+        // Save over the file as it is now, not as it was at startup, so settings
+        // changed since then survive
+        let mut config = crate::utils::Config::load()?;
+        config.security.admin_password_hash = Some(hash.clone());
+        config.save()?;
+        self.config.security.admin_password_hash = Some(hash);
+        Ok(())
+    }
+
+    /// Check the admin console master password
+    pub fn verify_admin_password(&self, password: &str) -> bool {
+        self.config.security.admin_password_hash
+            .as_ref()
+            .is_some_and(|hash| bcrypt::verify(password, hash).unwrap_or(false))
+    }
+
+    /// Whether `username` names the logged-in agent, however it's cased
+    fn is_current_user(&self, username: &str) -> bool {
+        self.current_user.as_ref().is_some_and(|u| {
+            storage::canonical_username(&u.username) == storage::canonical_username(username)
+        })
+    }
+
+    /// Whether the logged-in agent may run `admin` commands, going by the stored
+    /// account so a revoke takes effect straight away
+    pub fn is_admin(&self) -> bool {
+        self.current_user.as_ref().is_some_and(|user| {
+            self.storage.load_user(&user.username).ok().flatten().is_some_and(|stored| stored.is_admin)
+        })
+    }
+
+    /// Grant or revoke a user's admin rights (admin or operator function)
+    pub fn set_user_admin(&mut self, username: &str, admin: bool) -> Result<()> {
+        // Revoking yourself could leave nobody able to grant it back
+        if !admin && self.is_current_user(username) {
+            anyhow::bail!("Cannot revoke your own admin rights");
+        }
+        self.storage.set_admin(username, admin)
+    }
+
+    /// Get every registered user with their stats (admin function)
+    pub fn all_users(&self) -> Vec<User> {
+        self.storage.list_users()
+    }
+
+    /// Get statistics about the user database (admin function)
+    pub fn storage_stats(&self) -> storage::UserStorageStats {
+        self.storage.get_stats()
+    }
+
+    /// Lock or unlock a user's account (admin function)
+    pub fn set_user_locked(&mut self, username: &str, locked: bool) -> Result<()> {
+        if locked && self.is_current_user(username) {
+            anyhow::bail!("Cannot lock currently logged-in user");
+        }
+        self.storage.set_locked(username, locked)
+    }
+
+    /// Reset a user's reputation to zero (admin function)
+    pub fn reset_user_reputation(&mut self, username: &str) -> Result<()> {
+        // The running session would write its reputation straight back
+        if self.is_current_user(username) {
+            anyhow::bail!("Cannot reset reputation of currently logged-in user");
+        }
+        self.storage.reset_reputation(username)
+    }
+
+    /// Delete a user along with their saves and history (admin function)
     pub fn delete_user(&mut self, username: &str) -> Result<()> {
         // Cannot delete current user
-        if let Some(ref current) = self.current_user {
-            if current.username == username {
-                anyhow::bail!("Cannot delete currently logged-in user");
-            }
+        if self.is_current_user(username) {
+            anyhow::bail!("Cannot delete currently logged-in user");
         }
         
-        self.storage.delete_user(username)?;
-        Ok(())
+        let user = self.storage.load_user(username)?
+            .ok_or_else(|| anyhow::anyhow!("User '{}' not found", username))?;
+        self.storage.delete_user(&user.username)?;
+        crate::game::SaveGame::delete_agent_files(&user.username)
     }
 }
 
@@ -211,6 +626,13 @@ mod tests {
         assert!(user.is_active);
     }
 
+    #[test]
+    fn test_user_hashed_with_given_cost() {
+        let user = User::with_cost("testuser".to_string(), "Password123!", 4).unwrap();
+        assert!(user.password_hash.starts_with("$2b$04$"));
+        assert!(user.verify_password("Password123!"));
+    }
+
     #[test]
     fn test_password_verification() {
         let user = User::new("testuser".to_string(), "Password123!").unwrap();
@@ -233,4 +655,332 @@ mod tests {
         user.unlock();
         assert!(!user.is_locked());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_change_password() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut auth = AuthSystem::with_storage(storage, crate::utils::Config::default());
+        auth.register("agent", "Password123!", "Password123!").await.unwrap();
+        auth.login("agent", "Password123!").await.unwrap();
+
+        assert!(auth.change_password("wrong", "NewPass456!", "NewPass456!").is_err());
+        assert!(auth.change_password("Password123!", "NewPass456!", "NewPass789!").is_err());
+        assert!(auth.change_password("Password123!", "Password123!", "Password123!").is_err());
+        assert!(auth.change_password("Password123!", "weak", "weak").is_err());
+        auth.change_password("Password123!", "NewPass456!", "NewPass456!").unwrap();
+
+        auth.logout();
+        assert!(auth.login("agent", "Password123!").await.is_err());
+        assert!(auth.login("agent", "NewPass456!").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_recent_passwords_cannot_be_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        config.security.password_history = 2;
+        let mut auth = AuthSystem::with_storage(storage, config);
+        auth.register("agent", "Password1!", "Password1!").await.unwrap();
+        auth.login("agent", "Password1!").await.unwrap();
+
+        auth.change_password("Password1!", "Password2!", "Password2!").unwrap();
+        let err = auth.change_password("Password2!", "Password1!", "Password1!").unwrap_err();
+        assert!(err.to_string().contains("last 2 passwords"));
+
+        auth.change_password("Password2!", "Password3!", "Password3!").unwrap();
+        auth.change_password("Password3!", "Password4!", "Password4!").unwrap();
+        assert_eq!(auth.current_user().unwrap().password_history.len(), 2);
+        // Old enough to have dropped out of the history
+        auth.change_password("Password4!", "Password1!", "Password1!").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_login_upgrades_hash_cost() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        let mut auth = AuthSystem::with_storage(storage, config);
+        auth.register("agent", "Password123!", "Password123!").await.unwrap();
+
+        let first = auth.login("agent", "Password123!").await.unwrap();
+        assert_eq!(first.hash_cost(), Some(4));
+
+        auth.config.security.bcrypt_cost = 5;
+        let second = auth.login("agent", "Password123!").await.unwrap();
+        assert_eq!(second.hash_cost(), Some(5));
+        assert_ne!(first.password_hash, second.password_hash);
+        assert!(second.verify_password("Password123!"));
+    }
+
+    #[tokio::test]
+    async fn test_only_granted_agents_are_admins() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        let mut auth = AuthSystem::with_storage(storage, config);
+        for name in ["instructor", "student"] {
+            auth.register(name, "Password123!", "Password123!").await.unwrap();
+        }
+        // An account that happens to be called admin gets nothing from its name
+        auth.storage.save_user(&User::with_cost("admin".to_string(), "Password123!", 4).unwrap()).unwrap();
+        auth.set_user_admin("instructor", true).unwrap();
+
+        assert!(!auth.is_admin());
+        for (name, expected) in [("admin", false), ("instructor", true), ("student", false)] {
+            auth.login(name, "Password123!").await.unwrap();
+            assert_eq!(auth.is_admin(), expected, "{}", name);
+        }
+
+        // Revokes apply to a running session, but never to yourself
+        auth.set_user_admin("student", true).unwrap();
+        assert!(auth.is_admin());
+        assert!(auth.set_user_admin("student", false).is_err());
+        auth.login("instructor", "Password123!").await.unwrap();
+        auth.set_user_admin("student", false).unwrap();
+        assert!(auth.set_user_admin("ghost", true).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_usernames_ignore_case_and_spaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        let mut auth = AuthSystem::with_storage(storage, config);
+
+        let user = auth.register(" Ghost_Byte ", "Password123!", "Password123!").await.unwrap();
+        assert_eq!(user.username, "Ghost_Byte");
+        assert!(auth.register("ghost_byte", "Password123!", "Password123!").await.is_err());
+        assert!(auth.register("GHOST_BYTE ", "Password123!", "Password123!").await.is_err());
+
+        let user = auth.login("ghost_BYTE ", "Password123!").await.unwrap();
+        assert_eq!(user.username, "Ghost_Byte");
+        assert!(auth.delete_user("GHOST_byte").is_err());
+        assert_eq!(auth.list_users().unwrap(), vec!["Ghost_Byte".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_reserved_names_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        config.security.reserved_usernames.push("crimson".to_string());
+        let mut auth = AuthSystem::with_storage(storage, config);
+
+        for name in ["root", "Admin", "SYSTEM", "crimson"] {
+            let err = auth.register(name, "Password123!", "Password123!").await.unwrap_err();
+            assert!(err.to_string().contains("reserved"), "{}", name);
+        }
+        assert!(auth.register("rooter", "Password123!", "Password123!").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rename_follows_registration_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        let mut auth = AuthSystem::with_storage(storage, config);
+        auth.register("ghsot", "Password123!", "Password123!").await.unwrap();
+        auth.register("taken", "Password123!", "Password123!").await.unwrap();
+        auth.login("ghsot", "Password123!").await.unwrap();
+
+        for bad in ["TAKEN", "root", "no", "two words", "ghsot"] {
+            assert!(auth.rename_current_user(bad).is_err(), "{}", bad);
+        }
+        assert_eq!(auth.rename_current_user("Ghsot").unwrap().username, "Ghsot");
+        let renamed = auth.rename_current_user(" ghost ").unwrap();
+        assert_eq!(renamed.username, "ghost");
+        assert_eq!(auth.current_user().unwrap().username, "ghost");
+
+        auth.logout();
+        assert!(auth.login("ghsot", "Password123!").await.is_err());
+        assert_eq!(auth.login("ghost", "Password123!").await.unwrap().login_count, 2);
+        assert_eq!(auth.list_users().unwrap(), vec!["ghost", "taken"]);
+    }
+
+    #[tokio::test]
+    async fn test_agents_can_delete_themselves() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        let mut auth = AuthSystem::with_storage(storage, config);
+        auth.register("burner", "Password123!", "Password123!").await.unwrap();
+        assert!(auth.delete_current_user("Password123!").is_err());
+
+        auth.login("burner", "Password123!").await.unwrap();
+        assert!(auth.delete_user("burner").is_err());
+        assert!(auth.delete_current_user("wrong").is_err());
+        assert!(auth.is_authenticated());
+
+        assert_eq!(auth.delete_current_user("Password123!").unwrap(), "burner");
+        assert!(!auth.is_authenticated());
+        assert!(auth.login("burner", "Password123!").await.is_err());
+        assert!(auth.list_users().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deleted_agents_leave_nothing_for_the_next_owner() {
+        use crate::game::{GameState, SaveGame};
+        use crate::utils::history::CommandHistory;
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        let mut auth = AuthSystem::with_storage(storage, config);
+
+        let name = "deleted_agent_922";
+        auth.register(name, "Password123!", "Password123!").await.unwrap();
+        SaveGame::new(GameState::new(name.to_string(), 500)).save_for_user().unwrap();
+        CommandHistory::load_for_user(name).unwrap().push("exploit 10.0.0.1").unwrap();
+
+        auth.delete_user(name).unwrap();
+        auth.register(name, "Password123!", "Password123!").await.unwrap();
+        assert!(SaveGame::load_for_user(name).unwrap().is_none());
+        assert!(CommandHistory::load_for_user(name).unwrap().entries().is_empty());
+        auth.delete_user(name).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_previous_login_is_remembered() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        let mut auth = AuthSystem::with_storage(storage, config);
+        auth.register("agent", "Password123!", "Password123!").await.unwrap();
+
+        let first = auth.login("agent", "Password123!").await.unwrap();
+        assert!(auth.previous_login().is_none());
+        assert_eq!(first.last_login_host, Some(login_host()));
+
+        auth.logout();
+        auth.login("agent", "Password123!").await.unwrap();
+        let previous = auth.previous_login().unwrap();
+        assert_eq!(Some(previous.at), first.last_login);
+        assert_eq!(previous.host, Some(login_host()));
+    }
+
+    #[test]
+    fn test_login_host_prefers_ssh() {
+        assert_eq!(describe_host(Some("203.0.113.7 52144 22"), Some("lab-04")), "203.0.113.7");
+        assert_eq!(describe_host(None, Some("lab-04")), "lab-04");
+        assert_eq!(describe_host(Some(""), Some("  ")), "local");
+        assert_eq!(describe_host(None, None), "local");
+    }
+
+    #[tokio::test]
+    async fn test_two_factor_login_needs_a_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        config.security.enable_2fa = true;
+        let mut auth = AuthSystem::with_storage(storage, config);
+        auth.register("agent", "Password123!", "Password123!").await.unwrap();
+        auth.login("agent", "Password123!").await.unwrap();
+
+        let secret = totp::generate_secret();
+        assert!(auth.enable_two_factor(&secret, "abcdef").is_err());
+        let code = totp::code_at(&secret, totp::now()).unwrap();
+        auth.enable_two_factor(&secret, &code).unwrap();
+        auth.logout();
+
+        let err = auth.login("agent", "Password123!").await.unwrap_err();
+        assert!(err.is::<CodeRequired>());
+        assert!(auth.login("agent", "wrong").await.is_err_and(|e| !e.is::<CodeRequired>()));
+        // The code that turned 2FA on can't be replayed, but the next window's can be used once
+        assert!(auth.login_with_code("agent", "Password123!", Some(&code)).await.is_err());
+        let next = totp::code_at(&secret, totp::now() + totp::STEP_SECS).unwrap();
+        auth.login_with_code("agent", "Password123!", Some(&next)).await.unwrap();
+        auth.logout();
+        assert!(auth.login_with_code("agent", "Password123!", Some(&next)).await.is_err());
+
+        // Turning the feature off later doesn't drop 2FA for enrolled agents
+        auth.config.security.enable_2fa = false;
+        assert!(auth.login("agent", "Password123!").await.unwrap_err().is::<CodeRequired>());
+        // Every code in the window is spent, so stand in for a logged-in session
+        auth.current_user = auth.storage.load_user("agent").unwrap();
+        assert!(auth.disable_two_factor("wrong").is_err());
+        auth.disable_two_factor("Password123!").unwrap();
+        auth.logout();
+        auth.login("agent", "Password123!").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_admin_unlock_lifts_a_lockout() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage::UserStorage::open(dir.path().join("users.db")).unwrap();
+        let mut config = crate::utils::Config::default();
+        config.security.bcrypt_cost = 4;
+        let mut auth = AuthSystem::with_storage(storage, config);
+        auth.register("student", "Password123!", "Password123!").await.unwrap();
+        for _ in 0..3 {
+            assert!(auth.login("student", "wrong").await.is_err());
+        }
+        assert!(auth.is_user_locked("student"));
+
+        auth.set_user_locked("student", false).unwrap();
+        assert!(auth.login("student", "Password123!").await.is_ok());
+        assert!(auth.set_user_locked("student", true).is_err());
+        auth.logout();
+        auth.set_user_locked("student", true).unwrap();
+        assert!(auth.login("student", "Password123!").await.is_err());
+        assert!(auth.set_user_locked("ghost", false).is_err());
+    }
+
+    #[test]
+    fn test_lockout_cooldown_escalates_and_expires() {
+        let mut user = User::new("testuser".to_string(), "Password123!").unwrap();
+
+        let mut cooldowns = Vec::new();
+        for _ in 0..4 {
+            for _ in 0..3 {
+                user.record_failed_attempt_with_limit(3);
+            }
+            cooldowns.push(user.lockout_remaining().unwrap().num_seconds());
+        }
+        assert!(cooldowns[0] <= 30 && cooldowns[0] > 25);
+        assert!(cooldowns[1] > 100 && cooldowns[2] > 500);
+        assert!(cooldowns[3] <= 600);
+
+        // Once the time passes the account opens up on its own
+        user.locked_until = Some(Utc::now() - chrono::Duration::seconds(1));
+        assert!(!user.is_locked());
+    }
+
+    #[tokio::test]
+    async fn test_failed_attempts_persist_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("users.db");
+        let open = || {
+            let storage = storage::UserStorage::open(db_path.clone()).unwrap();
+            AuthSystem::with_storage(storage, crate::utils::Config::default())
+        };
+
+        let mut auth = open();
+        auth.register("agent", "Password123!", "Password123!").await.unwrap();
+        assert!(auth.login("agent", "wrong").await.is_err());
+        assert!(auth.login("agent", "wrong").await.is_err());
+        drop(auth);
+
+        // The counter survives a restart, so the third failure locks the account
+        let mut auth = open();
+        assert!(!auth.is_user_locked("agent"));
+        let err = auth.login("agent", "wrong").await.unwrap_err();
+        assert!(err.to_string().contains("Try again in 30s"));
+        drop(auth);
+
+        let mut auth = open();
+        assert!(auth.is_user_locked("agent"));
+        assert!(auth.login("agent", "Password123!").await.is_err());
+    }
+}
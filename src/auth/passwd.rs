@@ -0,0 +1,48 @@
+//! In-session password change and account deletion for CRIMSON-REDLINE
+
+use super::AuthSystem;
+use crate::ui::ColorScheme;
+use crate::ui::menu;
+use anyhow::Result;
+
+/// Prompt for the current and new passwords and apply the change
+pub fn run(auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Result<()> {
+    println!();
+    // Each buffer is zeroed as soon as it goes out of scope
+    let current = menu::read_password("Current password", color_scheme)?;
+    if current.is_empty() {
+        color_scheme.print_dim("  Cancelled\n")?;
+        return Ok(());
+    }
+    let new = menu::read_password("New password", color_scheme)?;
+    let confirm = menu::read_password("Confirm new password", color_scheme)?;
+
+    match auth.change_password(&current, &new, &confirm) {
+        Ok(()) => color_scheme.print_success("  [✓] Password changed\n")?,
+        Err(e) => color_scheme.print_error(&format!("  [!] {}\n", e))?,
+    }
+    Ok(())
+}
+
+/// Confirm and delete the logged-in agent's account, returning the username if it went
+pub fn delete_account(auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Result<Option<String>> {
+    println!();
+    color_scheme.print_warning("  [!] This wipes your account, saves and history. There is no undo.\n")?;
+    if !menu::confirm("Delete your account?", color_scheme)? {
+        color_scheme.print_dim("  Cancelled\n")?;
+        return Ok(None);
+    }
+    let password = menu::read_password("Password", color_scheme)?;
+    if password.is_empty() {
+        color_scheme.print_dim("  Cancelled\n")?;
+        return Ok(None);
+    }
+
+    match auth.delete_current_user(&password) {
+        Ok(username) => Ok(Some(username)),
+        Err(e) => {
+            color_scheme.print_error(&format!("  [!] {}\n", e))?;
+            Ok(None)
+        }
+    }
+}
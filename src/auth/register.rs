@@ -2,6 +2,7 @@
 
 use crate::ui::{ColorScheme, animations};
 use crate::auth::AuthSystem;
+use crate::utils::secret::{password_buffer, PasswordBuffer, MAX_PASSWORD_LEN};
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent},
     terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
@@ -10,12 +11,13 @@ use crossterm::{
 };
 use std::io::{self, Write};
 use anyhow::Result;
+use zeroize::Zeroize;
 
 /// Registration screen state
 pub struct RegisterScreen {
     username: String,
-    password: String,
-    confirm_password: String,
+    password: PasswordBuffer,
+    confirm_password: PasswordBuffer,
     show_password: bool,
     input_mode: InputMode,
     error_message: Option<String>,
@@ -39,13 +41,19 @@ enum PasswordStrength {
     VeryStrong,
 }
 
+impl Default for RegisterScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RegisterScreen {
     /// Create a new registration screen
     pub fn new() -> Self {
         RegisterScreen {
             username: String::new(),
-            password: String::new(),
-            confirm_password: String::new(),
+            password: password_buffer(),
+            confirm_password: password_buffer(),
             show_password: false,
             input_mode: InputMode::Username,
             error_message: None,
@@ -139,18 +147,17 @@ impl RegisterScreen {
         let password_display = if self.show_password {
             self.password.clone()
         } else {
-            "*".repeat(self.password.len())
+            PasswordBuffer::new("*".repeat(self.password.chars().count()))
         };
+        let password_cursor = if self.input_mode == InputMode::Password { "█" } else { "" };
         
-        color_scheme.print_colored(&format!(
-            "   {}PASSWORD: {}\n",
+        // The formatted line may hold the plaintext, so it is zeroed too
+        color_scheme.print_colored(&PasswordBuffer::new(format!(
+            "   {}PASSWORD: {}{}\n",
             password_prefix,
-            if self.input_mode == InputMode::Password {
-                format!("{}█", password_display)
-            } else {
-                password_display
-            }
-        ))?;
+            password_display.as_str(),
+            password_cursor
+        )))?;
         
         // Show password strength
         if !self.password.is_empty() {
@@ -171,18 +178,17 @@ impl RegisterScreen {
         let confirm_display = if self.show_password {
             self.confirm_password.clone()
         } else {
-            "*".repeat(self.confirm_password.len())
+            PasswordBuffer::new("*".repeat(self.confirm_password.chars().count()))
         };
+        let confirm_cursor = if self.input_mode == InputMode::ConfirmPassword { "█" } else { "" };
         
-        color_scheme.print_colored(&format!(
-            "   {}CONFIRM PASSWORD: {}\n",
+        // The formatted line may hold the plaintext, so it is zeroed too
+        color_scheme.print_colored(&PasswordBuffer::new(format!(
+            "   {}CONFIRM PASSWORD: {}{}\n",
             confirm_prefix,
-            if self.input_mode == InputMode::ConfirmPassword {
-                format!("{}█", confirm_display)
-            } else {
-                confirm_display
-            }
-        ))?;
+            confirm_display.as_str(),
+            confirm_cursor
+        )))?;
         
         // Show password match status
         if !self.confirm_password.is_empty() {
@@ -235,14 +241,14 @@ impl RegisterScreen {
             KeyCode::Backspace => {
                 match self.input_mode {
                     InputMode::Username => {
-                        self.username.pop();
+                        crate::ui::pop_char(&mut self.username);
                     }
                     InputMode::Password => {
-                        self.password.pop();
+                        crate::ui::pop_char(&mut self.password);
                         self.password_strength = Self::calculate_password_strength(&self.password);
                     }
                     InputMode::ConfirmPassword => {
-                        self.confirm_password.pop();
+                        crate::ui::pop_char(&mut self.confirm_password);
                     }
                 }
                 self.error_message = None;
@@ -256,13 +262,13 @@ impl RegisterScreen {
                         }
                     }
                     InputMode::Password => {
-                        if self.password.len() < 50 {
+                        if self.password.len() < MAX_PASSWORD_LEN {
                             self.password.push(c);
                             self.password_strength = Self::calculate_password_strength(&self.password);
                         }
                     }
                     InputMode::ConfirmPassword => {
-                        if self.confirm_password.len() < 50 {
+                        if self.confirm_password.len() < MAX_PASSWORD_LEN {
                             self.confirm_password.push(c);
                         }
                     }
@@ -307,21 +313,21 @@ impl RegisterScreen {
     }
 
     /// Process registration attempt
-pub async fn attempt_register(&mut self, auth: &mut AuthSystem) -> Result<bool> {
+pub async fn attempt_register(&mut self, auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Result<bool> {
     // Show loading animation
-    animations::show_processing("CREATING AGENT PROFILE", 2000).await?;
+    animations::show_processing("CREATING AGENT PROFILE", 2000, color_scheme).await?;
     
     match auth.register(&self.username, &self.password, &self.confirm_password).await {
         Ok(user) => {
             // Clear sensitive data
-            self.password.clear();
-            self.confirm_password.clear();
+            self.password.zeroize();
+            self.confirm_password.zeroize();
             
             // Show success message
             animations::show_success(&format!(
                 "AGENT {} SUCCESSFULLY CREATED - PROCEED TO LOGIN",
                 user.username
-            )).await?;
+            ), color_scheme).await?;
             
             // Don't set success_message here - just return true to exit
             // self.success_message = Some(format!("Agent {} created successfully!", user.username));
@@ -337,8 +343,8 @@ pub async fn attempt_register(&mut self, auth: &mut AuthSystem) -> Result<bool>
     /// Clear all fields
     pub fn clear(&mut self) {
         self.username.clear();
-        self.password.clear();
-        self.confirm_password.clear();
+        self.password.zeroize();
+        self.confirm_password.zeroize();
         self.show_password = false;
         self.input_mode = InputMode::Username;
         self.error_message = None;
@@ -374,8 +380,11 @@ pub async fn run_registration(auth: &mut AuthSystem, color_scheme: &ColorScheme)
     let result = loop {
         // Wait for a single key event
         let key = loop {
-            if let Ok(Event::Key(k)) = event::read() {
-                break k;
+            match event::read() {
+                Ok(Event::Key(k)) => break k,
+                // The screen clears before drawing, so a redraw fits the new size
+                Ok(Event::Resize(..)) => register_screen.display(color_scheme).await?,
+                _ => {}
             }
         };
         
@@ -386,10 +395,10 @@ pub async fn run_registration(auth: &mut AuthSystem, color_scheme: &ColorScheme)
                 register_screen.display(color_scheme).await?;
             }
             RegisterAction::AttemptRegister => {
-                let success = register_screen.attempt_register(auth).await?;
+                let success = register_screen.attempt_register(auth, color_scheme).await?;
                 if success {
                     // Show success for 2 seconds then exit
-                    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                    crate::ui::animations::pause(tokio::time::Duration::from_secs(2), color_scheme).await;
                     
                     // Exit the registration screen
                     break true;
@@ -14,6 +14,70 @@ struct UserDatabase {
     version: u32,
 }
 
+/// Current on-disk database format
+const DB_VERSION: u32 = 2;
+
+/// Database format before timed lockouts
+const DB_VERSION_V1: u32 = 1;
+
+/// Users as stored before timed lockouts (database version 1)
+#[derive(Deserialize)]
+struct UserV1 {
+    username: String,
+    password_hash: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    last_login: Option<chrono::DateTime<chrono::Utc>>,
+    login_count: u32,
+    reputation: i32,
+    is_active: bool,
+    failed_attempts: u32,
+}
+
+/// Failures after which version 1 locked an account for good
+const V1_LOCKOUT_ATTEMPTS: u32 = 5;
+
+impl From<UserV1> for User {
+    fn from(old: UserV1) -> Self {
+        let mut user = User {
+            username: old.username,
+            password_hash: old.password_hash,
+            created_at: old.created_at,
+            last_login: old.last_login,
+            login_count: old.login_count,
+            reputation: old.reputation,
+            is_active: old.is_active,
+            failed_attempts: old.failed_attempts,
+            locked_until: None,
+            lockouts: 0,
+            last_login_host: None,
+            password_history: Vec::new(),
+            totp_secret: None,
+            totp_last_step: None,
+            is_admin: false,
+        };
+        // Version 1 locked accounts for good after too many failures; give
+        // them the first cooldown instead
+        if old.failed_attempts >= V1_LOCKOUT_ATTEMPTS {
+            user.unlock();
+            user.locked_until = Some(chrono::Utc::now() + chrono::Duration::seconds(super::LOCKOUT_COOLDOWNS_SECS[0]));
+            user.lockouts = 1;
+        }
+        user
+    }
+}
+
+/// Version 1 database layout
+#[derive(Deserialize)]
+struct UserDatabaseV1 {
+    users: HashMap<String, UserV1>,
+}
+
+/// Form a username is compared and keyed in: trimmed and lowercased, so
+/// "Admin" and "admin " can't both be registered
+pub fn canonical_username(username: &str) -> String {
+    username.trim().to_lowercase()
+}
+
 /// User storage handler
 pub struct UserStorage {
     db_path: PathBuf,
@@ -24,15 +88,18 @@ impl UserStorage {
     /// Create a new user storage instance
     pub fn new() -> Result<Self> {
         let data_dir = crate::utils::get_data_dir()?;
-        let db_path = data_dir.join(crate::USER_DB_FILE);
-        
+        Self::open(data_dir.join(crate::USER_DB_FILE))
+    }
+
+    /// Open user storage backed by a specific database file
+    pub fn open(db_path: PathBuf) -> Result<Self> {
         // Load existing database or create new one
         let database = if db_path.exists() {
-            Self::load_database(&db_path)?
+            Self::canonicalize_keys(Self::load_database(&db_path)?)
         } else {
             UserDatabase {
                 users: HashMap::new(),
-                version: 1,
+                version: DB_VERSION,
             }
         };
         
@@ -46,15 +113,57 @@ impl UserStorage {
     fn load_database(path: &PathBuf) -> Result<UserDatabase> {
         let data = fs::read(path)?;
         
-        // Try to deserialize with bincode first (more secure)
-        if let Ok(db) = bincode::deserialize::<UserDatabase>(&data) {
+        // Fallback to JSON (for compatibility with hand-edited or exported files)
+        if data.first() == Some(&b'{') {
+            let json_str = String::from_utf8(data)?;
+            let db: UserDatabase = serde_json::from_str(&json_str)?;
             return Ok(db);
         }
         
-        // Fallback to JSON if bincode fails (for compatibility)
-        let json_str = String::from_utf8(data)?;
-        let db: UserDatabase = serde_json::from_str(&json_str)?;
-        Ok(db)
+        // The version is the last field, so bincode leaves it in the last four bytes
+        let version = data
+            .len()
+            .checked_sub(4)
+            .map(|start| u32::from_le_bytes([data[start], data[start + 1], data[start + 2], data[start + 3]]))
+            .ok_or_else(|| anyhow::anyhow!("user database is truncated"))?;
+        
+        match version {
+            DB_VERSION => Ok(bincode::deserialize::<UserDatabase>(&data)?),
+            DB_VERSION_V1 => {
+                let old: UserDatabaseV1 = bincode::deserialize(&data)?;
+                Ok(UserDatabase {
+                    users: old.users.into_iter().map(|(name, user)| (name, user.into())).collect(),
+                    version: DB_VERSION,
+                })
+            }
+            other => anyhow::bail!("unsupported user database version {}", other),
+        }
+    }
+
+    /// Re-key users saved under their exact names; near-duplicates from before
+    /// keys were canonical keep their exact key so neither account is lost
+    fn canonicalize_keys(database: UserDatabase) -> UserDatabase {
+        let (canonical, mut exact): (Vec<_>, Vec<_>) = database.users
+            .into_iter()
+            .partition(|(key, _)| *key == canonical_username(key));
+        exact.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut users: HashMap<String, User> = canonical.into_iter().collect();
+        for (key, user) in exact {
+            let canonical = canonical_username(&key);
+            let key = if users.contains_key(&canonical) { key } else { canonical };
+            users.insert(key, user);
+        }
+        UserDatabase { users, version: database.version }
+    }
+
+    /// Key an existing user is stored under, or the canonical key for a new one
+    fn key_for(&self, username: &str) -> String {
+        if self.database.users.contains_key(username) {
+            username.to_string()
+        } else {
+            canonical_username(username)
+        }
     }
 
     /// Save database to file
@@ -77,24 +186,24 @@ impl UserStorage {
 
     /// Check if a user exists
     pub fn user_exists(&self, username: &str) -> Result<bool> {
-        Ok(self.database.users.contains_key(username))
+        Ok(self.database.users.contains_key(&self.key_for(username)))
     }
 
     /// Save a user to storage
     pub fn save_user(&mut self, user: &User) -> Result<()> {
-        self.database.users.insert(user.username.clone(), user.clone());
+        self.database.users.insert(self.key_for(&user.username), user.clone());
         self.save_database()?;
         Ok(())
     }
 
     /// Load a user from storage
     pub fn load_user(&self, username: &str) -> Result<Option<User>> {
-        Ok(self.database.users.get(username).cloned())
+        Ok(self.database.users.get(&self.key_for(username)).cloned())
     }
 
     /// Delete a user from storage
     pub fn delete_user(&mut self, username: &str) -> Result<()> {
-        if self.database.users.remove(username).is_some() {
+        if self.database.users.remove(&self.key_for(username)).is_some() {
             self.save_database()?;
             Ok(())
         } else {
@@ -102,13 +211,57 @@ impl UserStorage {
         }
     }
 
+    /// Move a user to a new username, dropping the entry under the old one
+    pub fn rename_user(&mut self, old_username: &str, user: &User) -> Result<()> {
+        if self.database.users.remove(&self.key_for(old_username)).is_none() {
+            anyhow::bail!("User '{}' not found", old_username);
+        }
+        self.database.users.insert(self.key_for(&user.username), user.clone());
+        self.save_database()
+    }
+
     /// List all usernames
     pub fn list_usernames(&self) -> Result<Vec<String>> {
-        let mut usernames: Vec<String> = self.database.users.keys().cloned().collect();
+        let mut usernames: Vec<String> = self.database.users.values().map(|u| u.username.clone()).collect();
         usernames.sort();
         Ok(usernames)
     }
 
+    /// Load every user, sorted by username
+    pub fn list_users(&self) -> Vec<User> {
+        let mut users: Vec<User> = self.database.users.values().cloned().collect();
+        users.sort_by(|a, b| a.username.cmp(&b.username));
+        users
+    }
+
+    /// Lock or unlock a user's account
+    pub fn set_locked(&mut self, username: &str, locked: bool) -> Result<()> {
+        let mut user = self.load_user(username)?
+            .ok_or_else(|| anyhow::anyhow!("User '{}' not found", username))?;
+        if locked {
+            user.lock();
+        } else {
+            user.unlock();
+        }
+        self.save_user(&user)
+    }
+
+    /// Grant or revoke a user's admin rights
+    pub fn set_admin(&mut self, username: &str, admin: bool) -> Result<()> {
+        let mut user = self.load_user(username)?
+            .ok_or_else(|| anyhow::anyhow!("User '{}' not found", username))?;
+        user.is_admin = admin;
+        self.save_user(&user)
+    }
+
+    /// Reset a user's reputation to zero
+    pub fn reset_reputation(&mut self, username: &str) -> Result<()> {
+        let mut user = self.load_user(username)?
+            .ok_or_else(|| anyhow::anyhow!("User '{}' not found", username))?;
+        user.reputation = 0;
+        self.save_user(&user)
+    }
+
     /// Get total number of users
     pub fn user_count(&self) -> usize {
         self.database.users.len()
@@ -191,6 +344,61 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_version_one_database_still_loads() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let db_path = temp_dir.path().join("old_users.db");
+        
+        // Same layout the version 1 User serialized to
+        let hash = bcrypt::hash("Password123!", 4)?;
+        let old_user = ("veteran".to_string(), hash, chrono::Utc::now(), None::<chrono::DateTime<chrono::Utc>>, 7u32, 450i32, true, 1u32);
+        let users: HashMap<String, _> = [("veteran".to_string(), old_user)].into_iter().collect();
+        fs::write(&db_path, bincode::serialize(&(users, 1u32))?)?;
+        
+        let storage = UserStorage::open(db_path)?;
+        let user = storage.load_user("veteran")?.unwrap();
+        assert_eq!(user.reputation, 450);
+        assert_eq!(user.login_count, 7);
+        assert!(user.verify_password("Password123!"));
+        assert!(user.locked_until.is_none());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_version_one_lockouts_become_cooldowns() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let db_path = temp_dir.path().join("old_users.db");
+        
+        // Version 1 deactivated the account for good on the fifth failure
+        let hash = bcrypt::hash("Password123!", 4)?;
+        let old_user = ("locked".to_string(), hash, chrono::Utc::now(), None::<chrono::DateTime<chrono::Utc>>, 0u32, 0i32, false, 5u32);
+        let users: HashMap<String, _> = [("locked".to_string(), old_user)].into_iter().collect();
+        fs::write(&db_path, bincode::serialize(&(users, 1u32))?)?;
+        
+        let storage = UserStorage::open(db_path)?;
+        let user = storage.load_user("locked")?.unwrap();
+        assert!(user.is_active);
+        assert!(user.lockout_remaining().is_some_and(|left| left.num_seconds() <= 30));
+        assert_eq!((user.failed_attempts, user.lockouts), (0, 1));
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_database_version_is_refused() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let db_path = temp_dir.path().join("users.db");
+        let users: HashMap<String, User> = HashMap::new();
+        
+        fs::write(&db_path, bincode::serialize(&(users, DB_VERSION + 1))?)?;
+        assert!(UserStorage::open(db_path.clone()).is_err());
+        fs::write(&db_path, [1u8, 0])?;
+        assert!(UserStorage::open(db_path).is_err());
+        
+        Ok(())
+    }
+
     #[test]
     fn test_user_storage_operations() -> Result<()> {
         let mut storage = create_test_storage()?;
@@ -219,6 +427,26 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_exact_keys_become_canonical_on_open() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let db_path = temp_dir.path().join("users.db");
+        let mut database = UserDatabase { users: HashMap::new(), version: DB_VERSION };
+        for name in ["Neo", "admin", "Admin"] {
+            database.users.insert(name.to_string(), User::with_cost(name.to_string(), "Password123!", 4)?);
+        }
+        fs::write(&db_path, bincode::serialize(&database)?)?;
+
+        let storage = UserStorage::open(db_path)?;
+        assert_eq!(storage.load_user("NEO")?.unwrap().username, "Neo");
+        assert_eq!(storage.load_user("ADMIN")?.unwrap().username, "admin");
+        // The near-duplicate is still reachable by its exact name
+        assert_eq!(storage.load_user("Admin")?.unwrap().username, "Admin");
+        assert_eq!(storage.list_usernames()?, vec!["Admin", "Neo", "admin"]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_storage_stats() -> Result<()> {
         let mut storage = create_test_storage()?;
@@ -241,4 +469,26 @@ mod tests {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_admin_lock_and_reset() -> Result<()> {
+        let mut storage = create_test_storage()?;
+        let mut user = User::new("student".to_string(), "Password123!")?;
+        user.reputation = 420;
+        storage.save_user(&user)?;
+
+        storage.set_locked("student", true)?;
+        assert!(storage.load_user("student")?.unwrap().is_locked());
+        assert_eq!(storage.get_stats().locked_users, 1);
+
+        storage.set_locked("student", false)?;
+        storage.reset_reputation("student")?;
+        let user = storage.load_user("student")?.unwrap();
+        assert!(!user.is_locked());
+        assert_eq!(user.reputation, 0);
+
+        assert!(storage.set_locked("ghost", true).is_err());
+        assert_eq!(storage.list_users().len(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+//! Optional two-factor login for CRIMSON-REDLINE
+//!
+//! Time-based one-time passwords (RFC 6238): six digits from an HMAC-SHA1 of
+//! the current 30-second window, so any authenticator app can generate them.
+//! Turning it on sits behind `security.enable_2fa`; agents who already have it
+//! keep needing codes if that flag is switched off later.
+
+use super::AuthSystem;
+use crate::ui::ColorScheme;
+use crate::ui::menu;
+use anyhow::Result;
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// Seconds each code is valid for
+pub const STEP_SECS: u64 = 30;
+
+/// Digits in a code
+pub const DIGITS: usize = 6;
+
+/// Random bytes in a new secret, the size authenticator apps expect for SHA-1
+const SECRET_BYTES: usize = 20;
+
+/// Name authenticator apps list the account under
+const ISSUER: &str = "CRIMSON-REDLINE";
+
+/// Usage line for the `2fa` command
+pub const COMMAND_USAGE: &str = "2fa <status|enable|disable>";
+
+/// A fresh base32 secret to share with an authenticator app
+pub fn generate_secret() -> String {
+    let bytes: [u8; SECRET_BYTES] = rand::random();
+    BASE32_NOPAD.encode(&bytes)
+}
+
+/// The code for the window containing `unix_secs`
+pub fn code_at(secret: &str, unix_secs: u64) -> Result<String> {
+    let normalized: String = secret.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+    let key = BASE32_NOPAD.decode(normalized.trim_end_matches('=').as_bytes())?;
+
+    let mut mac = Hmac::<Sha1>::new_from_slice(&key).map_err(|_| anyhow::anyhow!("invalid two-factor secret"))?;
+    mac.update(&(unix_secs / STEP_SECS).to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    // Dynamic truncation: the low nibble of the last byte picks four bytes to use
+    let offset = usize::from(hash[hash.len() - 1] & 0x0f);
+    let binary = u32::from_be_bytes([hash[offset] & 0x7f, hash[offset + 1], hash[offset + 2], hash[offset + 3]]);
+    Ok(format!("{:0width$}", binary % 10u32.pow(DIGITS as u32), width = DIGITS))
+}
+
+/// Time step `code` belongs to, checking one window either side of now for clock drift
+pub fn matching_step(secret: &str, code: &str, unix_secs: u64) -> Option<u64> {
+    let code = code.trim();
+    if code.len() != DIGITS || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    [unix_secs.saturating_sub(STEP_SECS), unix_secs, unix_secs + STEP_SECS]
+        .iter()
+        .find(|&&time| code_at(secret, time).is_ok_and(|expected| expected == code))
+        .map(|time| time / STEP_SECS)
+}
+
+/// Whether `code` is right for now and newer than the last accepted step, so a
+/// code can't be replayed (RFC 6238 §5.2); returns the step to remember
+pub fn verify(secret: &str, code: &str, unix_secs: u64, last_step: Option<u64>) -> Option<u64> {
+    matching_step(secret, code, unix_secs).filter(|step| last_step.is_none_or(|last| *step > last))
+}
+
+/// Current time as authenticator apps count it
+pub fn now() -> u64 {
+    u64::try_from(chrono::Utc::now().timestamp()).unwrap_or(0)
+}
+
+/// `otpauth://` link that authenticator apps can import
+pub fn provisioning_uri(username: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{issuer}:{user}?secret={secret}&issuer={issuer}&digits={digits}&period={period}",
+        issuer = ISSUER,
+        user = username,
+        secret = secret,
+        digits = DIGITS,
+        period = STEP_SECS,
+    )
+}
+
+/// Run a `2fa` subcommand for the logged-in agent
+pub fn run_command(auth: &mut AuthSystem, args: &[String], color_scheme: &ColorScheme) -> Result<()> {
+    // Agents who already turned it on can still check or disable it
+    let enrolled = auth.current_user().is_some_and(|u| u.totp_secret.is_some());
+    if !auth.two_factor_available() && !enrolled {
+        color_scheme.print_error("  [!] Two-factor login is turned off on this install (security.enable_2fa)\n")?;
+        return Ok(());
+    }
+
+    match args.first().map(|s| s.as_str()) {
+        Some("status") | None => {
+            if auth.current_user().is_some_and(|u| u.totp_secret.is_some()) {
+                color_scheme.print_success("  [✓] Two-factor login is on\n")?;
+            } else {
+                color_scheme.print_dim("  Two-factor login is off. Turn it on with '2fa enable'\n")?;
+            }
+            Ok(())
+        }
+        Some("enable") => enable(auth, color_scheme),
+        Some("disable") => disable(auth, color_scheme),
+        _ => {
+            color_scheme.print_error(&format!("  [!] Usage: {}\n", COMMAND_USAGE))?;
+            Ok(())
+        }
+    }
+}
+
+/// Show a new secret once and switch 2FA on after the agent proves their app has it
+fn enable(auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Result<()> {
+    let Some(user) = auth.current_user() else {
+        return Ok(());
+    };
+    if user.totp_secret.is_some() {
+        color_scheme.print_warning("  [!] Two-factor login is already on\n")?;
+        return Ok(());
+    }
+
+    let secret = generate_secret();
+    println!();
+    color_scheme.print_bright("  Add this secret to your authenticator app. It won't be shown again.\n")?;
+    color_scheme.print_colored("  Secret: ")?;
+    color_scheme.print_bright(&format!("{}\n", secret))?;
+    color_scheme.print_dim(&format!("  {}\n", provisioning_uri(&user.username, &secret)))?;
+    println!();
+
+    let code = menu::read_line("Code from your app", color_scheme)?;
+    if code.is_empty() {
+        color_scheme.print_dim("  Cancelled\n")?;
+        return Ok(());
+    }
+    match auth.enable_two_factor(&secret, &code) {
+        Ok(()) => color_scheme.print_success("  [✓] Two-factor login is on. You'll need a code at every login\n")?,
+        Err(e) => color_scheme.print_error(&format!("  [!] {}\n", e))?,
+    }
+    Ok(())
+}
+
+/// Switch 2FA off once the password checks out
+fn disable(auth: &mut AuthSystem, color_scheme: &ColorScheme) -> Result<()> {
+    println!();
+    let password = menu::read_password("Password", color_scheme)?;
+    if password.is_empty() {
+        color_scheme.print_dim("  Cancelled\n")?;
+        return Ok(());
+    }
+    match auth.disable_two_factor(&password) {
+        Ok(()) => color_scheme.print_success("  [✓] Two-factor login is off\n")?,
+        Err(e) => color_scheme.print_error(&format!("  [!] {}\n", e))?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// "12345678901234567890", the RFC 6238 SHA-1 test key
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_codes_match_the_rfc_vectors() {
+        // The RFC lists eight digits; authenticator apps show the last six
+        assert_eq!(code_at(RFC_SECRET, 59).unwrap(), "287082");
+        assert_eq!(code_at(RFC_SECRET, 1111111109).unwrap(), "081804");
+        assert_eq!(code_at(RFC_SECRET, 1234567890).unwrap(), "005924");
+        assert_eq!(code_at("gezd gnbv gy3t qojq gezd gnbv gy3t qojq", 59).unwrap(), "287082");
+        assert!(code_at("not base32!", 59).is_err());
+    }
+
+    #[test]
+    fn test_verify_allows_one_window_of_drift() {
+        let secret = generate_secret();
+        let now = 1_700_000_000;
+        let code = code_at(&secret, now).unwrap();
+
+        assert_eq!(verify(&secret, &code, now, None), Some(now / STEP_SECS));
+        assert!(verify(&secret, &format!(" {} ", code), now + STEP_SECS, None).is_some());
+        assert!(verify(&secret, &code, now + 3 * STEP_SECS, None).is_none());
+        assert!(verify(&secret, "12345", now, None).is_none());
+        assert!(verify(&secret, "abcdef", now, None).is_none());
+    }
+
+    #[test]
+    fn test_verify_refuses_replayed_steps() {
+        let secret = generate_secret();
+        let now = 1_700_000_000;
+        let step = now / STEP_SECS;
+
+        assert!(verify(&secret, &code_at(&secret, now).unwrap(), now, Some(step)).is_none());
+        assert!(verify(&secret, &code_at(&secret, now - STEP_SECS).unwrap(), now, Some(step)).is_none());
+        assert_eq!(verify(&secret, &code_at(&secret, now + STEP_SECS).unwrap(), now, Some(step)), Some(step + 1));
+    }
+}
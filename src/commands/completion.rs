@@ -0,0 +1,149 @@
+//! Tab completion for the CRIMSON-REDLINE prompt
+
+use crate::commands::CommandRegistry;
+
+/// Commands whose first argument is a target host
+const TARGET_COMMANDS: &[&str] = &["exploit", "trace", "firewall", "pivot", "map", "ping", "connect"];
+
+/// Outcome of pressing Tab
+#[derive(Debug, PartialEq)]
+pub struct Completion {
+    /// The input line after completion
+    pub line: String,
+    /// Every match, when the prefix was ambiguous and they should be listed
+    pub candidates: Vec<String>,
+}
+
+/// Matches being cycled through by repeated Tab presses
+#[derive(Debug)]
+struct Cycle {
+    /// Input before the word being completed
+    base: String,
+    matches: Vec<String>,
+    index: usize,
+    /// Line as last completed, so edits end the cycle
+    line: String,
+}
+
+/// Completes command names and target IPs
+#[derive(Debug)]
+pub struct Completer {
+    commands: Vec<String>,
+    target_commands: Vec<String>,
+    cycle: Option<Cycle>,
+}
+
+impl Completer {
+    /// Build a completer for every command name and alias in the registry
+    pub fn new(registry: &CommandRegistry) -> Self {
+        let names = |cmd: &crate::commands::CommandInfo| {
+            std::iter::once(cmd.name.clone()).chain(cmd.aliases.iter().cloned()).collect::<Vec<_>>()
+        };
+
+        let mut commands: Vec<String> = registry.all_commands().into_iter().flat_map(names).collect();
+        commands.sort();
+        commands.dedup();
+
+        let target_commands = TARGET_COMMANDS
+            .iter()
+            .filter_map(|name| registry.get_command(name))
+            .flat_map(names)
+            .collect();
+
+        Completer {
+            commands,
+            target_commands,
+            cycle: None,
+        }
+    }
+
+    /// Complete the last word of `line`; `targets` are the IPs known to the player
+    pub fn complete(&mut self, line: &str, targets: &[String]) -> Option<Completion> {
+        // Repeated Tab with no edits in between moves to the next match
+        if let Some(cycle) = self.cycle.as_mut().filter(|c| c.line == line) {
+            cycle.index = (cycle.index + 1) % cycle.matches.len();
+            cycle.line = format!("{}{}", cycle.base, cycle.matches[cycle.index]);
+            return Some(Completion { line: cycle.line.clone(), candidates: Vec::new() });
+        }
+        self.cycle = None;
+
+        let split = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let (base, word) = line.split_at(split);
+        let previous: Vec<&str> = base.split_whitespace().collect();
+
+        let pool: &[String] = match previous.as_slice() {
+            [] => &self.commands,
+            [command] if self.target_commands.iter().any(|c| c.eq_ignore_ascii_case(command)) => targets,
+            _ => return None,
+        };
+
+        let word = word.to_lowercase();
+        let mut matches: Vec<String> = pool.iter().filter(|c| c.to_lowercase().starts_with(&word)).cloned().collect();
+        matches.sort();
+        matches.dedup();
+
+        match matches.len() {
+            0 => None,
+            1 => Some(Completion {
+                line: format!("{}{} ", base, matches[0]),
+                candidates: Vec::new(),
+            }),
+            _ => {
+                let line = format!("{}{}", base, matches[0]);
+                self.cycle = Some(Cycle {
+                    base: base.to_string(),
+                    matches: matches.clone(),
+                    index: 0,
+                    line: line.clone(),
+                });
+                Some(Completion { line, candidates: matches })
+            }
+        }
+    }
+
+    /// Forget any cycle in progress, e.g. after another key is pressed
+    pub fn reset(&mut self) {
+        self.cycle = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completer() -> Completer {
+        Completer::new(&CommandRegistry::new())
+    }
+
+    #[test]
+    fn test_unique_command_prefix_completes() {
+        let mut completer = completer();
+        let completion = completer.complete("expl", &[]).unwrap();
+        assert_eq!(completion.line, "exploit ");
+        assert!(completion.candidates.is_empty());
+        assert_eq!(completer.complete("zzz", &[]), None);
+    }
+
+    #[test]
+    fn test_ambiguous_prefix_lists_and_cycles() {
+        let mut completer = completer();
+        let first = completer.complete("s", &[]).unwrap();
+        assert!(first.candidates.len() > 1);
+        assert!(first.candidates.iter().all(|c| c.starts_with('s')));
+        assert_eq!(first.line, first.candidates[0]);
+
+        let second = completer.complete(&first.line, &[]).unwrap();
+        assert_eq!(second.line, first.candidates[1]);
+        assert!(second.candidates.is_empty());
+    }
+
+    #[test]
+    fn test_targets_complete_for_exploit() {
+        let mut completer = completer();
+        let targets = vec!["10.0.0.5".to_string(), "192.168.1.20".to_string()];
+        assert_eq!(completer.complete("exploit 19", &targets).unwrap().line, "exploit 192.168.1.20 ");
+        assert_eq!(completer.complete("pwn 10", &targets).unwrap().line, "pwn 10.0.0.5 ");
+        assert_eq!(completer.complete("scan 10", &targets), None);
+        assert_eq!(completer.complete("exploit 10.0.0.5 e", &targets), None);
+    }
+}
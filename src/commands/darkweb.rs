@@ -0,0 +1,142 @@
+//! Dark web marketplace for CRIMSON-REDLINE
+
+use crate::game::ReputationLevel;
+
+/// An item listed on the marketplace
+#[derive(Debug, Clone)]
+pub struct MarketItem {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub price: i32,
+    /// Minimum rank before sellers will deal with the agent
+    pub required_level: ReputationLevel,
+    /// Tool added to the agent's kit on purchase
+    pub tool: &'static str,
+    /// What owning the tool does, shown in the listing
+    pub effect: &'static str,
+}
+
+impl MarketItem {
+    /// Whether an agent with this much reputation may buy the item
+    pub fn is_available(&self, reputation: i32) -> bool {
+        reputation >= self.required_level.reputation_requirement()
+    }
+}
+
+/// Tool granted by the zero-day exploit kit
+pub const ZERO_DAY_TOOL: &str = "zero-day-kit";
+
+/// Exploit success chance added while the zero-day kit is owned
+pub const ZERO_DAY_SUCCESS_BONUS: f32 = 0.2;
+
+/// Items for sale, unlocked in step with the reputation perk progression
+pub const CATALOG: &[MarketItem] = &[
+    MarketItem {
+        id: "creds",
+        name: "Stolen Credentials Pack",
+        price: 500,
+        required_level: ReputationLevel::Nobody,
+        tool: "stolen-creds",
+        effect: "Valid logins for a handful of corporate VPNs",
+    },
+    MarketItem {
+        id: "dbdump",
+        name: "Database Dump (Fortune 500)",
+        price: 2500,
+        required_level: ReputationLevel::Amateur,
+        tool: "db-dump",
+        effect: "Customer records to mine for password reuse",
+    },
+    MarketItem {
+        id: "malware",
+        name: "Custom Malware Framework",
+        price: 3000,
+        required_level: ReputationLevel::Competent,
+        tool: "malware-framework",
+        effect: "Modular implants for persistent access",
+    },
+    MarketItem {
+        id: "botnet",
+        name: "Botnet Access (10k nodes)",
+        price: 5000,
+        required_level: ReputationLevel::Skilled,
+        tool: "botnet",
+        effect: "Ten thousand compromised machines on call",
+    },
+    MarketItem {
+        id: "zeroday",
+        name: "Zero-Day Exploit Kit",
+        price: 1000,
+        required_level: ReputationLevel::Expert,
+        tool: ZERO_DAY_TOOL,
+        effect: "+20% exploit success against any target",
+    },
+];
+
+/// Look up a catalog item by id
+pub fn find_item(id: &str) -> Option<&'static MarketItem> {
+    CATALOG.iter().find(|item| item.id.eq_ignore_ascii_case(id))
+}
+
+/// Base price of the log cleanup service
+pub const CLEANUP_BASE_COST: i32 = 200;
+
+/// Heat removed by a single log cleanup
+pub const CLEANUP_HEAT_REDUCTION: f32 = 60.0;
+
+/// Heat gained when a TOR connection attempt fails
+pub const TOR_FAILURE_HEAT: f32 = 2.0;
+
+/// Chance the TOR connection drops, from 5% when cold up to 50% at max heat
+pub fn tor_failure_chance(heat_level: f32) -> f32 {
+    0.05 + heat_level.clamp(0.0, 100.0) / 100.0 * 0.45
+}
+
+/// Credit cost of a log cleanup, rising steeply with current heat
+pub fn cleanup_cost(heat_level: f32) -> i32 {
+    let heat = heat_level.clamp(0.0, 100.0);
+    CLEANUP_BASE_COST + (heat * heat / 4.0).round() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_cost_scales_with_heat() {
+        assert_eq!(cleanup_cost(0.0), CLEANUP_BASE_COST);
+        assert_eq!(cleanup_cost(50.0), 825);
+        assert_eq!(cleanup_cost(100.0), 2700);
+        assert!(cleanup_cost(80.0) > cleanup_cost(40.0) * 2);
+    }
+
+    #[test]
+    fn test_tor_failure_scales_with_heat() {
+        assert!((tor_failure_chance(0.0) - 0.05).abs() < f32::EPSILON);
+        assert!((tor_failure_chance(100.0) - 0.5).abs() < f32::EPSILON);
+        assert!(tor_failure_chance(80.0) > tor_failure_chance(20.0));
+        assert_eq!(tor_failure_chance(250.0), tor_failure_chance(100.0));
+    }
+
+    #[test]
+    fn test_nobody_cannot_buy_zero_day_kit() {
+        let kit = find_item("zeroday").unwrap();
+        assert!(!kit.is_available(0));
+        assert!(kit.is_available(ReputationLevel::Expert.reputation_requirement()));
+        assert!(find_item("creds").unwrap().is_available(0));
+    }
+
+    #[test]
+    fn test_catalog_ids_and_tools_are_unique() {
+        for (i, item) in CATALOG.iter().enumerate() {
+            assert!(CATALOG[i + 1..].iter().all(|other| other.id != item.id && other.tool != item.tool));
+        }
+        assert_eq!(find_item("ZERODAY").unwrap().tool, ZERO_DAY_TOOL);
+    }
+
+    #[test]
+    fn test_cleanup_cost_clamps_heat() {
+        assert_eq!(cleanup_cost(-10.0), cleanup_cost(0.0));
+        assert_eq!(cleanup_cost(150.0), cleanup_cost(100.0));
+    }
+}
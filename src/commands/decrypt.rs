@@ -3,6 +3,46 @@
 use anyhow::Result;
 use rand::Rng;
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// Time to brute-force a single character of a blob
+pub const DECRYPT_MS_PER_CHAR: u64 = 150;
+
+/// Characters pulled from a remote host per round trip
+pub const REMOTE_CHUNK_SIZE: usize = 16;
+
+/// Most bonus reputation a long decryption can earn
+pub const MAX_DECRYPT_BONUS: i32 = 20;
+
+/// Share of the usual decryption time needed when the right key is supplied
+pub const KEYED_DECRYPT_FACTOR: f32 = 0.25;
+
+/// Passwords a dictionary attack can recover; hashes and blobs map onto them deterministically
+const WORDLIST: &[&str] = &[
+    "password123",
+    "admin",
+    "letmein",
+    "qwerty123",
+    "P@ssw0rd",
+    "123456789",
+    "administrator",
+    "root",
+    "toor",
+    "changeme",
+];
+
+/// Expected decryption time, adding a round trip per chunk when the blob is remote
+pub fn decrypt_duration(length: usize, remote_latency_ms: Option<u64>) -> Duration {
+    let cracking = length as u64 * DECRYPT_MS_PER_CHAR;
+    let round_trips = length.div_ceil(REMOTE_CHUNK_SIZE) as u64;
+    let network = remote_latency_ms.map_or(0, |latency| latency * round_trips);
+    Duration::from_millis(cracking + network)
+}
+
+/// Reputation for a decryption, with a bonus of one point per two seconds of work
+pub fn decrypt_reputation(base: i32, duration: Duration) -> i32 {
+    base + (duration.as_secs() as i32 / 2).min(MAX_DECRYPT_BONUS)
+}
 
 /// Generate random encrypted data
 pub fn generate_encrypted_data() -> String {
@@ -21,14 +61,11 @@ pub fn generate_encrypted_data() -> String {
 }
 
 /// Decrypt data (simulated)
-pub fn decrypt_data(encrypted: &str) -> Result<String> {
+pub fn decrypt_data(_encrypted: &str) -> Result<String> {
     let mut rng = rand::thread_rng();
     
-    // Remove 0x prefix if present
-    let clean_data = encrypted.trim_start_matches("0x").trim_start_matches("0X");
-    
     // Simulate different types of decrypted content
-    let decryption_results = vec![
+    let decryption_results = [
         // Credentials
         vec![
             "admin:P@ssw0rd123!",
@@ -108,25 +145,26 @@ pub fn decrypt_data(encrypted: &str) -> Result<String> {
 pub async fn decrypt_file(filename: &str) -> Result<FileContent> {
     let mut rng = rand::thread_rng();
     
-    let file_type = determine_file_type(filename);
-    
-    let content = match file_type {
-        FileType::Document => generate_document_content(),
-        FileType::Database => generate_database_content(),
-        FileType::Config => generate_config_content(),
-        FileType::Log => generate_log_content(),
-        FileType::Binary => generate_binary_description(),
-    };
-    
     Ok(FileContent {
         filename: filename.to_string(),
-        file_type,
+        file_type: determine_file_type(filename),
         size: rng.gen_range(1024..10485760), // 1KB to 10MB
-        content,
+        content: generate_file_content(filename),
         metadata: generate_metadata(),
     })
 }
 
+/// Generate plausible contents for a file, going by its name
+pub fn generate_file_content(filename: &str) -> String {
+    match determine_file_type(filename) {
+        FileType::Document => generate_document_content(),
+        FileType::Database => generate_database_content(),
+        FileType::Config => generate_config_content(),
+        FileType::Log => generate_log_content(),
+        FileType::Binary => generate_binary_description(),
+    }
+}
+
 /// File content structure
 #[derive(Debug)]
 pub struct FileContent {
@@ -147,6 +185,33 @@ pub enum FileType {
     Binary,
 }
 
+/// Whether a decrypt argument names a file rather than raw data
+pub fn looks_like_file(arg: &str) -> bool {
+    if arg.starts_with("0x") {
+        return false;
+    }
+    let has_extension = arg
+        .rsplit_once('.')
+        .is_some_and(|(stem, ext)| !stem.is_empty() && !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()));
+    has_extension || arg.contains('/') || arg.contains('\\')
+}
+
+/// Human-readable file size
+pub fn format_size(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
 /// Determine file type from filename
 fn determine_file_type(filename: &str) -> FileType {
     if filename.ends_with(".txt") || filename.ends_with(".doc") || filename.ends_with(".pdf") {
@@ -165,7 +230,7 @@ fn determine_file_type(filename: &str) -> FileType {
 /// Generate document content
 fn generate_document_content() -> String {
     let mut rng = rand::thread_rng();
-    let documents = vec![
+    let documents = [
         "CONFIDENTIAL MEMO\n\nTo: All Staff\nFrom: Security Team\nRe: Password Policy Update\n\nEffective immediately, all passwords must be changed to comply with new security standards.",
         "PROJECT PHOENIX - STATUS REPORT\n\nPhase 1: Complete\nPhase 2: 75% Complete\nPhase 3: Pending Authorization\n\nBudget Status: $2.3M remaining\nDeadline: Q4 2024",
         "INCIDENT REPORT #2024-0847\n\nDate: 2024-03-15\nSeverity: CRITICAL\nAffected Systems: Database Cluster A\nRoot Cause: Unauthorized Access Attempt\nStatus: Resolved",
@@ -215,7 +280,7 @@ fn generate_log_content() -> String {
     let mut rng = rand::thread_rng();
     let mut logs = String::new();
     
-    for i in 0..10 {
+    for _ in 0..10 {
         let level = ["INFO", "WARN", "ERROR", "DEBUG"][rng.gen_range(0..4)];
         let hour = rng.gen_range(0..24);
         let min = rng.gen_range(0..60);
@@ -287,6 +352,31 @@ fn generate_metadata() -> HashMap<String, String> {
     metadata
 }
 
+/// The key protecting a hash or blob, as recovered by `crack`
+pub fn key_for(data: &str) -> &'static str {
+    WORDLIST[(crate::utils::stable_hash(data) % WORDLIST.len() as u64) as usize]
+}
+
+/// Look up a cipher by name, ignoring case
+pub fn find_cipher(name: &str) -> Option<(&'static str, &'static str)> {
+    get_cipher_types().into_iter().find(|(cipher, _)| cipher.eq_ignore_ascii_case(name))
+}
+
+/// How long a cipher takes to break relative to an unidentified one
+pub fn cipher_work_factor(cipher: &str) -> f32 {
+    match cipher {
+        "AES-256" => 1.5,
+        "RSA-4096" => 2.0,
+        "ChaCha20" => 1.3,
+        "3DES" => 0.7,
+        "Blowfish" => 0.8,
+        "Twofish" => 1.2,
+        "Serpent" => 1.4,
+        "Camellia" => 1.1,
+        _ => 1.0,
+    }
+}
+
 /// Generate cipher types
 pub fn get_cipher_types() -> Vec<(&'static str, &'static str)> {
     vec![
@@ -301,27 +391,33 @@ pub fn get_cipher_types() -> Vec<(&'static str, &'static str)> {
     ]
 }
 
-/// Crack password hash (simulated)
-pub async fn crack_hash(hash: &str) -> Result<String> {
+/// Generate a random password hash to crack
+pub fn generate_hash() -> String {
+    let mut rng = rand::thread_rng();
+    let length = [32, 40, 64][rng.gen_range(0..3)];
+    (0..length).map(|_| char::from_digit(rng.gen_range(0..16), 16).unwrap()).collect()
+}
+
+/// Guess a hash's algorithm from its shape
+pub fn identify_hash(hash: &str) -> &'static str {
+    let is_hex = hash.chars().all(|c| c.is_ascii_hexdigit());
+    match hash.len() {
+        _ if hash.starts_with("$2") => "bcrypt",
+        32 if is_hex => "MD5",
+        40 if is_hex => "SHA-1",
+        64 if is_hex => "SHA-256",
+        _ => "unknown",
+    }
+}
+
+/// Crack password hash (simulated), taking `pace` times the usual time
+pub async fn crack_hash(hash: &str, pace: u32) -> Result<String> {
     let mut rng = rand::thread_rng();
     
     // Simulate cracking time
-    tokio::time::sleep(tokio::time::Duration::from_millis(rng.gen_range(1000..3000))).await;
-    
-    let passwords = vec![
-        "password123",
-        "admin",
-        "letmein",
-        "qwerty123",
-        "P@ssw0rd",
-        "123456789",
-        "administrator",
-        "root",
-        "toor",
-        "changeme",
-    ];
+    tokio::time::sleep(tokio::time::Duration::from_millis(rng.gen_range(1000..3000)) * pace).await;
     
-    Ok(passwords[rng.gen_range(0..passwords.len())].to_string())
+    Ok(key_for(hash).to_string())
 }
 
 #[cfg(test)]
@@ -335,6 +431,24 @@ mod tests {
         assert!(encrypted.len() >= 18); // "0x" + at least 16 chars
     }
 
+    #[test]
+    fn test_decrypt_duration() {
+        assert_eq!(decrypt_duration(0, None), Duration::ZERO);
+        assert_eq!(decrypt_duration(10, None), Duration::from_millis(1500));
+        
+        // 40 chars over a 200ms link: 3 round trips
+        assert_eq!(decrypt_duration(40, Some(200)), Duration::from_millis(6000 + 600));
+        assert!(decrypt_duration(64, Some(500)) > decrypt_duration(64, Some(50)));
+        assert!(decrypt_duration(64, None) > decrypt_duration(16, None));
+    }
+
+    #[test]
+    fn test_decrypt_reputation_bonus() {
+        assert_eq!(decrypt_reputation(10, Duration::from_millis(1500)), 10);
+        assert_eq!(decrypt_reputation(10, Duration::from_secs(9)), 14);
+        assert_eq!(decrypt_reputation(10, Duration::from_secs(600)), 10 + MAX_DECRYPT_BONUS);
+    }
+
     #[test]
     fn test_decrypt_data() {
         let encrypted = "0x4142434445464748";
@@ -343,6 +457,22 @@ mod tests {
         assert!(!result.unwrap().is_empty());
     }
 
+    #[test]
+    fn test_looks_like_file() {
+        assert!(looks_like_file("passwords.xlsx"));
+        assert!(looks_like_file("/etc/shadow"));
+        assert!(!looks_like_file("0x4142434445464748"));
+        assert!(!looks_like_file("SGVsbG8="));
+        assert!(!looks_like_file("v1.2."));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(10 * 1024 * 1024), "10.0 MB");
+    }
+
     #[test]
     fn test_determine_file_type() {
         assert!(matches!(determine_file_type("test.txt"), FileType::Document));
@@ -360,11 +490,28 @@ mod tests {
         assert!(metadata.contains_key("permissions"));
     }
 
+    #[test]
+    fn test_keys_and_ciphers() {
+        assert_eq!(key_for("0xDEADBEEF"), key_for("0xDEADBEEF"));
+        assert!(WORDLIST.contains(&key_for("anything")));
+        assert_eq!(find_cipher("aes-256"), Some(("AES-256", "Advanced Encryption Standard (256-bit)")));
+        assert_eq!(find_cipher("rot13"), None);
+        assert!(cipher_work_factor("RSA-4096") > cipher_work_factor("3DES"));
+    }
+
+    #[test]
+    fn test_identify_hash() {
+        assert_eq!(identify_hash("5f4dcc3b5aa765d61d8327deb882cf99"), "MD5");
+        assert_eq!(identify_hash("$2b$12$abcdefghijklmnopqrstuv"), "bcrypt");
+        assert_eq!(identify_hash("not-a-hash"), "unknown");
+        assert_ne!(identify_hash(&generate_hash()), "unknown");
+    }
+
     #[tokio::test]
     async fn test_crack_hash() {
         let hash = "5f4dcc3b5aa765d61d8327deb882cf99";
-        let result = crack_hash(hash).await;
+        let result = crack_hash(hash, 1).await;
         assert!(result.is_ok());
-        assert!(!result.unwrap().is_empty());
+        assert_eq!(result.unwrap(), key_for(hash));
     }
 }
\ No newline at end of file
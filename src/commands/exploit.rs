@@ -1,5 +1,7 @@
 //! Exploit execution simulation for CRIMSON-REDLINE
 
+use crate::commands::darkweb;
+use crate::game::state::NetworkNode;
 use crate::ui::{ColorScheme, animations};
 use anyhow::Result;
 use rand::Rng;
@@ -36,37 +38,20 @@ pub struct Exploit {
     pub required_port: Option<u16>,
 }
 
-/// Execute an exploit
-pub async fn execute_exploit(target: &str, vuln_id: &str, color_scheme: &ColorScheme) -> Result<ExploitResult> {
+/// Execute an exploit; the staged lead-up is shown by `animations::exploit_animation`
+pub async fn execute_exploit(exploit: &Exploit, pace: u32, color_scheme: &ColorScheme) -> Result<ExploitResult> {
     println!();
-    color_scheme.print_colored(&format!("  [>] Targeting: {}\n", target))?;
-    color_scheme.print_colored(&format!("  [>] Exploit: {}\n", vuln_id))?;
-    println!();
-    
-    // Select exploit based on vuln_id
-    let exploit = select_exploit(vuln_id);
-    
-    // Show exploitation stages
-    color_scheme.print_colored("  [>] Stage 1: Reconnaissance...\n")?;
-    animations::show_processing("Gathering target information", 1000).await?;
-    
-    color_scheme.print_colored("  [>] Stage 2: Weaponization...\n")?;
-    animations::show_processing("Preparing exploit payload", 1500).await?;
-    
-    color_scheme.print_colored("  [>] Stage 3: Delivery...\n")?;
-    animations::show_processing("Sending malicious payload", 1000).await?;
-    
-    color_scheme.print_colored("  [>] Stage 4: Exploitation...\n")?;
-    let exploitation_result = attempt_exploitation(&exploit, color_scheme).await?;
+    color_scheme.print_colored("  [>] Exploitation...\n")?;
+    let exploitation_result = attempt_exploitation(exploit, pace, color_scheme).await?;
     
     if exploitation_result.success {
-        color_scheme.print_colored("  [>] Stage 5: Installation...\n")?;
-        animations::show_processing("Establishing persistence", 1500).await?;
+        color_scheme.print_colored("  [>] Installation...\n")?;
+        animations::show_processing("Establishing persistence", 1500, color_scheme).await?;
         
-        color_scheme.print_colored("  [>] Stage 6: Command & Control...\n")?;
-        animations::show_processing("Opening backdoor channel", 1000).await?;
+        color_scheme.print_colored("  [>] Command & Control...\n")?;
+        animations::show_processing("Opening backdoor channel", 1000, color_scheme).await?;
         
-        color_scheme.print_colored("  [>] Stage 7: Actions on Objective...\n")?;
+        color_scheme.print_colored("  [>] Actions on Objective...\n")?;
         let data = extract_data(color_scheme).await?;
         
         println!();
@@ -100,7 +85,7 @@ pub async fn execute_exploit(target: &str, vuln_id: &str, color_scheme: &ColorSc
 }
 
 /// Select exploit based on vulnerability
-fn select_exploit(vuln_id: &str) -> Exploit {
+pub fn select_exploit(vuln_id: &str) -> Exploit {
     match vuln_id {
         "eternalblue" | "ms17-010" => Exploit {
             id: "MS17-010".to_string(),
@@ -157,13 +142,83 @@ fn select_exploit(vuln_id: &str) -> Exploit {
     }
 }
 
-/// Attempt exploitation
-async fn attempt_exploitation(exploit: &Exploit, color_scheme: &ColorScheme) -> Result<ExploitResult> {
+/// Success chance against a host that was never scanned
+pub const UNRECONNOITERED_SUCCESS_RATE: f32 = 0.1;
+
+/// Minimum success chance when the scan found the exploited weakness
+pub const MATCHED_SUCCESS_RATE: f32 = 0.85;
+
+/// Whether a scanned host is known to have the weakness this exploit targets.
+/// Auto-selected zero-days can use any weakness the scan found.
+pub fn matches_vulnerability(exploit: &Exploit, node: &NetworkNode) -> bool {
+    let id = exploit.id.to_lowercase();
+    let name = exploit.name.to_lowercase();
+    let auto = id.starts_with("auto-");
+    node.vulnerabilities.iter().any(|vuln| {
+        let vuln = vuln.to_lowercase();
+        auto || vuln.contains(&id) || vuln.contains(&name)
+    })
+}
+
+/// Success chance for an exploit given what reconnaissance found about the target
+pub fn recon_success_rate(exploit: &Exploit, node: Option<&NetworkNode>) -> f32 {
+    match node {
+        None => UNRECONNOITERED_SUCCESS_RATE,
+        Some(node) if matches_vulnerability(exploit, node) => exploit.success_rate.max(MATCHED_SUCCESS_RATE),
+        // Scanned, but the weakness isn't there: a long shot
+        Some(_) => exploit.success_rate * 0.4,
+    }
+}
+
+/// Which exploits an arsenal item helps with
+#[derive(Debug, Clone, Copy)]
+enum Applies {
+    Any,
+    /// Auto-selected zero-days
+    ZeroDay,
+    /// Exploits aimed at a service on this port
+    Port(u16),
+}
+
+/// Discovered exploits and unlocked tools that improve the odds, with their bonus;
+/// zero-day sources are listed strongest first
+const ARSENAL: &[(&str, Applies, f32)] = &[
+    ("AI_Slayer", Applies::Any, 0.10),
+    ("zero_day_pack", Applies::ZeroDay, 0.25),
+    (darkweb::ZERO_DAY_TOOL, Applies::Any, darkweb::ZERO_DAY_SUCCESS_BONUS),
+    ("elite_tools", Applies::Any, 0.05),
+    ("stolen-creds", Applies::Port(3389), 0.10),
+];
+
+/// Arsenal items drawing on the same unpatched bugs, of which only one counts per exploit
+const ZERO_DAY_SOURCES: &[&str] = &["zero_day_pack", darkweb::ZERO_DAY_TOOL];
+
+/// Arsenal items that apply to this exploit, with the success chance each adds;
+/// `owns` says whether the agent has a tool or discovered exploit by name
+pub fn arsenal_bonuses(exploit: &Exploit, owns: impl Fn(&str) -> bool) -> Vec<(String, f32)> {
+    let mut zero_day_counted = false;
+    ARSENAL
+        .iter()
+        .filter(|(name, applies, _)| {
+            owns(name)
+                && match applies {
+                    Applies::Any => true,
+                    Applies::ZeroDay => exploit.id.starts_with("AUTO-"),
+                    Applies::Port(port) => exploit.required_port == Some(*port),
+                }
+        })
+        .filter(|(name, _, _)| !ZERO_DAY_SOURCES.contains(name) || !std::mem::replace(&mut zero_day_counted, true))
+        .map(|(name, _, bonus)| (name.to_string(), *bonus))
+        .collect()
+}
+
+/// Attempt exploitation, sending each packet at `pace`
+async fn attempt_exploitation(exploit: &Exploit, pace: u32, color_scheme: &ColorScheme) -> Result<ExploitResult> {
     let mut rng = rand::thread_rng();
     
     // Simulate exploitation process
     for i in 0..5 {
-        sleep(Duration::from_millis(400)).await;
+        sleep(Duration::from_millis(400) * pace).await;
         color_scheme.print_dim(&format!("      [>] Sending packet {}...\n", i + 1))?;
     }
     
@@ -198,9 +253,9 @@ async fn extract_data(color_scheme: &ColorScheme) -> Result<Vec<String>> {
     let mut rng = rand::thread_rng();
     let mut data = Vec::new();
     
-    animations::show_processing("Extracting sensitive data", 2000).await?;
+    animations::show_processing("Extracting sensitive data", 2000, color_scheme).await?;
     
-    let data_types = vec![
+    let data_types = [
         vec![
             "user_credentials.db (2.3 MB)",
             "financial_records.xlsx (14.7 MB)",
@@ -227,8 +282,8 @@ async fn extract_data(color_scheme: &ColorScheme) -> Result<Vec<String>> {
     let selected_set = &data_types[rng.gen_range(0..data_types.len())];
     let items_to_extract = rng.gen_range(2..5);
     
-    for i in 0..items_to_extract.min(selected_set.len()) {
-        data.push(selected_set[i].to_string());
+    for item in selected_set.iter().take(items_to_extract) {
+        data.push(item.to_string());
     }
     
     Ok(data)
@@ -362,6 +417,47 @@ mod tests {
         assert!(auto_exploit.id.starts_with("AUTO-"));
     }
 
+    #[test]
+    fn test_recon_drives_success_rate() {
+        let mut node = NetworkNode {
+            ip: "10.0.0.5".to_string(),
+            hostname: "SRV-005".to_string(),
+            node_type: crate::game::state::NodeType::Server,
+            is_compromised: false,
+            security_level: crate::game::state::SecurityLevel::Medium,
+            discovered_at: chrono::Utc::now(),
+            suspected_honeypot: false,
+            vulnerabilities: vec!["CVE-2017-0144: EternalBlue".to_string()],
+            has_rootkit: false,
+            open_ports: Vec::new(),
+        };
+        let eternalblue = select_exploit("eternalblue");
+        let log4shell = select_exploit("log4shell");
+
+        assert_eq!(recon_success_rate(&eternalblue, None), UNRECONNOITERED_SUCCESS_RATE);
+        assert!(recon_success_rate(&eternalblue, Some(&node)) >= MATCHED_SUCCESS_RATE);
+        assert!(recon_success_rate(&log4shell, Some(&node)) < 0.5);
+        assert!(matches_vulnerability(&select_exploit("auto"), &node));
+
+        node.vulnerabilities.clear();
+        assert!(!matches_vulnerability(&select_exploit("auto"), &node));
+    }
+
+    #[test]
+    fn test_arsenal_bonuses_apply_where_relevant() {
+        let owned = ["AI_Slayer", "zero_day_pack", "stolen-creds", darkweb::ZERO_DAY_TOOL];
+        let owns = |name: &str| owned.contains(&name);
+        let names = |exploit: &Exploit| -> Vec<String> {
+            arsenal_bonuses(exploit, owns).into_iter().map(|(name, _)| name).collect()
+        };
+
+        assert_eq!(names(&select_exploit("eternalblue")), vec!["AI_Slayer", darkweb::ZERO_DAY_TOOL]);
+        assert_eq!(names(&select_exploit("bluekeep")), vec!["AI_Slayer", darkweb::ZERO_DAY_TOOL, "stolen-creds"]);
+        // The zero-day pack and kit don't stack
+        assert_eq!(names(&select_exploit("auto")), vec!["AI_Slayer", "zero_day_pack"]);
+        assert!(arsenal_bonuses(&select_exploit("log4shell"), |_| false).is_empty());
+    }
+
     #[test]
     fn test_generate_payload() {
         let payload = generate_payload("buffer_overflow");
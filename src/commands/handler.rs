@@ -1,12 +1,26 @@
 //! Command handler and executor for CRIMSON-REDLINE
 
-use crate::commands::{CommandRegistry, scanner, exploit, decrypt};
-use crate::game::GameState;
+use crate::commands::{CommandRegistry, scanner, exploit, decrypt, darkweb, netmap, payload, hostfs, tools};
+use crate::game::{GameState, ReputationLevel, SaveGame, SharedGameState};
+use crate::game::state::{NetworkNode, SecurityLevel, PERFECT_HACK_HEAT, SPOOF_HEAT_FACTOR, STEALTH_FACTOR};
+use crate::game::{Mission, MissionDifficulty, ObjectiveKind};
+use crate::game::difficulty::DifficultyModifier;
 use crate::ui::{ColorScheme, animations};
+use crate::ui::pager::Pager;
+use crate::ui::colors::Tone;
+use crate::game::events::EventSeverity;
 use crate::auth::User;
+use crate::utils::Config;
+use crate::utils::config::{ColorTheme, SETTABLE_KEYS};
+use crate::utils::history::CommandHistory;
 use anyhow::Result;
+use rand::Rng;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio::sync::{Mutex, MutexGuard};
+
+/// Credit cost of assuming a cover identity
+const SPOOF_COST: i32 = 250;
 
 /// Result of command execution
 #[derive(Debug)]
@@ -16,23 +30,125 @@ pub enum CommandResult {
     Exit,
     Logout,
     Continue,
+    /// Hand the terminal over to the admin console
+    AdminConsole,
+    /// Run an admin subcommand, which needs the auth system
+    Admin(Vec<String>),
+    /// Prompt for a password change
+    ChangePassword,
+    /// Change the agent's username to the one given
+    Rename(String),
+    /// Confirm and delete the agent's account
+    DeleteAccount,
+    /// Run a two-factor subcommand, which needs the auth system
+    TwoFactor(Vec<String>),
+    /// The color theme changed and the UI should follow
+    ThemeChanged(ColorTheme),
+    /// Settings changed or were re-read, and the session should pick them up
+    ConfigChanged(Box<Config>),
 }
 
 /// Main command handler
 pub struct CommandHandler {
     registry: CommandRegistry,
-    game_state: GameState,
+    game_state: SharedGameState,
     color_scheme: ColorScheme,
+    config: Config,
+    history: CommandHistory,
+    /// Account details shown by `profile`
+    member_since: chrono::DateTime<chrono::Utc>,
+    login_count: u32,
+    /// Terminal height for paging long output; 0 never pages
+    page_height: u16,
+    /// Whether a player is at the keyboard; scripts never prompt or write config.json
+    interactive: bool,
 }
 
 impl CommandHandler {
     /// Create a new command handler
     pub fn new(user: &User) -> Self {
+        Self::with_config(user, Config::default())
+    }
+
+    /// Create a command handler using the given configuration
+    pub fn with_config(user: &User, config: Config) -> Self {
         CommandHandler {
             registry: CommandRegistry::new(),
-            game_state: GameState::new(user.username.clone(), user.reputation),
-            color_scheme: ColorScheme::new(),
+            game_state: Arc::new(Mutex::new(GameState::new(user.username.clone(), user.reputation))),
+            color_scheme: ColorScheme::from_display(&config.display),
+            config,
+            history: CommandHistory::default(),
+            member_since: user.created_at,
+            login_count: user.login_count,
+            page_height: 0,
+            interactive: true,
+        }
+    }
+
+    /// Create a command handler that resumes the agent's saved game, if any
+    pub fn load_or_new(user: &User) -> Result<Self> {
+        Self::load_or_new_with_config(user, Config::default())
+    }
+
+    /// Resume the agent's saved game using the given configuration; fails when
+    /// the save exists but can't be used, e.g. one from a newer build
+    pub fn load_or_new_with_config(user: &User, config: Config) -> Result<Self> {
+        let saved = SaveGame::load_for_user(&user.username)?;
+
+        let mut handler = Self::with_config(user, config);
+        if let Some(saved) = saved {
+            handler.game_state = Arc::new(Mutex::new(restore_state(user, saved)));
+        }
+        match CommandHistory::load_for_user(&user.username) {
+            Ok(history) => handler.history = history,
+            Err(e) => eprintln!("Warning: could not load command history: {}", e),
         }
+        Ok(handler)
+    }
+
+    /// Save the agent's ongoing game so the next session picks it up
+    pub async fn save_game(&self) -> Result<()> {
+        let state = {
+            let mut state = self.game_state.lock().await;
+            state.update_time_played();
+            state.clone()
+        };
+        SaveGame::new(state).save_for_user()
+    }
+
+    /// Carry the game and its files over to the agent's new username
+    pub async fn rename_agent(&mut self, new_username: &str) -> Result<()> {
+        let old_username = {
+            let mut state = self.game_state.lock().await;
+            std::mem::replace(&mut state.username, new_username.to_string())
+        };
+        let moved = SaveGame::move_user_saves(&old_username, new_username)
+            .and_then(|()| self.history.move_to(CommandHistory::path_for_user(new_username)?));
+        // Save under the new name either way, so the next login resumes from here
+        self.save_game().await?;
+        moved
+    }
+
+    /// Delete the agent's saves and history once their account is gone
+    pub async fn delete_agent_files(&mut self) -> Result<()> {
+        let username = self.game_state.lock().await.username.clone();
+        SaveGame::delete_agent_files(&username)?;
+        self.history.wipe()
+    }
+
+    /// Page long output to fit a terminal this many rows tall
+    pub fn set_page_height(&mut self, height: u16) {
+        self.page_height = height;
+    }
+
+    /// Run without a player: prompts take their default and settings last only for the session
+    pub fn set_interactive(&mut self, interactive: bool) {
+        self.interactive = interactive;
+    }
+
+    /// Command history, for up/down recall at the prompt
+    pub fn history_mut(&mut self) -> &mut CommandHistory {
+        &mut self.history
     }
 
     /// Execute a command
@@ -43,34 +159,130 @@ impl CommandHandler {
             return Ok(CommandResult::Continue);
         }
 
+        // History is kept in plain text, so secrets such as decryption keys are masked first
+        if let Err(e) = self.history.push(&crate::utils::session_log::redact_command(input)) {
+            self.color_scheme.print_warning(&format!("  [!] Could not save history: {}\n", e))?;
+        }
+        
         let (command, args) = crate::commands::parse_args(input);
         
-        match command.as_str() {
+        // Hold the state for the whole command so background ticks land between commands
+        let shared = Arc::clone(&self.game_state);
+        let mut state = shared.lock().await;
+        let state = &mut *state;
+        state.update_time_played();
+        if let Some(info) = self.registry.get_command(&command) {
+            state.stats.record_command(&info.name);
+        }
+        let level_before = state.reputation_level();
+        
+        let result = match command.as_str() {
             "help" | "?" | "h" => self.handle_help(args).await,
-            "scan" | "nmap" | "recon" => self.handle_scan(args).await,
-            "exploit" | "pwn" | "attack" => self.handle_exploit(args).await,
-            "decrypt" | "decode" | "decipher" => self.handle_decrypt(args).await,
-            "inject" | "payload" | "implant" => self.handle_inject(args).await,
-            "trace" | "traceroute" | "track" => self.handle_trace(args).await,
-            "status" | "stats" | "info" => self.handle_status().await,
-            "mission" | "objective" | "task" => self.handle_mission(args).await,
-            "darkweb" | "market" | "underground" => self.handle_darkweb(args).await,
-            "firewall" | "fw" | "barrier" => self.handle_firewall(args).await,
+            "scan" | "nmap" | "recon" => self.handle_scan(state, args).await,
+            "exploit" | "pwn" | "attack" => self.handle_exploit(state, args).await,
+            "decrypt" | "decode" | "decipher" => self.handle_decrypt(state, args).await,
+            "crack" | "hashcat" | "john" => self.handle_crack(state, args).await,
+            "inject" | "payload" | "implant" => self.handle_inject(state, args).await,
+            "trace" | "traceroute" | "track" => self.handle_trace(state, args).await,
+            "ping" => self.handle_ping(state, args).await,
+            "connect" | "ssh" => self.handle_connect(state, args).await,
+            "disconnect" => self.handle_disconnect(state).await,
+            "ls" | "dir" => self.handle_ls(state).await,
+            "cat" | "type" => self.handle_cat(state, args).await,
+            "status" | "stats" | "info" => self.handle_status(state).await,
+            "records" | "bests" => self.handle_records(state).await,
+            "events" | "eventlog" => self.handle_events(state, args).await,
+            "mission" | "objective" | "task" => self.handle_mission(state, args).await,
+            "darkweb" | "market" | "underground" => self.handle_darkweb(state, args).await,
+            "tools" | "inventory" | "inv" => self.handle_tools(state).await,
+            "firewall" | "fw" | "barrier" => self.handle_firewall(state, args).await,
+            "hint" | "tip" | "advice" => self.handle_hint(state).await,
+            "spoof" | "mask" => self.handle_spoof(state, args).await,
+            "stealth" | "quiet" => self.handle_stealth(state, args).await,
+            "achievements" | "trophies" | "ach" => self.handle_achievements(state, args).await,
+            "pivot" | "lateral" | "hop" => self.handle_pivot(state, args).await,
+            "map" | "topology" | "netmap" => self.handle_map(state, args).await,
+            "card" | "flex" | "share" => self.handle_card(state, args).await,
+            "profile" | "whoami" | "dossier" => self.handle_profile(state).await,
+            "history" | "hist" => self.handle_history(args).await,
+            "passwd" | "password" => Ok(CommandResult::ChangePassword),
+            "rename" => self.handle_rename(args),
+            "delete-account" => Ok(CommandResult::DeleteAccount),
+            "2fa" | "mfa" => Ok(CommandResult::TwoFactor(args)),
+            "theme" | "colors" | "skin" => self.handle_theme(args),
+            "difficulty" | "diff" => self.handle_difficulty().await,
+            "config" | "settings" => self.handle_config(args),
+            "sudo" => self.handle_sudo(args).await,
+            "admin" => self.handle_admin(args),
             "clear" | "cls" | "cl" => self.handle_clear().await,
-            "logout" | "exit" | "quit" | "disconnect" => Ok(CommandResult::Logout),
+            "logout" | "exit" | "quit" => Ok(CommandResult::Logout),
             "showcase" | "demo" | "art" => self.handle_showcase().await,
             _ => {
                 self.color_scheme.print_error(&format!("  [!] Unknown command: {}\n", command))?;
+                if let Some(suggestion) = self.registry.closest_match(&command) {
+                    self.color_scheme.print_warning(&format!("  [?] Did you mean '{}'?\n", suggestion))?;
+                }
                 self.color_scheme.print_dim("  Type 'help' for available commands\n")?;
                 Ok(CommandResult::Continue)
             }
+        };
+        
+        state.update_stats();
+        
+        for mission in state.expire_missions(chrono::Utc::now()) {
+            self.color_scheme.print_error(&format!(
+                "\n  [✗] MISSION FAILED: {} ran out of time\n",
+                mission.name
+            ))?;
         }
+        for mission in state.settle_missions() {
+            self.color_scheme.print_success(&format!(
+                "\n  [✓] MISSION COMPLETE: {} (+{} reputation, +{} credits)\n",
+                mission.name, mission.reward_reputation, mission.reward_credits
+            ))?;
+        }
+        
+        let level_after = state.reputation_level();
+        if level_after != level_before {
+            self.announce_level_change(&level_before, &level_after).await?;
+        }
+        
+        result
+    }
+
+    /// Celebrate a promotion with a full-width banner, or warn quietly about a demotion
+    async fn announce_level_change(&self, before: &ReputationLevel, after: &ReputationLevel) -> Result<()> {
+        if !after.outranks(before) {
+            self.color_scheme.print_warning(&format!(
+                "\n  [!] Standing lost: demoted from {} to {}\n",
+                before.display_name(),
+                after.display_name()
+            ))?;
+            return Ok(());
+        }
+        
+        animations::pause(Duration::from_millis(800), &self.color_scheme).await;
+        animations::glitch_transition(&self.color_scheme).await?;
+        
+        let width = crossterm::terminal::size().map_or(80, |(w, _)| w as usize);
+        let rule = "═".repeat(width);
+        let title = format!("LEVEL UP // {}", after.display_name().to_uppercase());
+        self.color_scheme.print_rgb(&format!("{}\n", rule), 255, 215, 0)?;
+        self.color_scheme.print_bright(&format!("{:^width$}\n", title, width = width))?;
+        self.color_scheme.print_rgb(&format!("{}\n\n", rule), 255, 215, 0)?;
+        
+        self.color_scheme.print_colored("  Perks unlocked:\n")?;
+        for perk in after.get_perks() {
+            self.color_scheme.print_success(&format!("    ▸ {}\n", perk))?;
+        }
+        println!();
+        Ok(())
     }
 
     /// Handle help command
     async fn handle_help(&self, args: Vec<String>) -> Result<CommandResult> {
         if args.is_empty() {
-            self.registry.display_help(&self.color_scheme).await?;
+            self.registry.display_help(&self.color_scheme, self.page_height).await?;
         } else {
             self.registry.display_command_help(&args[0], &self.color_scheme).await?;
         }
@@ -78,84 +290,292 @@ impl CommandHandler {
     }
 
     /// Handle scan command
-    async fn handle_scan(&mut self, args: Vec<String>) -> Result<CommandResult> {
-        let target = if args.is_empty() {
-            "network"
-        } else {
-            &args[0]
+    async fn handle_scan(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let safe = args.iter().any(|a| a == "--safe");
+        // Machine-readable output: no art, animations or decoration
+        let json = args.iter().any(|a| a == "--json");
+        let target = crate::commands::positional_args(&args, &["--count", "-p"])
+            .first()
+            .copied()
+            .unwrap_or("network");
+        
+        let deep = args.iter().any(|a| a == "--deep");
+        let vuln = args.iter().any(|a| a == "--vuln");
+        if deep || vuln {
+            if deep && vuln {
+                self.color_scheme.print_error("  [!] Pick one of --deep or --vuln\n")?;
+                return Ok(CommandResult::Continue);
+            }
+            if target == "network" {
+                self.color_scheme.print_error("  [!] --deep and --vuln need a target: scan <target_ip> --deep\n")?;
+                return Ok(CommandResult::Continue);
+            }
+            return if deep {
+                self.handle_deep_scan(state, target, json).await
+            } else {
+                self.handle_vuln_scan(state, target, json).await
+            };
+        }
+        
+        let ports = match scanner::parse_ports_flag(&args) {
+            Ok(Some(_)) if target == "network" => {
+                self.color_scheme.print_error("  [!] -p needs a target: scan <target_ip> -p <ports>\n")?;
+                return Ok(CommandResult::Continue);
+            }
+            Ok(ports) => ports,
+            Err(e) => {
+                self.color_scheme.print_error(&format!("  [!] {}\n", e))?;
+                return Ok(CommandResult::Continue);
+            }
+        };
+        
+        let device_count = match scanner::parse_count_flag(&args) {
+            Ok(Some(count)) => count,
+            Ok(None) if target == "network" => scanner::network_device_count(
+                self.config.game.scan_min_devices,
+                self.config.game.scan_max_devices,
+            ),
+            Ok(None) => 1,
+            Err(e) => {
+                self.color_scheme.print_error(&format!("  [!] {}\n", e))?;
+                return Ok(CommandResult::Continue);
+            }
         };
 
-        // Show network map ASCII art
-        println!();
-        self.color_scheme.print_colored(crate::ui::ascii_art::NETWORK_MAP)?;
-        println!();
-        sleep(Duration::from_millis(500)).await;
+        if !json {
+            // Show network map ASCII art
+            println!();
+            self.color_scheme.print_colored(crate::ui::ascii_art::NETWORK_MAP)?;
+            println!();
+            animations::pause(Duration::from_millis(500), &self.color_scheme).await;
 
-        // Show scanning animation
-        animations::scanning_animation(target, &self.color_scheme).await?;
+            // Show scanning animation
+            animations::scanning_animation(target, &self.color_scheme).await?;
+        }
         
         // Execute scan
-        let results = scanner::execute_scan(target).await?;
+        let results = match &ports {
+            Some(ports) => scanner::execute_scan_ports(target, ports, state.operation_pace()).await?,
+            None => scanner::execute_scan_with_count(target, device_count, state.operation_pace()).await?,
+        };
         
-        // Display results
-        println!();
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        self.color_scheme.print_bright("                    SCAN RESULTS                               \n")?;
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        println!();
+        // Remember every host seen, linked to what was scanned
+        let origin = if target == "network" { scanner::SCAN_ORIGIN } else { target };
+        for device in &results.devices {
+            state.network_map.add_node(device.to_network_node());
+            if device.ip != origin {
+                state.network_map.add_connection(origin.to_string(), device.ip.clone());
+            }
+        }
+        
+        // Safe scans fingerprint every host for honeypot signatures
+        let mut flagged = Vec::new();
+        if safe {
+            if !json {
+                animations::show_processing("Fingerprinting honeypot signatures", 2500, &self.color_scheme).await?;
+            }
+            for device in &results.devices {
+                if scanner::detect_honeypot(state.reputation) {
+                    state.network_map.flag_honeypot(&device.ip, &device.hostname);
+                    flagged.push(device.ip.clone());
+                }
+            }
+        }
+        
+        if json {
+            println!("{}", scan_json(&results, safe.then_some(flagged.as_slice()))?);
+        } else {
+            self.display_scan_results(&results, &flagged, ports.as_deref())?;
+        }
+        
+        // Update game state
+        state.record_scan();
+        state.record_scan_target(target);
+        let vulnerabilities = results.devices.iter().map(|d| d.vulnerabilities.len() as u32).sum();
+        state.advance_missions(ObjectiveKind::Vulnerability, vulnerabilities);
+        state.add_reputation(self.config.game.reputation_formula.scan);
+        self.raise_heat(state, if safe { 15.0 } else { 10.0 });
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Slow, thorough scan of one host that turns up advanced vulnerabilities
+    async fn handle_deep_scan(&self, state: &mut GameState, target: &str, json: bool) -> Result<CommandResult> {
+        let started = std::time::Instant::now();
+        let device = if json {
+            scanner::deep_scan(target, state.operation_pace()).await?
+        } else {
+            animations::with_spinner(&format!("Deep scanning {}", target), &self.color_scheme, scanner::deep_scan(target, state.operation_pace())).await??
+        };
+        let results = scanner::ScanResult { devices: vec![device], scan_time: started.elapsed() };
         
         for device in &results.devices {
-            self.color_scheme.print_bright(&format!("  [+] {}\n", device.hostname))?;
-            self.color_scheme.print_colored(&format!("      IP: {}\n", device.ip))?;
-            self.color_scheme.print_colored(&format!("      MAC: {}\n", device.mac))?;
-            self.color_scheme.print_colored(&format!("      OS: {}\n", device.os))?;
+            state.network_map.add_node(device.to_network_node());
+            state.network_map.add_connection(scanner::SCAN_ORIGIN.to_string(), device.ip.clone());
+        }
+        
+        if json {
+            println!("{}", scan_json(&results, None)?);
+        } else {
+            self.display_scan_results(&results, &[], None)?;
+        }
+        
+        state.record_scan();
+        state.record_scan_target(target);
+        let vulnerabilities = results.devices.iter().map(|d| d.vulnerabilities.len() as u32).sum();
+        state.advance_missions(ObjectiveKind::Vulnerability, vulnerabilities);
+        state.add_reputation(self.config.game.reputation_formula.deep_scan);
+        self.raise_heat(state, 25.0);
+        
+        Ok(CommandResult::Continue)
+    }
+    
+    /// Probe one host for vulnerabilities only, adding them to what's known about it
+    async fn handle_vuln_scan(&self, state: &mut GameState, target: &str, json: bool) -> Result<CommandResult> {
+        let vulnerabilities = if json {
+            scanner::vulnerability_scan(target, state.operation_pace()).await?
+        } else {
+            animations::with_spinner(&format!("Probing {} for vulnerabilities", target), &self.color_scheme, scanner::vulnerability_scan(target, state.operation_pace())).await??
+        };
+        
+        if let Some(node) = state.network_map.get_node_mut(target) {
+            for vuln in &vulnerabilities {
+                if !node.vulnerabilities.contains(vuln) {
+                    node.vulnerabilities.push(vuln.clone());
+                }
+            }
+        }
+        
+        if json {
+            println!("{}", serde_json::to_string_pretty(&serde_json::json!({
+                "target": target,
+                "vulnerabilities": vulnerabilities,
+            }))?);
+        } else {
+            println!();
+            self.color_scheme.print_bright(&format!("  [+] {} vulnerabilities on {}\n", vulnerabilities.len(), target))?;
+            for vuln in &vulnerabilities {
+                self.color_scheme.print_error(&format!("      - {}\n", vuln))?;
+            }
+        }
+        
+        state.record_scan();
+        state.record_scan_target(target);
+        state.advance_missions(ObjectiveKind::Vulnerability, vulnerabilities.len() as u32);
+        state.add_reputation(self.config.game.reputation_formula.vuln_scan);
+        self.raise_heat(state, 18.0);
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Show scan results, paged when they run past the screen
+    fn display_scan_results(&self, results: &scanner::ScanResult, flagged: &[String], ports: Option<&[u16]>) -> Result<()> {
+        let mut pager = Pager::new(self.page_height);
+        pager.blank();
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.bright("                    SCAN RESULTS                               \n");
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.blank();
+        
+        for device in &results.devices {
+            pager.bright(&format!("  [+] {}\n", device.hostname));
+            pager.colored(&format!("      IP: {}\n", device.ip));
+            if flagged.contains(&device.ip) {
+                pager.error("      [!] LIKELY HONEYPOT - exploit at your own risk\n");
+            }
+            pager.colored(&format!("      MAC: {}\n", device.mac));
+            pager.colored(&format!("      OS: {}\n", device.os));
             
             if !device.open_ports.is_empty() {
-                self.color_scheme.print_colored("      Open Ports: ")?;
+                pager.colored("      Open Ports: ");
                 for port in &device.open_ports {
-                    self.color_scheme.print_success(&format!("{} ", port))?;
+                    pager.success(&format!("{} ", port));
                 }
-                println!();
+                pager.blank();
             }
             
             if !device.vulnerabilities.is_empty() {
-                self.color_scheme.print_warning("      Vulnerabilities:\n")?;
+                pager.warning("      Vulnerabilities:\n");
                 for vuln in &device.vulnerabilities {
-                    self.color_scheme.print_error(&format!("        - {}\n", vuln))?;
+                    pager.error(&format!("        - {}\n", vuln));
                 }
             }
-            println!();
+            pager.blank();
         }
         
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        self.color_scheme.print_dim(&format!("  Total devices found: {}\n", results.devices.len()))?;
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        
-        // Update game state
-        self.game_state.add_reputation(5);
-        self.game_state.increase_heat(10.0);
-        
-        Ok(CommandResult::Continue)
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.dim(&format!("  Total devices found: {}\n", results.devices.len()));
+        if let Some(ports) = ports {
+            let open = results.devices.iter().map(|d| d.open_ports.len()).sum::<usize>();
+            pager.dim(&format!("  Ports probed: {} ({} open, {} closed/filtered)\n", ports.len(), open, ports.len() - open));
+        }
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.show(&self.color_scheme)
     }
 
     /// Handle exploit command
-    async fn handle_exploit(&mut self, args: Vec<String>) -> Result<CommandResult> {
-        if args.len() < 1 {
+    async fn handle_exploit(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let force = args.iter().any(|a| a == "--force");
+        let args: Vec<&String> = args.iter().filter(|a| !a.starts_with("--")).collect();
+        // The connected host is the default target
+        let Some(target) = args.first().map(|s| s.to_string()).or_else(|| state.current_target.clone()) else {
             self.color_scheme.print_error("  [!] Usage: exploit <target> [vulnerability_id]\n")?;
             return Ok(CommandResult::Continue);
-        }
-
-        let target = &args[0];
+        };
+        let target = target.as_str();
         let vuln_id = args.get(1).map(|s| s.as_str()).unwrap_or("auto");
         
+        // Walking into a known honeypot without acknowledging it trips extra alarms
+        if state.network_map.is_suspected_honeypot(target) {
+            if force {
+                self.color_scheme.print_warning("  [!] Proceeding against suspected honeypot (risk acknowledged)\n")?;
+            } else {
+                self.color_scheme.print_error("  [!] Target was flagged as a likely honeypot! Alarms tripped (+20% heat)\n")?;
+                self.color_scheme.print_dim("      Use --force to acknowledge the risk next time\n")?;
+                self.raise_heat(state, 20.0);
+            }
+        }
+        
         // Show target ASCII art
         println!();
         self.color_scheme.print_colored(crate::ui::ascii_art::TARGET)?;
         println!();
-        sleep(Duration::from_millis(800)).await;
+        animations::pause(Duration::from_millis(800), &self.color_scheme).await;
+        
+        let mut selected = exploit::select_exploit(vuln_id);
+        let node = state.network_map.get_node(target);
+        match node {
+            None => {
+                self.color_scheme.print_warning("  [!] Target not reconnoitered - going in blind\n")?;
+                self.color_scheme.print_dim(&format!("      Run 'scan {}' first to find a weakness\n", target))?;
+            }
+            Some(node) if !exploit::matches_vulnerability(&selected, node) => {
+                self.color_scheme.print_warning(&format!("  [!] No known {} weakness on {}\n", selected.name, target))?;
+            }
+            Some(_) => {}
+        }
+        selected.success_rate = exploit::recon_success_rate(&selected, node);
+        let owns = |name: &str| state.has_tool(name) || state.discovered_exploits.iter().any(|e| e == name);
+        for (name, bonus) in exploit::arsenal_bonuses(&selected, owns) {
+            selected.success_rate = (selected.success_rate + bonus).min(0.95);
+            self.color_scheme.print_dim(&format!("  [+] {} in your arsenal: +{:.0}% odds\n", name, bonus * 100.0))?;
+        }
+        selected.success_rate = self.difficulty().scale_success(selected.success_rate);
+        if self.config.game.adaptive_difficulty {
+            let modifier = crate::game::difficulty::adaptive_modifier(state);
+            selected.success_rate = modifier.scale_success(selected.success_rate);
+        }
+        if self.color_scheme.animations() {
+            if !animations::exploit_animation(target, &selected.id, &self.color_scheme).await? {
+                self.color_scheme.print_warning("\n  [!] Exploit aborted - connection dropped before payload delivery\n")?;
+                return Ok(CommandResult::Continue);
+            }
+        } else {
+            self.color_scheme.print_colored(&format!("  [>] Exploiting {} with {}\n", target, selected.id))?;
+        }
         
         // Execute exploit
-        let result = exploit::execute_exploit(target, vuln_id, &self.color_scheme).await?;
+        let result = exploit::execute_exploit(&selected, state.operation_pace(), &self.color_scheme).await?;
         
         if result.success {
             // Show access granted
@@ -163,74 +583,245 @@ impl CommandHandler {
             self.color_scheme.print_success(crate::ui::ascii_art::ACCESS_GRANTED)?;
             println!();
             
-            self.game_state.add_reputation(20);
-            self.game_state.increase_heat(25.0);
-            self.color_scheme.print_success(&format!("\n  [✓] Exploit successful! Gained {} reputation\n", 20))?;
+            let reward = self.config.game.reputation_formula.exploit_success;
+            let undetected = state.heat_level < PERFECT_HACK_HEAT;
+            state.network_map.mark_compromised(target);
+            state.record_successful_hack();
+            state.stats.record_hack(target, &selected.id, undetected);
+            state.add_reputation(reward);
+            self.raise_heat(state, 25.0);
+            self.color_scheme.print_success(&format!("\n  [✓] Exploit successful! Gained {} reputation\n", reward))?;
         } else {
             // Show access denied
             println!();
             self.color_scheme.print_error(crate::ui::ascii_art::ACCESS_DENIED)?;
             println!();
             
-            self.game_state.increase_heat(15.0);
+            state.record_failed_hack();
+            state.add_reputation(self.config.game.reputation_formula.exploit_failure);
+            self.raise_heat(state, 15.0);
             self.color_scheme.print_error("\n  [✗] Exploit failed!\n")?;
         }
         
         Ok(CommandResult::Continue)
     }
 
+    /// Handle pivot command
+    async fn handle_pivot(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let Some(ip) = args.first() else {
+            self.color_scheme.print_error("  [!] Usage: pivot <compromised_ip>\n")?;
+            return Ok(CommandResult::Continue);
+        };
+        
+        let hostname = match state.network_map.get_node(ip) {
+            Some(node) if node.is_compromised => node.hostname.clone(),
+            _ => {
+                self.color_scheme.print_error(&format!("  [!] Cannot pivot from {}: host is not compromised\n", ip))?;
+                self.color_scheme.print_dim("      Exploit it first to gain a foothold\n")?;
+                return Ok(CommandResult::Continue);
+            }
+        };
+        
+        println!();
+        self.color_scheme.print_colored(&format!("  [>] Pivoting through {} ({})...\n", hostname, ip))?;
+        animations::show_processing("Mapping adjacent hosts from inside", 1500, &self.color_scheme).await?;
+        
+        // Lateral movement reveals the internal hosts next to the foothold
+        let count = rand::thread_rng().gen_range(1..=scanner::PIVOT_MAX_NEIGHBORS);
+        let results = scanner::execute_scan_with_count("network", count, state.operation_pace()).await?;
+        for device in &results.devices {
+            state.network_map.add_node(device.to_network_node());
+            state.network_map.add_connection(ip.clone(), device.ip.clone());
+        }
+        
+        println!();
+        self.color_scheme.print_bright(&format!("  ADJACENT TO {}\n", ip))?;
+        for node in state.network_map.get_connected_nodes(ip) {
+            let line = format!("  [+] {:<16} {}", node.ip, node.hostname);
+            if node.is_compromised {
+                self.color_scheme.print_success(&format!("{} [OWNED]\n", line))?;
+            } else {
+                self.color_scheme.print_colored(&format!("{}\n", line))?;
+            }
+        }
+        
+        self.raise_heat(state, scanner::PIVOT_HEAT);
+        self.color_scheme.print_dim(&format!(
+            "\n  Quiet lateral sweep: +{:.0}% heat. Exploit a neighbor to pivot deeper.\n",
+            scanner::PIVOT_HEAT
+        ))?;
+        
+        Ok(CommandResult::Continue)
+    }
+
     /// Handle decrypt command
-    async fn handle_decrypt(&mut self, args: Vec<String>) -> Result<CommandResult> {
-        if args.is_empty() {
-            // Generate random encrypted data
-            let encrypted = decrypt::generate_encrypted_data();
-            animations::decryption_animation(&encrypted, &self.color_scheme).await?;
-            
-            let decrypted = decrypt::decrypt_data(&encrypted)?;
-            self.color_scheme.print_success(&format!("\n  [✓] Decrypted: {}\n", decrypted))?;
+    async fn handle_decrypt(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let positional = crate::commands::positional_args(&args, &["-k", "-m", "--file"]);
+        let file = crate::commands::flag_value(&args, "--file").or(match positional.as_slice() {
+            [arg] if decrypt::looks_like_file(arg) => Some(*arg),
+            _ => None,
+        });
+        if let Some(file) = file {
+            return self.decrypt_file(state, file).await;
+        }
+        
+        let cipher = match crate::commands::flag_value(&args, "-m") {
+            Some(name) => match decrypt::find_cipher(name) {
+                Some(cipher) => Some(cipher),
+                None => {
+                    self.color_scheme.print_error(&format!("  [!] Unknown cipher: {}\n", name))?;
+                    let names: Vec<&str> = decrypt::get_cipher_types().into_iter().map(|(name, _)| name).collect();
+                    self.color_scheme.print_dim(&format!("      Available: {}\n", names.join(", ")))?;
+                    return Ok(CommandResult::Continue);
+                }
+            },
+            None => None,
+        };
+        
+        // Intercepted blobs live on a remote host; data the player supplies is local
+        let (data, latency) = if positional.is_empty() {
+            (decrypt::generate_encrypted_data(), Some(self.config.network.remote_latency_ms))
         } else {
-            let data = args.join(" ");
-            animations::decryption_animation(&data, &self.color_scheme).await?;
-            
-            let decrypted = decrypt::decrypt_data(&data)?;
-            self.color_scheme.print_success(&format!("\n  [✓] Decrypted: {}\n", decrypted))?;
+            (positional.join(" "), None)
+        };
+        
+        // The key `crack` recovers for this data unlocks it quickly; any other key scrambles it
+        let key_correct = crate::commands::flag_value(&args, "-k").map(|key| key == decrypt::key_for(&data));
+        let mut duration = decrypt::decrypt_duration(data.chars().count(), latency);
+        if let Some((name, description)) = cipher {
+            self.color_scheme.print_dim(&format!("  [>] Cipher: {} - {}\n", name, description))?;
+            duration = duration.mul_f32(decrypt::cipher_work_factor(name));
+        }
+        if key_correct == Some(true) {
+            self.color_scheme.print_success("  [+] Key accepted - skipping brute force\n")?;
+            duration = duration.mul_f32(decrypt::KEYED_DECRYPT_FACTOR);
         }
+        duration *= state.operation_pace();
+        
+        if let Some(latency) = latency {
+            self.color_scheme.print_dim(&format!(
+                "  [>] Remote blob over a {}ms link - estimated {:.1}s\n",
+                latency,
+                duration.as_secs_f32()
+            ))?;
+        }
+        
+        // The reveal takes the whole decryption time; without it the work still takes that long
+        if !self.color_scheme.animations() {
+            animations::with_spinner("Decrypting", &self.color_scheme, tokio::time::sleep(duration)).await?;
+        } else if !animations::decryption_animation(&data, duration, &self.color_scheme).await? {
+            self.color_scheme.print_warning("  [!] Decryption aborted\n")?;
+            return Ok(CommandResult::Continue);
+        }
+        
+        let decrypted = decrypt::decrypt_data(&data)?;
+        if key_correct == Some(false) {
+            let garbled = crate::utils::glitch_text(&decrypted, 0.6);
+            self.color_scheme.print_error(&format!("\n  [✗] Wrong key - output is garbage: {}\n", garbled))?;
+            self.raise_heat(state, 5.0);
+            return Ok(CommandResult::Continue);
+        }
+        self.color_scheme.print_success(&format!("\n  [✓] Decrypted: {}\n", decrypted))?;
+        
+        let reward = decrypt::decrypt_reputation(self.config.game.reputation_formula.decrypt, duration);
+        state.record_decryption();
+        state.add_reputation(reward);
+        self.raise_heat(state, 5.0);
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Decrypt a whole file and show it in a panel
+    async fn decrypt_file(&self, state: &mut GameState, filename: &str) -> Result<CommandResult> {
+        animations::show_processing(&format!("Decrypting {}", filename), 2000, &self.color_scheme).await?;
+        let file = decrypt::decrypt_file(filename).await?;
+        
+        println!();
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        self.color_scheme.print_bright(&format!("  {}\n", file.filename))?;
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        self.color_scheme.print_dim(&format!("  Type: {:?}    Size: {}\n", file.file_type, decrypt::format_size(file.size)))?;
+        let mut metadata: Vec<_> = file.metadata.iter().collect();
+        metadata.sort();
+        for (key, value) in metadata {
+            self.color_scheme.print_dim(&format!("  {:<12} {}\n", format!("{}:", key), value))?;
+        }
+        self.color_scheme.print_colored("───────────────────────────────────────────────────────────────\n")?;
+        for line in file.content.lines() {
+            self.color_scheme.print_success(&format!("  {}\n", line))?;
+        }
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        
+        state.record_decryption();
+        state.add_reputation(self.config.game.reputation_formula.decrypt);
+        self.raise_heat(state, 5.0);
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle crack command
+    async fn handle_crack(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        // Without a hash, crack one lifted from a captured credential dump
+        let hash = match args.first() {
+            Some(hash) => hash.clone(),
+            None => {
+                self.color_scheme.print_dim("  [>] No hash given - pulled one from a captured dump\n")?;
+                decrypt::generate_hash()
+            }
+        };
+        
+        println!();
+        self.color_scheme.print_colored(&format!("  [>] Target hash: {}\n", hash))?;
+        self.color_scheme.print_dim(&format!("      Algorithm:   {}\n", decrypt::identify_hash(&hash)))?;
+        let password = animations::with_spinner("Running dictionary attack", &self.color_scheme, decrypt::crack_hash(&hash, state.operation_pace())).await??;
+        self.color_scheme.print_success(&format!("\n  [✓] Password recovered: {}\n", password))?;
         
-        self.game_state.add_reputation(10);
-        self.game_state.increase_heat(5.0);
+        state.add_reputation(self.config.game.reputation_formula.crack);
+        self.raise_heat(state, 3.0);
         
         Ok(CommandResult::Continue)
     }
 
     /// Handle inject command
-    async fn handle_inject(&mut self, args: Vec<String>) -> Result<CommandResult> {
+    async fn handle_inject(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
         if args.is_empty() {
-            self.color_scheme.print_error("  [!] Usage: inject <target> <payload_type>\n")?;
+            self.color_scheme.print_error(&format!("  [!] Usage: inject <target> <{}>\n", payload::payload_names().join("|")))?;
             return Ok(CommandResult::Continue);
         }
 
         let target = &args[0];
-        let payload = args.get(1).map(|s| s.as_str()).unwrap_or("trojan");
+        let name = args.get(1).map(|s| s.as_str()).unwrap_or("trojan");
+        let Some(payload) = payload::find_payload(name) else {
+            self.color_scheme.print_error(&format!("  [!] Unknown payload: {}\n", name))?;
+            for payload in payload::PAYLOADS {
+                self.color_scheme.print_dim(&format!("      {:<12} {}\n", payload.name, payload.description))?;
+            }
+            return Ok(CommandResult::Continue);
+        };
+        if payload.name == "rootkit" && state.network_map.get_node(target).is_none() {
+            self.color_scheme.print_error(&format!("  [!] No known host at {}\n", target))?;
+            self.color_scheme.print_dim("      Scan it first - a rootkit needs a host you've mapped\n")?;
+            return Ok(CommandResult::Continue);
+        }
         
         // Show injection ASCII art
         println!();
         self.color_scheme.print_colored(crate::ui::ascii_art::INJECTION)?;
         println!();
         
-        self.color_scheme.print_colored(&format!("  [>] Preparing {} payload for {}...\n", payload, target))?;
-        animations::show_processing("Compiling payload", 1500).await?;
+        self.color_scheme.print_colored(&format!("  [>] Preparing {} payload for {}...\n", payload.name, target))?;
+        animations::show_processing(payload.stages[0], 1500, &self.color_scheme).await?;
         
         self.color_scheme.print_colored("  [>] Establishing connection...\n")?;
-        sleep(Duration::from_millis(800)).await;
+        animations::pause(Duration::from_millis(800), &self.color_scheme).await;
         
-        self.color_scheme.print_colored("  [>] Bypassing security...\n")?;
-        sleep(Duration::from_millis(1000)).await;
+        self.color_scheme.print_colored(&format!("  [>] {}...\n", payload.stages[1]))?;
+        animations::pause(Duration::from_millis(1000), &self.color_scheme).await;
         
         self.color_scheme.print_colored("  [>] Injecting payload...\n")?;
-        animations::show_processing("Injection in progress", 2000).await?;
+        animations::show_processing(payload.stages[2], 2000, &self.color_scheme).await?;
         
-        // Random success/failure
-        let success = rand::random::<bool>();
+        let success = rand::thread_rng().gen::<f32>() < self.difficulty().scale_success(payload.success_chance);
         
         if success {
             // Show virus symbol on success
@@ -238,204 +829,1201 @@ impl CommandHandler {
             self.color_scheme.print_colored(crate::ui::ascii_art::VIRUS)?;
             println!();
             
-            self.color_scheme.print_success(&format!("\n  [✓] {} successfully injected into {}\n", payload, target))?;
-            self.game_state.add_reputation(15);
-            self.game_state.increase_heat(20.0);
+            self.color_scheme.print_success(&format!("\n  [✓] {} successfully injected into {}\n", payload.name, target))?;
+            if payload.name == "rootkit" {
+                state.record_rootkit(target);
+                self.color_scheme.print_success("  [+] Persistent access established - the host stays yours\n")?;
+            }
+            state.advance_missions(ObjectiveKind::Inject, 1);
+            let reward = self.config.game.reputation_formula.inject as f32 * payload.reward_factor;
+            state.add_reputation(reward as i32);
+            self.raise_heat(state, payload.heat);
         } else {
             self.color_scheme.print_error("\n  [✗] Injection failed - Target secured\n")?;
-            self.game_state.increase_heat(10.0);
+            self.raise_heat(state, payload.failure_heat);
+        }
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle ping command
+    async fn handle_ping(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let Some(target) = args.first() else {
+            self.color_scheme.print_error("  [!] Usage: ping <ip>\n")?;
+            return Ok(CommandResult::Continue);
+        };
+        let node = state.network_map.get_node(target);
+        let reachable = scanner::ping_reachable(node);
+        let ttl = scanner::ping_ttl(node);
+        
+        println!();
+        match node {
+            Some(node) => self.color_scheme.print_colored(&format!("  PING {} ({}): 56 data bytes\n", target, node.hostname))?,
+            None => self.color_scheme.print_colored(&format!("  PING {}: 56 data bytes\n", target))?,
+        }
+        
+        let mut rng = rand::thread_rng();
+        let mut times = Vec::new();
+        for seq in 1..=scanner::PING_COUNT {
+            animations::pause(Duration::from_millis(400), &self.color_scheme).await;
+            if reachable {
+                let time = rng.gen_range(8.0..180.0f32);
+                let hops = rng.gen_range(3..16);
+                times.push(time);
+                self.color_scheme.print_colored(&format!(
+                    "  64 bytes from {}: icmp_seq={} ttl={} time={:.1} ms\n",
+                    target, seq, ttl - hops, time
+                ))?;
+            } else {
+                self.color_scheme.print_warning("  Request timed out.\n")?;
+            }
+        }
+        
+        let loss = 100 * (scanner::PING_COUNT - times.len()) / scanner::PING_COUNT;
+        println!();
+        self.color_scheme.print_dim(&format!(
+            "  {} packets transmitted, {} received, {}% packet loss\n",
+            scanner::PING_COUNT,
+            times.len(),
+            loss
+        ))?;
+        if let Some((min, avg, max)) = scanner::ping_summary(&times) {
+            self.color_scheme.print_dim(&format!("  rtt min/avg/max = {:.1}/{:.1}/{:.1} ms\n", min, avg, max))?;
+        }
+        
+        self.raise_heat(state, scanner::PING_HEAT);
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle connect command
+    async fn handle_connect(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let Some(ip) = args.first() else {
+            self.color_scheme.print_error("  [!] Usage: connect <ip>\n")?;
+            return Ok(CommandResult::Continue);
+        };
+        
+        let Some(node) = state.network_map.get_node(ip) else {
+            self.color_scheme.print_error(&format!("  [!] Unknown host {} - scan for it first\n", ip))?;
+            return Ok(CommandResult::Continue);
+        };
+        
+        self.color_scheme.print_success(&format!("  [✓] Session opened on {} ({})\n", node.hostname, ip))?;
+        if node.is_compromised {
+            self.color_scheme.print_dim("      Use 'ls' to look around and 'cat <file>' to read\n")?;
+        } else {
+            self.color_scheme.print_warning("      No shell yet - exploit the host to browse its files\n")?;
+        }
+        state.current_target = Some(ip.clone());
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle disconnect command
+    async fn handle_disconnect(&self, state: &mut GameState) -> Result<CommandResult> {
+        match state.current_target.take() {
+            Some(ip) => self.color_scheme.print_dim(&format!("  [>] Session on {} closed\n", ip))?,
+            None => self.color_scheme.print_warning("  [!] Not connected to a host\n")?,
+        }
+        Ok(CommandResult::Continue)
+    }
+
+    /// The compromised node the session is connected to, reporting when there isn't one
+    fn connected_node<'a>(&self, state: &'a GameState) -> Result<Option<&'a NetworkNode>> {
+        match state.current_target.as_deref().and_then(|ip| state.network_map.get_node(ip)) {
+            Some(node) if node.is_compromised => Ok(Some(node)),
+            Some(node) => {
+                self.color_scheme.print_error(&format!("  [!] No shell on {} - exploit it first\n", node.ip))?;
+                Ok(None)
+            }
+            None => {
+                self.color_scheme.print_error("  [!] Not connected to a host - use 'connect <ip>' first\n")?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Handle ls command
+    async fn handle_ls(&self, state: &mut GameState) -> Result<CommandResult> {
+        let Some(node) = self.connected_node(state)? else {
+            return Ok(CommandResult::Continue);
+        };
+        
+        println!();
+        for file in hostfs::list_files(node) {
+            self.color_scheme.print_dim(&format!("  {}  {:>10}  ", file.permissions, decrypt::format_size(file.size)))?;
+            self.color_scheme.print_colored(&format!("{}\n", file.path))?;
+        }
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle cat command
+    async fn handle_cat(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let Some(name) = args.first() else {
+            self.color_scheme.print_error("  [!] Usage: cat <file>\n")?;
+            return Ok(CommandResult::Continue);
+        };
+        let Some(node) = self.connected_node(state)? else {
+            return Ok(CommandResult::Continue);
+        };
+        let Some(file) = hostfs::find_file(node, name) else {
+            self.color_scheme.print_error(&format!("  [!] cat: {}: No such file or directory\n", name))?;
+            return Ok(CommandResult::Continue);
+        };
+        
+        println!();
+        for line in decrypt::generate_file_content(&file.path).lines() {
+            self.color_scheme.print_colored(&format!("  {}\n", line))?;
+        }
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle trace command
+    async fn handle_trace(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let target = if args.is_empty() {
+            crate::commands::generate_random_ip()
+        } else {
+            args[0].clone()
+        };
+
+        println!();
+        self.color_scheme.print_colored(&format!(
+            "  [>] Tracing route to {} as {}...\n\n",
+            target,
+            state.display_name()
+        ))?;
+        
+        let hops = rand::random::<u8>() % 10 + 5;
+        
+        // Mapped hosts may notice the probe; random addresses and hosts already owned don't
+        let chance = state.network_map.get_node(&target)
+            .filter(|node| !node.is_compromised)
+            .map_or(0.0, |node| scanner::trace_detection_chance(&node.security_level));
+        let detected_at = scanner::roll_trace_detection(hops, chance, &mut rand::thread_rng());
+        
+        for i in 1..=hops {
+            let hop_ip = crate::commands::generate_random_ip();
+            let latency = rand::random::<u16>() % 150 + 10;
+            
+            self.color_scheme.print_colored(&format!("  {:2}  ", i))?;
+            animations::type_text_effect(&hop_ip, 20, &self.color_scheme).await?;
+            self.color_scheme.print_dim(&format!("  [{} ms]", latency))?;
+            
+            if detected_at == Some(i) {
+                self.color_scheme.print_error("  [TRACE DETECTED]\n\n")?;
+                self.color_scheme.print_error(&format!("  [!] Trace aborted at hop {}: {} flagged the probe\n", i, target))?;
+                self.raise_heat(state, scanner::TRACE_DETECTED_HEAT);
+                return Ok(CommandResult::Continue);
+            }
+            
+            if i == hops {
+                self.color_scheme.print_success("  [TARGET REACHED]")?;
+            }
+            
+            println!();
+            animations::pause(Duration::from_millis(300), &self.color_scheme).await;
+        }
+        
+        println!();
+        self.color_scheme.print_success(&format!("  [✓] Trace complete: {} hops to target\n", hops))?;
+        
+        self.raise_heat(state, 3.0);
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle status command
+    async fn handle_status(&self, state: &mut GameState) -> Result<CommandResult> {
+        println!();
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        self.color_scheme.print_bright("                    AGENT STATUS                               \n")?;
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        println!();
+        
+        self.color_scheme.print_colored(&format!("  Agent:      {}\n", state.display_name()))?;
+        self.color_scheme.print_colored(&format!("  Reputation: {}\n", state.reputation))?;
+        
+        let manager = &state.reputation_manager;
+        match manager.streak_seconds_remaining() {
+            Some(remaining) if remaining > 0 => {
+                self.color_scheme.print_colored(&format!(
+                    "  Streak:     {} (x{:.2}) - {}\n",
+                    manager.streak,
+                    manager.multiplier,
+                    manager.get_streak_bonus_description()
+                ))?;
+                self.color_scheme.print_dim(&format!("              {}s left to keep it going\n", remaining))?;
+            }
+            _ => self.color_scheme.print_dim("  Streak:     none - act quickly to build a multiplier\n")?,
+        }
+        
+        // Heat level with visual indicator
+        let heat_bar = self.create_heat_bar(state.heat_level);
+        self.color_scheme.print_colored("  Heat Level: ")?;
+        if state.heat_level > 75.0 {
+            self.color_scheme.print_error(&heat_bar)?;
+        } else if state.heat_level > 50.0 {
+            self.color_scheme.print_warning(&heat_bar)?;
+        } else {
+            self.color_scheme.print_success(&heat_bar)?;
+        }
+        println!();
+        
+        self.color_scheme.print_colored(&format!("  Missions:   {} completed\n", state.missions_completed))?;
+        self.color_scheme.print_colored(&format!("  Hacks:      {} successful\n", state.successful_hacks))?;
+        self.color_scheme.print_colored(&format!("  Played:     {}\n", crate::utils::format_duration(state.time_played)))?;
+        
+        println!();
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle records command
+    async fn handle_records(&self, state: &mut GameState) -> Result<CommandResult> {
+        let stats = &state.stats;
+        let or_none = |text: &str| if text.is_empty() { "none yet".to_string() } else { text.to_string() };
+        
+        println!();
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        self.color_scheme.print_bright("                    PERSONAL RECORDS                           \n")?;
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        println!();
+        
+        self.color_scheme.print_colored(&format!("  Reputation earned:  {}\n", stats.total_reputation_earned))?;
+        self.color_scheme.print_colored(&format!("  Credits earned:     {}\n", stats.total_credits_earned))?;
+        self.color_scheme.print_colored(&format!("  Credits spent:      {}\n", stats.total_credits_spent))?;
+        self.color_scheme.print_colored(&format!("  Highest heat:       {:.0}%\n", stats.highest_heat_level))?;
+        self.color_scheme.print_colored(&format!("  Longest session:    {}\n", crate::utils::format_duration(stats.longest_session)))?;
+        println!();
+        self.color_scheme.print_colored(&format!("  Perfect hacks:      {}\n", stats.perfect_hacks))?;
+        self.color_scheme.print_colored(&format!("  Close calls:        {}\n", stats.close_calls))?;
+        self.color_scheme.print_colored(&format!("  Exploits mastered:  {}\n", stats.unique_exploits_used))?;
+        self.color_scheme.print_colored(&format!("  Most hacked system: {}\n", or_none(&stats.most_hacked_system)))?;
+        self.color_scheme.print_colored(&format!("  Favorite command:   {}\n", or_none(&stats.favorite_command)))?;
+        
+        println!();
+        self.color_scheme.print_dim("  Perfect hacks land with no heat on you; close calls push heat past 90%\n")?;
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle mission command
+    async fn handle_mission(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let id = args.get(1);
+        match (args.first().map(|s| s.as_str()), id) {
+            (None | Some("list"), _) => self.list_missions(state)?,
+            (Some("view"), Some(id)) => {
+                // Accepted missions show live progress, others the fresh briefing
+                let mission = state.get_active_mission(id).cloned().or_else(|| crate::game::find_mission(id));
+                match mission {
+                    Some(mission) => self.show_mission(state, &mission)?,
+                    None => self.color_scheme.print_error(&format!("  [!] Unknown mission: {}\n", id))?,
+                }
+            }
+            (Some("accept"), Some(id)) => {
+                if state.start_mission(id.clone()) {
+                    let mission = state.get_active_mission(id).cloned().expect("mission was just started");
+                    self.color_scheme.print_success(&format!("  [✓] Mission accepted: {}\n", mission.name))?;
+                    self.show_mission(state, &mission)?;
+                } else if crate::game::find_mission(id).is_none() {
+                    self.color_scheme.print_error(&format!("  [!] Unknown mission: {}\n", id))?;
+                } else {
+                    self.color_scheme.print_warning(&format!("  [!] Mission {} is already active or completed\n", id.to_uppercase()))?;
+                }
+            }
+            _ => self.color_scheme.print_error("  [!] Usage: mission [list|view <id>|accept <id>]\n")?,
+        }
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Print the mission catalogue with each mission's status
+    fn list_missions(&self, state: &GameState) -> Result<()> {
+        println!();
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        self.color_scheme.print_bright("                    MISSION BRIEFING                           \n")?;
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        println!();
+        
+        for mission in crate::game::generate_missions() {
+            self.color_scheme.print_bright(&format!("  [{}] {}", mission.id, mission.name))?;
+            if let Some(active) = state.get_active_mission(&mission.id) {
+                self.color_scheme.print_warning(&format!("  ACTIVE {:.0}%\n", active.get_completion_percentage()))?;
+            } else if state.completed_missions.contains(&mission.id) {
+                self.color_scheme.print_success("  COMPLETED\n")?;
+            } else {
+                println!();
+            }
+            self.color_scheme.print_colored(&format!("    {}\n", mission.description))?;
+            self.print_mission_risk(&mission)?;
+            println!();
+        }
+        
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        self.color_scheme.print_dim("  Type 'mission view <id>' for objectives, 'mission accept <id>' to take it on\n")?;
+        Ok(())
+    }
+
+    /// Print a mission's objectives and progress
+    fn show_mission(&self, state: &GameState, mission: &Mission) -> Result<()> {
+        println!();
+        self.color_scheme.print_bright(&format!("  [{}] {}\n", mission.id, mission.name))?;
+        self.color_scheme.print_colored(&format!("    {}\n", mission.description))?;
+        self.print_mission_risk(mission)?;
+        
+        println!();
+        for objective in &mission.objectives {
+            let line = format!(
+                "    {} {} ({}/{})\n",
+                if objective.is_completed { "[✓]" } else { "[ ]" },
+                objective.description,
+                objective.progress.min(objective.required),
+                objective.required
+            );
+            if objective.is_completed {
+                self.color_scheme.print_success(&line)?;
+            } else {
+                self.color_scheme.print_colored(&line)?;
+            }
+        }
+        
+        println!();
+        let status = if state.get_active_mission(&mission.id).is_some() {
+            "ACTIVE"
+        } else if state.completed_missions.contains(&mission.id) {
+            "COMPLETED"
+        } else {
+            "AVAILABLE"
+        };
+        self.color_scheme.print_dim(&format!(
+            "    Status: {}  |  Completion: {:.0}%\n",
+            status,
+            mission.get_completion_percentage()
+        ))?;
+        if let Some(remaining) = mission.time_remaining(chrono::Utc::now()) {
+            self.color_scheme.print_warning(&format!(
+                "    Time left: {}m {:02}s\n",
+                remaining.as_secs() / 60,
+                remaining.as_secs() % 60
+            ))?;
+        } else if let Some(limit) = mission.time_limit {
+            self.color_scheme.print_dim(&format!("    Time limit: {} minutes once accepted\n", limit.as_secs() / 60))?;
+        }
+        Ok(())
+    }
+
+    /// Print a mission's difficulty and rewards
+    fn print_mission_risk(&self, mission: &Mission) -> Result<()> {
+        let risk = format!("    Risk: {}\n", format!("{:?}", mission.difficulty).to_uppercase());
+        match mission.difficulty {
+            MissionDifficulty::Extreme | MissionDifficulty::Impossible => self.color_scheme.print_error(&risk)?,
+            MissionDifficulty::Hard | MissionDifficulty::Medium => self.color_scheme.print_warning(&risk)?,
+            _ => self.color_scheme.print_success(&risk)?,
+        }
+        self.color_scheme.print_colored(&format!(
+            "    Reward: {} reputation, {} credits\n",
+            mission.reward_reputation, mission.reward_credits
+        ))?;
+        Ok(())
+    }
+
+    /// Handle darkweb command
+    async fn handle_darkweb(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        // Show biohazard warning
+        println!();
+        self.color_scheme.print_error(crate::ui::ascii_art::BIOHAZARD)?;
+        self.color_scheme.print_warning("\n  [!] ENTERING DARK WEB - ILLEGAL CONTENT WARNING\n")?;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
+        
+        self.color_scheme.print_colored("  [>] Connecting to dark web...\n")?;
+        loop {
+            animations::show_processing("Establishing TOR connection", 2000, &self.color_scheme).await?;
+            
+            // Hot agents get refused by nervous exit nodes
+            if rand::random::<f32>() >= darkweb::tor_failure_chance(state.heat_level) {
+                break;
+            }
+            
+            self.raise_heat(state, darkweb::TOR_FAILURE_HEAT);
+            self.color_scheme.print_error("\n  [✗] TOR circuit collapsed - exit nodes are refusing your traffic\n")?;
+            self.color_scheme.print_dim(&format!("      Heat {:.0}%: the hotter you are, the fewer relays will carry you\n", state.heat_level))?;
+            
+            if !self.interactive || !crate::ui::menu::confirm("Retry connection?", &self.color_scheme)? {
+                return Ok(CommandResult::Continue);
+            }
+        }
+        
+        if args.first().map(|s| s.as_str()) == Some("buy") {
+            return self.handle_darkweb_buy(state, args.get(1).map(|s| s.as_str())).await;
+        }
+        
+        println!();
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        self.color_scheme.print_bright("              DARK WEB MARKETPLACE                             \n")?;
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        println!();
+        
+        for item in darkweb::CATALOG {
+            if state.has_tool(item.tool) {
+                self.color_scheme.print_success(&format!("  • {} [owned]\n", item.name))?;
+                self.color_scheme.print_dim(&format!("    {}\n", item.effect))?;
+            } else if item.is_available(state.reputation) {
+                self.color_scheme.print_colored(&format!("  • {} [{}]\n", item.name, item.id))?;
+                self.color_scheme.print_dim(&format!("    Price: {} credits - {}\n", item.price, item.effect))?;
+            } else {
+                // Locked listings stay visible so there is something to work towards
+                self.color_scheme.print_dim(&format!(
+                    "  • {} [locked - requires {}]\n",
+                    item.name,
+                    item.required_level.display_name()
+                ))?;
+                self.color_scheme.print_dim(&format!("    Price: {} credits\n", item.price))?;
+            }
+        }
+        
+        println!();
+        self.color_scheme.print_bright("  SERVICES\n")?;
+        self.color_scheme.print_colored("  • Log Cleanup [cleanup]\n")?;
+        self.color_scheme.print_dim(&format!(
+            "    Price: {} credits (scales with heat) - removes {:.0}% heat\n",
+            darkweb::cleanup_cost(state.heat_level),
+            darkweb::CLEANUP_HEAT_REDUCTION
+        ))?;
+        
+        println!();
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        self.color_scheme.print_dim("  Type 'darkweb buy <item_id>' to purchase\n")?;
+        
+        self.raise_heat(state, 5.0);
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle tools command
+    async fn handle_tools(&self, state: &mut GameState) -> Result<CommandResult> {
+        let all_tools = tools::all_tools();
+        
+        println!();
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        self.color_scheme.print_bright("                    TOOLKIT                                    \n")?;
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        
+        for source in [tools::ToolSource::Starter, tools::ToolSource::Market, tools::ToolSource::Field] {
+            println!();
+            self.color_scheme.print_bright(&format!("  {}\n", source.heading()))?;
+            let mut listed = 0;
+            for tool in all_tools.iter().filter(|t| t.source == source) {
+                let reachable = state.reputation >= tool.required_level.reputation_requirement();
+                if state.has_tool(tool.id) {
+                    self.color_scheme.print_success(&format!("  • {} [{}]\n", tool.name, tool.id))?;
+                    self.color_scheme.print_dim(&format!("    {}\n", tool.description))?;
+                } else if !reachable {
+                    self.color_scheme.print_dim(&format!(
+                        "  • {} [locked - reach {}]\n",
+                        tool.name,
+                        tool.required_level.display_name()
+                    ))?;
+                } else if source == tools::ToolSource::Market {
+                    self.color_scheme.print_colored(&format!("  • {} [for sale on the dark web]\n", tool.name))?;
+                } else {
+                    continue;
+                }
+                listed += 1;
+            }
+            
+            // Tools from older events that aren't catalogued still belong to the agent
+            if source == tools::ToolSource::Field {
+                for id in state.unlocked_tools.iter().filter(|id| tools::find_tool(id).is_none()) {
+                    self.color_scheme.print_success(&format!("  • {}\n", id))?;
+                    listed += 1;
+                }
+            }
+            if listed == 0 {
+                self.color_scheme.print_dim("  Nothing yet - random events sometimes turn up gear\n")?;
+            }
+        }
+        
+        println!();
+        self.color_scheme.print_bright("  DISCOVERED EXPLOITS\n")?;
+        if state.discovered_exploits.is_empty() {
+            self.color_scheme.print_dim("  None yet - keep an eye out during events\n")?;
+        }
+        for name in &state.discovered_exploits {
+            self.color_scheme.print_warning(&format!("  • {}\n", name))?;
+            self.color_scheme.print_dim(&format!("    {}\n", tools::exploit_description(name)))?;
+        }
+        
+        println!();
+        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle darkweb purchase
+    async fn handle_darkweb_buy(&self, state: &mut GameState, item: Option<&str>) -> Result<CommandResult> {
+        match item {
+            Some("cleanup") => {
+                let cost = darkweb::cleanup_cost(state.heat_level);
+                
+                if !state.spend_credits(cost) {
+                    self.color_scheme.print_error(&format!(
+                        "  [!] Insufficient credits: cleanup costs {} (you have {})\n",
+                        cost, state.credits
+                    ))?;
+                    return Ok(CommandResult::Continue);
+                }
+                
+                self.color_scheme.print_colored("  [>] Contacting cleanup crew...\n")?;
+                animations::show_processing("Scrubbing logs", 2500, &self.color_scheme).await?;
+                self.color_scheme.print_colored("  [>] Rewriting access records...\n")?;
+                animations::show_processing("Burning evidence trail", 1500, &self.color_scheme).await?;
+                
+                state.decrease_heat(darkweb::CLEANUP_HEAT_REDUCTION);
+                state.record_transaction("Log Cleanup", -cost);
+                
+                self.color_scheme.print_success(&format!(
+                    "\n  [✓] Logs scrubbed for {} credits. Heat now {:.0}%\n",
+                    cost, state.heat_level
+                ))?;
+            }
+            Some(id) => {
+                let Some(item) = darkweb::find_item(id) else {
+                    self.color_scheme.print_error(&format!("  [!] No such item for sale: {}\n", id))?;
+                    return Ok(CommandResult::Continue);
+                };
+                
+                if !item.is_available(state.reputation) {
+                    self.color_scheme.print_error(&format!(
+                        "  [!] The seller won't deal with you. {} requires {} rank\n",
+                        item.name,
+                        item.required_level.display_name()
+                    ))?;
+                    return Ok(CommandResult::Continue);
+                }
+                
+                if state.has_tool(item.tool) {
+                    self.color_scheme.print_warning(&format!("  [!] You already own the {}\n", item.name))?;
+                    return Ok(CommandResult::Continue);
+                }
+                
+                if !state.spend_credits(item.price) {
+                    self.color_scheme.print_error(&format!(
+                        "  [!] Insufficient credits: {} costs {} (you have {})\n",
+                        item.name, item.price, state.credits
+                    ))?;
+                    return Ok(CommandResult::Continue);
+                }
+                
+                animations::show_processing("Transferring funds through mixers", 2000, &self.color_scheme).await?;
+                state.record_transaction(item.name, -item.price);
+                state.unlock_tool(item.tool.to_string());
+                
+                self.color_scheme.print_success(&format!(
+                    "\n  [✓] Purchased {} for {} credits\n",
+                    item.name, item.price
+                ))?;
+                self.color_scheme.print_colored(&format!("  [+] Unlocked tool: {} - {}\n", item.tool, item.effect))?;
+            }
+            None => {
+                self.color_scheme.print_error("  [!] Usage: darkweb buy <item_id>\n")?;
+            }
+        }
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle firewall command
+    async fn handle_firewall(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        if args.is_empty() {
+            self.color_scheme.print_error("  [!] Usage: firewall <target> [bypass|disable|analyze]\n")?;
+            return Ok(CommandResult::Continue);
+        }
+
+        let target = &args[0];
+        let action = args.get(1).map(|s| s.as_str()).unwrap_or("analyze");
+        
+        // Show firewall ASCII art
+        println!();
+        self.color_scheme.print_colored(crate::ui::ascii_art::FIREWALL)?;
+        println!();
+        animations::pause(Duration::from_millis(1000), &self.color_scheme).await;
+        
+        self.color_scheme.print_colored(&format!("  [>] Analyzing firewall on {}...\n", target))?;
+        animations::show_processing("Detecting firewall rules", 1500, &self.color_scheme).await?;
+        
+        match action {
+            "bypass" => {
+                self.color_scheme.print_colored("  [>] Attempting to bypass firewall...\n")?;
+                animations::show_processing("Exploiting vulnerabilities", 2000, &self.color_scheme).await?;
+                self.color_scheme.print_success("\n  [✓] Firewall bypassed successfully\n")?;
+                state.advance_missions(ObjectiveKind::FirewallBypass, 1);
+                state.add_reputation(self.config.game.reputation_formula.firewall_bypass);
+                self.raise_heat(state, 30.0);
+            }
+            "disable" => {
+                self.color_scheme.print_colored("  [>] Attempting to disable firewall...\n")?;
+                animations::show_processing("Sending kill packets", 2500, &self.color_scheme).await?;
+                self.color_scheme.print_warning("\n  [!] Firewall temporarily disabled\n")?;
+                state.add_reputation(self.config.game.reputation_formula.firewall_disable);
+                self.raise_heat(state, 40.0);
+            }
+            _ => {
+                self.color_scheme.print_colored("\n  Firewall Analysis:\n")?;
+                self.color_scheme.print_colored("    Type: Next-Gen Enterprise Firewall\n")?;
+                self.color_scheme.print_colored("    Rules: 247 active\n")?;
+                self.color_scheme.print_colored("    IDS/IPS: Enabled\n")?;
+                self.color_scheme.print_warning("    Vulnerabilities: 3 potential weaknesses detected\n")?;
+                self.raise_heat(state, 5.0);
+            }
+        }
+        
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle stealth command
+    async fn handle_stealth(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let requirement = ReputationLevel::Skilled;
+        let turn_on = match args.first().map(|s| s.as_str()) {
+            Some("on") => true,
+            Some("off") => false,
+            _ => {
+                let status = if state.stealth { "on" } else { "off" };
+                self.color_scheme.print_colored(&format!("  [*] Stealth mode is {}\n", status))?;
+                self.color_scheme.print_dim("  Usage: stealth <on|off>\n")?;
+                return Ok(CommandResult::Continue);
+            }
+        };
+
+        if turn_on && state.reputation < requirement.reputation_requirement() {
+            self.color_scheme.print_error(&format!(
+                "  [!] Stealth mode unlocks at {}\n",
+                requirement.display_name()
+            ))?;
+            return Ok(CommandResult::Continue);
+        }
+        if state.stealth == turn_on {
+            self.color_scheme.print_dim(&format!("  [*] Stealth mode is already {}\n", args[0]))?;
+            return Ok(CommandResult::Continue);
+        }
+
+        state.stealth = turn_on;
+        if turn_on {
+            self.color_scheme.print_success("  [✓] Stealth mode on: half the heat, half the reputation, and everything takes longer\n")?;
+        } else {
+            self.color_scheme.print_success("  [✓] Stealth mode off. Back to full speed\n")?;
+        }
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle spoof command
+    async fn handle_spoof(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        match args.first().map(|s| s.as_str()) {
+            None => {
+                match state.spoofed_name {
+                    Some(ref name) => self.color_scheme.print_colored(&format!("  [*] Cover identity active: {}\n", name))?,
+                    None => self.color_scheme.print_dim("  [*] No cover identity active\n")?,
+                }
+                self.color_scheme.print_dim("  Usage: spoof <name> | spoof --reset\n")?;
+            }
+            Some("--reset") => {
+                if state.spoofed_name.take().is_some() {
+                    self.color_scheme.print_success(&format!(
+                        "  [✓] Cover dropped. Operating as {}\n",
+                        state.username
+                    ))?;
+                } else {
+                    self.color_scheme.print_dim("  [*] No cover identity active\n")?;
+                }
+            }
+            Some(name) => {
+                let valid = (3..=20).contains(&name.chars().count())
+                    && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+                if !valid {
+                    self.color_scheme.print_error("  [!] Cover name must be 3-20 letters, numbers, '_' or '-'\n")?;
+                    return Ok(CommandResult::Continue);
+                }
+                
+                if !state.spend_credits(SPOOF_COST) {
+                    self.color_scheme.print_error(&format!("  [!] Insufficient credits: a cover identity costs {}\n", SPOOF_COST))?;
+                    return Ok(CommandResult::Continue);
+                }
+                
+                animations::show_processing("Forging identity records", 1500, &self.color_scheme).await?;
+                state.record_transaction("Cover Identity", -SPOOF_COST);
+                state.spoofed_name = Some(name.to_string());
+                state.advance_missions(ObjectiveKind::Spoof, 1);
+                self.color_scheme.print_success(&format!("  [✓] Now operating as {}\n", name))?;
+            }
         }
         
         Ok(CommandResult::Continue)
     }
 
-    /// Handle trace command
-    async fn handle_trace(&mut self, args: Vec<String>) -> Result<CommandResult> {
-        let target = if args.is_empty() {
-            crate::commands::generate_random_ip()
-        } else {
-            args[0].clone()
+    /// Handle achievements command
+    async fn handle_achievements(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let definitions = crate::game::generate_achievements();
+        let name_of = |id: &str| {
+            definitions
+                .iter()
+                .find(|a| a.id == id)
+                .map_or(id.to_string(), |a| a.name.clone())
         };
-
-        println!();
-        self.color_scheme.print_colored(&format!("  [>] Tracing route to {}...\n\n", target))?;
-        
-        let hops = rand::random::<u8>() % 10 + 5;
         
-        for i in 1..=hops {
-            let hop_ip = crate::commands::generate_random_ip();
-            let latency = rand::random::<u16>() % 150 + 10;
-            
-            self.color_scheme.print_colored(&format!("  {:2}  ", i))?;
-            animations::type_text_effect(&hop_ip, 20, &self.color_scheme).await?;
-            self.color_scheme.print_dim(&format!("  [{} ms]", latency))?;
-            
-            if i == hops {
-                self.color_scheme.print_success("  [TARGET REACHED]")?;
+        println!();
+        if args.iter().any(|a| a == "--recent") {
+            self.color_scheme.print_bright("  RECENTLY UNLOCKED\n")?;
+            let recent = state.recent_achievements(5);
+            if recent.is_empty() {
+                self.color_scheme.print_dim("  [*] No achievements unlocked yet\n")?;
+            }
+            for unlocked in recent {
+                self.color_scheme.print_success(&format!("  🏆 {:<24}", name_of(&unlocked.id)))?;
+                self.color_scheme.print_dim(&format!(" {}\n", unlocked.unlocked_at.format("%Y-%m-%d %H:%M UTC")))?;
+            }
+        } else {
+            self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+            self.color_scheme.print_bright("                       ACHIEVEMENTS                            \n")?;
+            self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+            for achievement in &definitions {
+                let unlocked = state.unlocked_achievements.iter().find(|u| u.id == achievement.id);
+                let rarity = format!("{:?}", achievement.rarity).to_uppercase();
+                match unlocked {
+                    Some(unlocked) => {
+                        let (r, g, b) = achievement.rarity.color_rgb();
+                        self.color_scheme.print_success(&format!("  {} {:<30}", achievement.icon, achievement.name))?;
+                        self.color_scheme.print_rgb(&format!(" {:<10}", rarity), r, g, b)?;
+                        self.color_scheme.print_colored(&format!(" {:>3} pts\n", achievement.points))?;
+                        self.color_scheme.print_dim(&format!(
+                            "     {} - {}\n",
+                            achievement.description,
+                            unlocked.unlocked_at.format("%Y-%m-%d %H:%M UTC")
+                        ))?;
+                    }
+                    None => {
+                        self.color_scheme.print_dim(&format!(
+                            "  [ ? ] {:<28} {:<10} {:>3} pts\n     {}\n",
+                            achievement.name, rarity, achievement.points, achievement.description
+                        ))?;
+                    }
+                }
             }
             
-            println!();
-            sleep(Duration::from_millis(300)).await;
+            let progress = crate::game::achievements::progress(state);
+            self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+            self.color_scheme.print_bright(&format!(
+                "  {}/{} unlocked ({:.0}%)  |  {}/{} points\n",
+                progress.unlocked,
+                progress.total,
+                progress.percent(),
+                progress.points,
+                progress.max_points
+            ))?;
+            self.color_scheme.print_dim("  Type 'achievements --recent' for the latest unlocks\n")?;
         }
         
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle hint command
+    async fn handle_hint(&self, state: &mut GameState) -> Result<CommandResult> {
         println!();
-        self.color_scheme.print_success(&format!("  [✓] Trace complete: {} hops to target\n", hops))?;
+        match hint_for(state) {
+            Some(hint) => self.color_scheme.print_bright(&format!("  [?] {}\n", hint))?,
+            None => self.color_scheme.print_dim("  [?] No suggestions right now - you know what you're doing\n")?,
+        }
+        Ok(CommandResult::Continue)
+    }
+
+    /// Suggest the next useful action based on current game state
+    pub async fn current_hint(&self) -> Option<String> {
+        hint_for(&*self.game_state.lock().await)
+    }
+
+    /// Handle map command
+    async fn handle_map(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let map = &state.network_map;
+        if map.discovered_nodes.is_empty() {
+            self.color_scheme.print_warning("  [!] No hosts discovered yet\n")?;
+            self.color_scheme.print_dim("      Run 'scan' to start mapping the network\n")?;
+            return Ok(CommandResult::Continue);
+        }
         
-        self.game_state.increase_heat(3.0);
+        let mut pager = Pager::new(self.page_height);
+        pager.blank();
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.bright("                    NETWORK MAP                                \n");
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.blank();
+        
+        match args.first() {
+            Some(ip) => {
+                let neighbors = map.get_connected_nodes(ip);
+                match map.get_node(ip) {
+                    Some(node) => push_map_line(&mut pager, &netmap::describe_node(node), Some(node)),
+                    None if !neighbors.is_empty() => push_map_line(&mut pager, &format!("[{}]", ip), None),
+                    None => {
+                        self.color_scheme.print_error(&format!("  [!] Unknown host: {}\n", ip))?;
+                        return Ok(CommandResult::Continue);
+                    }
+                }
+                
+                if neighbors.is_empty() {
+                    pager.dim("  No known neighbors. Try 'pivot' once it's compromised.\n");
+                }
+                for (i, node) in neighbors.iter().enumerate() {
+                    let branch = if i == neighbors.len() - 1 { "└── " } else { "├── " };
+                    push_map_line(&mut pager, &format!("{}{}", branch, netmap::describe_node(node)), Some(node));
+                }
+            }
+            None => {
+                for line in netmap::render_tree(map) {
+                    push_map_line(&mut pager, &line.text, line.node);
+                }
+                pager.blank();
+                pager.dim(&format!(
+                    "  {} hosts known. Use 'map <ip>' to focus on one.\n",
+                    map.discovered_nodes.len()
+                ));
+            }
+        }
         
+        pager.show(&self.color_scheme)?;
         Ok(CommandResult::Continue)
     }
 
-    /// Handle status command
-    async fn handle_status(&mut self) -> Result<CommandResult> {
-        println!();
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        self.color_scheme.print_bright("                    AGENT STATUS                               \n")?;
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+    /// Handle card command
+    async fn handle_card(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let plain = args.iter().any(|a| a == "--plain");
+        let rank = crate::game::ReputationLevel::from_reputation(state.reputation);
+        
+        let fields = [
+            ("AGENT", state.display_name().to_uppercase()),
+            ("RANK", rank.display_name().to_string()),
+            ("LEVEL", format!("{} - {}", state.get_level(), state.get_level_title())),
+            ("REPUTATION", state.reputation.to_string()),
+            ("SUCCESS RATE", format!("{:.0}%", state.get_success_rate())),
+            ("ACHIEVEMENTS", format!(
+                "{}/{}",
+                state.unlocked_achievements.len(),
+                crate::game::generate_achievements().len()
+            )),
+            ("CREDITS", state.credits.to_string()),
+        ];
+        let card = crate::ui::card::render_card("CRIMSON-REDLINE AGENT", &fields);
+        
         println!();
+        if plain {
+            // No ANSI codes, so it pastes cleanly into chat
+            for line in &card {
+                println!("{}", line);
+            }
+        } else {
+            let from = self.config.get_color_rgb();
+            let to = (from.0 / 3, from.1 / 3, from.2 / 3);
+            for (i, line) in card.iter().enumerate() {
+                if i == 0 || i == card.len() - 1 {
+                    self.color_scheme.print_gradient(line, from, to)?;
+                } else {
+                    self.color_scheme.print_colored(line)?;
+                }
+                println!();
+            }
+            self.color_scheme.print_dim("\n  Use 'card --plain' for a copy-paste friendly version\n")?;
+        }
         
-        self.color_scheme.print_colored(&format!("  Agent:      {}\n", self.game_state.username))?;
-        self.color_scheme.print_colored(&format!("  Reputation: {}\n", self.game_state.reputation))?;
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle profile command
+    async fn handle_profile(&self, state: &mut GameState) -> Result<CommandResult> {
+        let fields = [
+            ("AGENT", state.display_name().to_string()),
+            ("RANK", state.reputation_level().display_name().to_string()),
+            ("LEVEL", format!("{} - {}", state.get_level(), state.get_level_title())),
+            ("REPUTATION", state.reputation.to_string()),
+            ("CREDITS", state.credits.to_string()),
+            ("HACKS", format!(
+                "{} ok / {} failed ({:.0}% success)",
+                state.successful_hacks,
+                state.failed_hacks,
+                state.get_success_rate()
+            )),
+            ("SYSTEMS OWNED", state.systems_compromised.to_string()),
+            ("FILES DECRYPTED", state.files_decrypted.to_string()),
+            ("MISSIONS", state.missions_completed.to_string()),
+            ("TIME PLAYED", crate::utils::format_duration(state.time_played)),
+            ("MEMBER SINCE", self.member_since.format("%Y-%m-%d").to_string()),
+            ("LOGINS", self.login_count.to_string()),
+        ];
         
-        // Heat level with visual indicator
-        let heat_bar = self.create_heat_bar(self.game_state.heat_level);
-        self.color_scheme.print_colored("  Heat Level: ")?;
-        if self.game_state.heat_level > 75.0 {
-            self.color_scheme.print_error(&heat_bar)?;
-        } else if self.game_state.heat_level > 50.0 {
-            self.color_scheme.print_warning(&heat_bar)?;
-        } else {
-            self.color_scheme.print_success(&heat_bar)?;
+        println!();
+        for line in crate::ui::card::render_card("AGENT DOSSIER", &fields) {
+            self.color_scheme.print_colored(&line)?;
+            println!();
         }
         println!();
         
-        self.color_scheme.print_colored(&format!("  Missions:   {} completed\n", self.game_state.missions_completed))?;
-        self.color_scheme.print_colored(&format!("  Hacks:      {} successful\n", self.game_state.successful_hacks))?;
+        Ok(CommandResult::Continue)
+    }
+
+    /// Handle history command
+    async fn handle_history(&self, args: Vec<String>) -> Result<CommandResult> {
+        let entries = self.history.entries();
+        let count = match args.first().map(|a| a.parse::<usize>()) {
+            Some(Ok(count)) => count,
+            Some(Err(_)) => {
+                self.color_scheme.print_error("  [!] Usage: history [count]\n")?;
+                return Ok(CommandResult::Continue);
+            }
+            None => 20,
+        };
         
         println!();
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+        let start = entries.len().saturating_sub(count);
+        for (i, entry) in entries.iter().enumerate().skip(start) {
+            self.color_scheme.print_dim(&format!("  {:>4}  ", i + 1))?;
+            self.color_scheme.print_colored(&format!("{}\n", entry))?;
+        }
+        self.color_scheme.print_dim("\n  Use the up/down arrows to recall commands\n")?;
         
         Ok(CommandResult::Continue)
     }
 
-    /// Handle mission command
-    async fn handle_mission(&mut self, _args: Vec<String>) -> Result<CommandResult> {
-        println!();
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        self.color_scheme.print_bright("                    MISSION BRIEFING                           \n")?;
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        println!();
+    /// Handle events command
+    async fn handle_events(&self, state: &mut GameState, args: Vec<String>) -> Result<CommandResult> {
+        let count = match args.first().map(|a| a.parse::<usize>()) {
+            Some(Ok(count)) => count,
+            Some(Err(_)) => {
+                self.color_scheme.print_error("  [!] Usage: events [count]\n")?;
+                return Ok(CommandResult::Continue);
+            }
+            None => 10,
+        };
         
-        let missions = vec![
-            ("ALPHA-01", "Infiltrate corporate mainframe", "HIGH", 50),
-            ("BETA-02", "Decrypt government communications", "MEDIUM", 30),
-            ("GAMMA-03", "Deploy ransomware to target", "EXTREME", 100),
-            ("DELTA-04", "Extract database from server", "LOW", 20),
-        ];
+        if state.event_log.is_empty() {
+            self.color_scheme.print_dim("\n  No events yet. The grid is quiet... for now.\n")?;
+            return Ok(CommandResult::Continue);
+        }
         
-        for (id, desc, risk, reward) in missions {
-            self.color_scheme.print_bright(&format!("  [{}]\n", id))?;
-            self.color_scheme.print_colored(&format!("    Description: {}\n", desc))?;
-            
-            match risk {
-                "EXTREME" => self.color_scheme.print_error(&format!("    Risk: {}\n", risk))?,
-                "HIGH" => self.color_scheme.print_warning(&format!("    Risk: {}\n", risk))?,
-                _ => self.color_scheme.print_success(&format!("    Risk: {}\n", risk))?,
+        let mut pager = Pager::new(self.page_height);
+        pager.blank();
+        let start = state.event_log.len().saturating_sub(count);
+        for record in &state.event_log[start..] {
+            pager.dim(&format!("  {}  ", record.at.format("%Y-%m-%d %H:%M")));
+            let tone = match record.severity {
+                EventSeverity::Critical => Tone::Error,
+                EventSeverity::High => Tone::Warning,
+                EventSeverity::Medium => Tone::Bright,
+                EventSeverity::Low => Tone::Primary,
+            };
+            pager.push(tone, &format!("{:<28}", record.title));
+            pager.dim(&format!(" {:<9}", format!("{:?}", record.severity)));
+            match &record.choice {
+                Some((index, label)) => pager.secondary(&format!("{}. {}\n", index + 1, label)),
+                None => pager.warning("timed out\n"),
             }
-            
-            self.color_scheme.print_colored(&format!("    Reward: {} reputation\n", reward))?;
-            println!();
         }
-        
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        self.color_scheme.print_dim("  Type 'mission accept <id>' to accept a mission\n")?;
+        pager.show(&self.color_scheme)?;
         
         Ok(CommandResult::Continue)
     }
 
-    /// Handle darkweb command
-    async fn handle_darkweb(&mut self, _args: Vec<String>) -> Result<CommandResult> {
-        // Show biohazard warning
-        println!();
-        self.color_scheme.print_error(crate::ui::ascii_art::BIOHAZARD)?;
-        self.color_scheme.print_warning("\n  [!] ENTERING DARK WEB - ILLEGAL CONTENT WARNING\n")?;
-        sleep(Duration::from_millis(1500)).await;
-        
-        self.color_scheme.print_colored("  [>] Connecting to dark web...\n")?;
-        animations::show_processing("Establishing TOR connection", 2000).await?;
+    /// Handle theme command
+    fn handle_theme(&mut self, args: Vec<String>) -> Result<CommandResult> {
+        let name = match args.first() {
+            Some(name) if name != "list" => name,
+            _ => {
+                self.color_scheme.print_bright("\n  Available themes:\n")?;
+                for theme in ColorTheme::ALL {
+                    let marker = if theme == self.config.display.color_theme { " (active)" } else { "" };
+                    let mut preview = self.config.display.clone();
+                    preview.color_theme = theme.clone();
+                    ColorScheme::from_display(&preview).print_colored(&format!("    {}{}\n", theme.name(), marker))?;
+                }
+                self.color_scheme.print_dim("\n  Usage: theme <name>\n")?;
+                return Ok(CommandResult::Continue);
+            }
+        };
+
+        let Some(theme) = ColorTheme::from_name(name) else {
+            self.color_scheme.print_error(&format!("  [!] Unknown theme: {}\n", name))?;
+            self.color_scheme.print_dim("      Type 'theme list' to see the options\n")?;
+            return Ok(CommandResult::Continue);
+        };
+
+        self.config.display.color_theme = theme.clone();
+        self.color_scheme = ColorScheme::from_display(&self.config.display);
+        if theme == ColorTheme::Custom && self.config.display.custom_colors.is_none() {
+            self.color_scheme.print_warning("  [!] No valid custom_colors in config - using crimson\n")?;
+        }
+
+        // Update the file on disk rather than our copy, which may be stale; scripts keep it to the session
+        if self.interactive {
+            let saved = Config::load().and_then(|mut config| {
+                config.display.color_theme = theme.clone();
+                config.save()
+            });
+            if let Err(e) = saved {
+                self.color_scheme.print_warning(&format!("  [!] Could not save theme: {}\n", e))?;
+            }
+        }
+
+        self.color_scheme.print_success(&format!("  [+] Theme set to {}\n", theme.name()))?;
+        self.color_scheme.print_colored("  root@redline:~# ")?;
+        self.color_scheme.print_bright("exploit ")?;
+        self.color_scheme.print_secondary("10.0.0.5 ")?;
+        self.color_scheme.print_warning("[heat +5%] ")?;
+        self.color_scheme.print_dim("sample output\n")?;
+
+        Ok(CommandResult::ThemeChanged(theme))
+    }
+
+    /// Handle difficulty command
+    async fn handle_difficulty(&self) -> Result<CommandResult> {
+        let modifier = self.difficulty();
+        let percent = |factor: f32| format!("{:+.0}%", (factor - 1.0) * 100.0);
         
         println!();
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        self.color_scheme.print_bright("              DARK WEB MARKETPLACE                             \n")?;
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        println!();
-        
-        let items = vec![
-            ("Zero-Day Exploit Kit", "1000"),
-            ("Botnet Access (10k nodes)", "5000"),
-            ("Database Dump (Fortune 500)", "2500"),
-            ("Custom Malware Framework", "3000"),
-            ("Stolen Credentials Pack", "500"),
-        ];
-        
-        for (item, price) in items {
-            self.color_scheme.print_colored(&format!("  • {}\n", item))?;
-            self.color_scheme.print_dim(&format!("    Price: {} credits\n", price))?;
+        self.color_scheme.print_bright(&format!("  Difficulty: {:?}\n\n", self.config.game.difficulty))?;
+        self.color_scheme.print_colored(&format!("    Heat per action:  {}\n", percent(modifier.heat_gain)))?;
+        self.color_scheme.print_colored(&format!("    Heat cooldown:    {}\n", percent(modifier.heat_decay)))?;
+        self.color_scheme.print_colored(&format!("    Random events:    {}\n", percent(modifier.event_chance)))?;
+        self.color_scheme.print_colored(&format!("    Success chance:   {}\n", percent(modifier.success_chance)))?;
+        if self.config.game.adaptive_difficulty {
+            self.color_scheme.print_dim("    Adaptive difficulty is on and adjusts these as you play\n")?;
         }
-        
         println!();
-        self.color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        
-        self.game_state.increase_heat(5.0);
+        self.color_scheme.print_dim("  Change it with 'config set difficulty <Script|Hacker|Ghost|Phantom>'\n")?;
         
         Ok(CommandResult::Continue)
     }
 
-    /// Handle firewall command
-    async fn handle_firewall(&mut self, args: Vec<String>) -> Result<CommandResult> {
-        if args.is_empty() {
-            self.color_scheme.print_error("  [!] Usage: firewall <target> [bypass|disable|analyze]\n")?;
-            return Ok(CommandResult::Continue);
+    /// Handle config command
+    fn handle_config(&mut self, args: Vec<String>) -> Result<CommandResult> {
+        match args.first().map(|s| s.as_str()) {
+            None | Some("show") => {
+                self.show_config()?;
+                Ok(CommandResult::Continue)
+            }
+            Some("reload") => self.reload_config(),
+            Some("set") => match (args.get(1), args.get(2)) {
+                (Some(key), Some(value)) => self.set_config(&key.to_lowercase(), value),
+                _ => {
+                    self.color_scheme.print_error("  [!] Usage: config set <key> <value>\n")?;
+                    self.color_scheme.print_dim(&format!("      Settable keys: {}\n", SETTABLE_KEYS.join(", ")))?;
+                    Ok(CommandResult::Continue)
+                }
+            },
+            Some(other) => {
+                self.color_scheme.print_error(&format!("  [!] Unknown config action: {}\n", other))?;
+                self.color_scheme.print_dim("      Usage: config [show | reload] | config set <key> <value>\n")?;
+                Ok(CommandResult::Continue)
+            }
         }
+    }
 
-        let target = &args[0];
-        let action = args.get(1).map(|s| s.as_str()).unwrap_or("analyze");
+    /// Print the display, security and game settings
+    fn show_config(&self) -> Result<()> {
+        let sections = [
+            ("DISPLAY", serde_json::to_value(&self.config.display)?),
+            ("SECURITY", serde_json::to_value(&self.config.security)?),
+            ("GAME", serde_json::to_value(&self.config.game)?),
+        ];
         
-        // Show firewall ASCII art
-        println!();
-        self.color_scheme.print_colored(crate::ui::ascii_art::FIREWALL)?;
         println!();
-        sleep(Duration::from_millis(1000)).await;
+        for (title, section) in sections {
+            self.color_scheme.print_bright(&format!("  [{}]\n", title))?;
+            let Some(fields) = section.as_object() else { continue };
+            for (key, value) in fields {
+                let shown = match value {
+                    // Never echo the admin hash, only whether one is set
+                    _ if key == "admin_password_hash" => if value.is_null() { "(not set)".to_string() } else { "(set)".to_string() },
+                    serde_json::Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                let marker = if SETTABLE_KEYS.contains(&key.as_str()) { "*" } else { " " };
+                self.color_scheme.print_colored(&format!("   {} {:<26}", marker, key))?;
+                self.color_scheme.print_secondary(&format!("{}\n", shown))?;
+            }
+            println!();
+        }
+        self.color_scheme.print_dim("  * change with 'config set <key> <value>'\n")?;
+        Ok(())
+    }
+
+    /// Change a whitelisted setting, save it and apply it to this session
+    fn set_config(&mut self, key: &str, value: &str) -> Result<CommandResult> {
+        if let Err(e) = self.config.set_value(key, value) {
+            self.color_scheme.print_error(&format!("  [!] {}\n", e))?;
+            return Ok(CommandResult::Continue);
+        }
         
-        self.color_scheme.print_colored(&format!("  [>] Analyzing firewall on {}...\n", target))?;
-        animations::show_processing("Detecting firewall rules", 1500).await?;
+        // Update the file on disk rather than our copy, which may be stale; scripts keep it to the session
+        if self.interactive {
+            let saved = Config::load().and_then(|mut config| {
+                config.set_value(key, value)?;
+                config.save()
+            });
+            if let Err(e) = saved {
+                self.color_scheme.print_warning(&format!("  [!] Could not save config: {}\n", e))?;
+            }
+        }
         
-        match action {
-            "bypass" => {
-                self.color_scheme.print_colored("  [>] Attempting to bypass firewall...\n")?;
-                animations::show_processing("Exploiting vulnerabilities", 2000).await?;
-                self.color_scheme.print_success("\n  [✓] Firewall bypassed successfully\n")?;
-                self.game_state.add_reputation(25);
-                self.game_state.increase_heat(30.0);
+        self.color_scheme = ColorScheme::from_display(&self.config.display);
+        self.color_scheme.print_success(&format!("  [+] {} set to {}\n", key, value))?;
+        
+        Ok(CommandResult::ConfigChanged(Box::new(self.config.clone())))
+    }
+
+    /// Re-read config.json and use it for the rest of the session
+    fn reload_config(&mut self) -> Result<CommandResult> {
+        let config = match Config::reload() {
+            Ok(config) => config,
+            Err(e) => {
+                self.color_scheme.print_error(&format!("  [!] Could not reload config: {}\n", e))?;
+                self.color_scheme.print_dim("      Keeping the current settings\n")?;
+                return Ok(CommandResult::Continue);
             }
-            "disable" => {
-                self.color_scheme.print_colored("  [>] Attempting to disable firewall...\n")?;
-                animations::show_processing("Sending kill packets", 2500).await?;
-                self.color_scheme.print_warning("\n  [!] Firewall temporarily disabled\n")?;
-                self.game_state.add_reputation(30);
-                self.game_state.increase_heat(40.0);
+        };
+        
+        self.config = config.clone();
+        self.color_scheme = ColorScheme::from_display(&self.config.display);
+        self.color_scheme.print_success("  [+] Config reloaded\n")?;
+        Ok(CommandResult::ConfigChanged(Box::new(config)))
+    }
+
+    /// Handle sudo command
+    async fn handle_sudo(&self, args: Vec<String>) -> Result<CommandResult> {
+        match args.first().map(|s| s.as_str()) {
+            Some("console") => Ok(CommandResult::AdminConsole),
+            _ => {
+                self.color_scheme.print_error("  [!] Usage: sudo console\n")?;
+                Ok(CommandResult::Continue)
             }
+        }
+    }
+
+    /// Handle rename command; the account itself is renamed by the auth system
+    fn handle_rename(&self, args: Vec<String>) -> Result<CommandResult> {
+        match args.as_slice() {
+            [new_username] => Ok(CommandResult::Rename(new_username.clone())),
             _ => {
-                self.color_scheme.print_colored("\n  Firewall Analysis:\n")?;
-                self.color_scheme.print_colored("    Type: Next-Gen Enterprise Firewall\n")?;
-                self.color_scheme.print_colored("    Rules: 247 active\n")?;
-                self.color_scheme.print_colored("    IDS/IPS: Enabled\n")?;
-                self.color_scheme.print_warning("    Vulnerabilities: 3 potential weaknesses detected\n")?;
-                self.game_state.increase_heat(5.0);
+                self.color_scheme.print_error("  [!] Usage: rename <new_username>\n")?;
+                Ok(CommandResult::Continue)
             }
         }
-        
-        Ok(CommandResult::Continue)
+    }
+
+    /// Handle admin command; access is checked against the account once it reaches the auth system
+    fn handle_admin(&self, args: Vec<String>) -> Result<CommandResult> {
+        if args.is_empty() {
+            self.color_scheme.print_error(&format!("  [!] Usage: {}\n", crate::auth::admin::COMMAND_USAGE))?;
+            return Ok(CommandResult::Continue);
+        }
+        Ok(CommandResult::Admin(args))
     }
 
     /// Handle clear command
@@ -454,65 +2042,65 @@ impl CommandHandler {
         // System Logo
         self.color_scheme.print_colored("\n[SYSTEM LOGO]\n")?;
         self.color_scheme.print_colored(crate::ui::ascii_art::SYSTEM_LOGO)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Network Map
         self.color_scheme.print_colored("\n[NETWORK TOPOLOGY]\n")?;
         self.color_scheme.print_colored(crate::ui::ascii_art::NETWORK_MAP)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Target
         self.color_scheme.print_colored("\n[TARGET ACQUIRED]\n")?;
         self.color_scheme.print_colored(crate::ui::ascii_art::TARGET)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Firewall
         self.color_scheme.print_colored("\n[FIREWALL DETECTED]\n")?;
         self.color_scheme.print_colored(crate::ui::ascii_art::FIREWALL)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Access Granted
         self.color_scheme.print_success(crate::ui::ascii_art::ACCESS_GRANTED)?;
-        sleep(Duration::from_millis(1000)).await;
+        animations::pause(Duration::from_millis(1000), &self.color_scheme).await;
         
         // Access Denied
         self.color_scheme.print_error(crate::ui::ascii_art::ACCESS_DENIED)?;
-        sleep(Duration::from_millis(1000)).await;
+        animations::pause(Duration::from_millis(1000), &self.color_scheme).await;
         
         // Warning Sign
         self.color_scheme.print_warning("\n[WARNING]\n")?;
         self.color_scheme.print_warning(crate::ui::ascii_art::WARNING_SIGN)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Biohazard
         self.color_scheme.print_error("\n[BIOHAZARD]\n")?;
         self.color_scheme.print_error(crate::ui::ascii_art::BIOHAZARD)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Virus
         self.color_scheme.print_colored("\n[VIRUS DETECTED]\n")?;
         self.color_scheme.print_colored(crate::ui::ascii_art::VIRUS)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Binary Cascade
         self.color_scheme.print_dim("\n[BINARY CASCADE]\n")?;
         self.color_scheme.print_dim(crate::ui::ascii_art::BINARY_CASCADE)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Circuit Pattern
         self.color_scheme.print_colored("\n[CIRCUIT PATTERN]\n")?;
         self.color_scheme.print_colored(crate::ui::ascii_art::CIRCUIT_PATTERN)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Data Stream
         self.color_scheme.print_colored("\n[DATA STREAM]\n")?;
         self.color_scheme.print_colored(crate::ui::ascii_art::DATA_STREAM)?;
-        sleep(Duration::from_millis(1500)).await;
+        animations::pause(Duration::from_millis(1500), &self.color_scheme).await;
         
         // Injection
         self.color_scheme.print_bright("\n[INJECTION VECTOR]\n")?;
         self.color_scheme.print_bright(crate::ui::ascii_art::INJECTION)?;
-        sleep(Duration::from_millis(1000)).await;
+        animations::pause(Duration::from_millis(1000), &self.color_scheme).await;
         
         // Boot Sequence
         self.color_scheme.print_colored("\n[BOOT SEQUENCE]\n")?;
@@ -525,6 +2113,19 @@ impl CommandHandler {
         Ok(CommandResult::Continue)
     }
 
+    /// Multipliers for the configured difficulty
+    fn difficulty(&self) -> DifficultyModifier {
+        DifficultyModifier::for_difficulty(self.config.game.difficulty)
+    }
+
+    /// Add heat from an action, scaled by difficulty, halved in stealth mode
+    /// and softened a little by a cover identity
+    fn raise_heat(&self, state: &mut GameState, amount: f32) {
+        let amount = if state.stealth { amount * STEALTH_FACTOR } else { amount };
+        let amount = if state.spoofed_name.is_some() { amount * SPOOF_HEAT_FACTOR } else { amount };
+        state.increase_heat(amount * self.difficulty().heat_gain);
+    }
+
     /// Create heat level bar
     fn create_heat_bar(&self, heat: f32) -> String {
         let bar_width = 20;
@@ -540,20 +2141,67 @@ impl CommandHandler {
         }
         bar.push_str(&format!("] {:.0}%", heat));
         
-        bar
+        crate::ui::ascii_art::render(&bar).into_owned()
+    }
+
+    /// Lock and get current game state
+    pub async fn game_state(&self) -> MutexGuard<'_, GameState> {
+        self.game_state.lock().await
+    }
+
+    /// Registry of known commands
+    pub fn registry(&self) -> &CommandRegistry {
+        &self.registry
+    }
+
+    /// Handle to the shared game state, for background tasks
+    pub fn shared_state(&self) -> SharedGameState {
+        Arc::clone(&self.game_state)
+    }
+
+    /// Cool off heat in place for the time since the last update;
+    /// `rate` is the share of heat kept per minute
+    pub async fn apply_heat_decay(&self, rate: f32) {
+        self.game_state.lock().await.decay_heat(rate);
     }
+}
 
-    /// Get current game state
-    pub fn game_state(&self) -> &GameState {
-        &self.game_state
+/// Scan results as pretty JSON; safe scans add the hosts flagged as likely honeypots
+fn scan_json(results: &scanner::ScanResult, honeypots: Option<&[String]>) -> Result<String> {
+    let mut json = serde_json::to_value(results)?;
+    if let Some(honeypots) = honeypots {
+        json["suspected_honeypots"] = serde_json::json!(honeypots);
     }
+    Ok(serde_json::to_string_pretty(&json)?)
+}
 
-    /// Update game state
-    pub fn update_game_state(&mut self, state: GameState) {
-        self.game_state = state;
+/// Buffer a map line colored by the node's status
+fn push_map_line(pager: &mut Pager, text: &str, node: Option<&NetworkNode>) {
+    let text = format!("  {}\n", text);
+    match node {
+        None => pager.bright(&text),
+        Some(n) if n.is_compromised => pager.success(&text),
+        Some(n) => match n.security_level {
+            SecurityLevel::Maximum => pager.error(&text),
+            SecurityLevel::High => pager.warning(&text),
+            _ => pager.colored(&text),
+        },
     }
 }
 
+/// Rebuild a saved game for a new session; the account stays the source of truth for reputation
+fn restore_state(user: &User, saved: SaveGame) -> GameState {
+    let mut state = saved.game_state;
+    state.reputation = user.reputation;
+    state.session_start = chrono::Utc::now();
+    state
+}
+
+/// Suggest the next useful action for a game state
+fn hint_for(state: &GameState) -> Option<String> {
+    crate::game::suggest_hint(state, &state.reputation_manager)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -574,4 +2222,175 @@ mod tests {
         assert!(bar.contains('█'));
         assert!(bar.contains('░'));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_scan_json_is_machine_readable() {
+        let results = scanner::ScanResult {
+            devices: vec![scanner::Device {
+                ip: "10.0.0.5".to_string(),
+                hostname: "SRV-001".to_string(),
+                mac: "00:11:22:33:44:55".to_string(),
+                os: "Ubuntu 22.04".to_string(),
+                open_ports: vec![22, 80],
+                vulnerabilities: vec!["CVE-2021-44228".to_string()],
+                services: vec![scanner::Service {
+                    port: 80,
+                    name: "http".to_string(),
+                    version: "nginx 1.18".to_string(),
+                    vulnerable: true,
+                }],
+            }],
+            scan_time: Duration::from_millis(1500),
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&scan_json(&results, None).unwrap()).unwrap();
+        assert_eq!(json["scan_time_ms"], 1500);
+        assert_eq!(json["devices"][0]["open_ports"], serde_json::json!([22, 80]));
+        assert_eq!(json["devices"][0]["services"][0]["vulnerable"], true);
+        assert!(json.get("suspected_honeypots").is_none());
+
+        let flagged = vec!["10.0.0.5".to_string()];
+        let json: serde_json::Value = serde_json::from_str(&scan_json(&results, Some(&flagged)).unwrap()).unwrap();
+        assert_eq!(json["suspected_honeypots"][0], "10.0.0.5");
+    }
+
+    #[tokio::test]
+    async fn test_config_rejects_unknown_keys() {
+        let user = User::new("test".to_string(), "pass").unwrap();
+        let mut handler = CommandHandler::new(&user);
+        
+        handler.execute("config show").await.unwrap();
+        handler.execute("config set bcrypt_cost 4").await.unwrap();
+        handler.execute("config set glitch_intensity loud").await.unwrap();
+        assert_eq!(handler.config.security.bcrypt_cost, 12);
+        assert_eq!(handler.config.display.glitch_intensity, 0.1);
+    }
+
+    #[tokio::test]
+    async fn test_history_masks_keys() {
+        let user = User::new("test".to_string(), "pass").unwrap();
+        let mut handler = CommandHandler::new(&user);
+        
+        handler.execute("status -k hunter2").await.unwrap();
+        assert_eq!(handler.history_mut().older(), Some("status -k ***"));
+    }
+
+    #[tokio::test]
+    async fn test_heat_decays_in_place() {
+        let user = User::new("test".to_string(), "pass").unwrap();
+        let handler = CommandHandler::new(&user);
+        {
+            let mut state = handler.game_state().await;
+            state.heat_level = 80.0;
+            state.last_heat_update = chrono::Utc::now() - chrono::Duration::minutes(1);
+        }
+        
+        handler.apply_heat_decay(0.5).await;
+        let heat = handler.game_state().await.heat_level;
+        assert!(heat < 80.0 && heat > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_mission_accept_tracks_objectives() {
+        let user = User::new("test".to_string(), "pass").unwrap();
+        let mut handler = CommandHandler::new(&user);
+        
+        handler.execute("mission accept recon-001").await.unwrap();
+        handler.execute("mission accept BOGUS-1").await.unwrap();
+        
+        let state = handler.game_state().await;
+        assert_eq!(state.active_missions.len(), 1);
+        assert_eq!(state.active_missions[0].id, "RECON-001");
+    }
+
+    #[tokio::test]
+    async fn test_focused_scans_need_a_target_and_cost_more() {
+        let user = User::new("test".to_string(), "pass").unwrap();
+        let mut handler = CommandHandler::new(&user);
+        
+        handler.execute("scan --deep").await.unwrap();
+        handler.execute("scan 10.0.0.9 --deep --vuln").await.unwrap();
+        assert_eq!(handler.game_state().await.total_scans, 0);
+        
+        handler.execute("scan 10.0.0.9 --deep --json").await.unwrap();
+        let (heat, found) = {
+            let state = handler.game_state().await;
+            let node = state.network_map.get_node("10.0.0.9").unwrap();
+            (state.heat_level, node.vulnerabilities.len())
+        };
+        assert!(found >= 2);
+        
+        handler.execute("scan 10.0.0.9 --vuln --json").await.unwrap();
+        let state = handler.game_state().await;
+        assert_eq!(state.total_scans, 2);
+        assert!(state.network_map.get_node("10.0.0.9").unwrap().vulnerabilities.len() >= found);
+        assert!(state.heat_level > heat && heat > 10.0);
+    }
+
+    #[tokio::test]
+    async fn test_stealth_needs_skilled_and_halves_heat() {
+        let user = User::new("test".to_string(), "pass").unwrap();
+        let mut handler = CommandHandler::new(&user);
+        
+        handler.execute("stealth on").await.unwrap();
+        assert!(!handler.game_state().await.stealth);
+        
+        handler.game_state().await.reputation = ReputationLevel::Skilled.reputation_requirement();
+        handler.execute("stealth on").await.unwrap();
+        let mut state = handler.game_state().await;
+        assert!(state.stealth);
+        handler.raise_heat(&mut state, 10.0);
+        assert_eq!(state.heat_level, 10.0 * STEALTH_FACTOR * handler.difficulty().heat_gain);
+        drop(state);
+        
+        handler.execute("quiet off").await.unwrap();
+        assert!(!handler.game_state().await.stealth);
+    }
+
+    #[tokio::test]
+    async fn test_cover_identity_masks_command_heat() {
+        let user = User::new("test".to_string(), "pass").unwrap();
+        let mut handler = CommandHandler::new(&user);
+        handler.set_interactive(false);
+        handler.game_state().await.credits = SPOOF_COST * 3;
+
+        // Too short once counted in characters, however many bytes it takes
+        handler.execute("spoof éé").await.unwrap();
+        assert_eq!(handler.game_state().await.spoofed_name, None);
+        handler.execute("alias ghost").await.unwrap();
+        assert_eq!(handler.game_state().await.spoofed_name, None);
+
+        handler.execute("mask ghöst").await.unwrap();
+        let mut state = handler.game_state().await;
+        assert_eq!(state.spoofed_name.as_deref(), Some("ghöst"));
+        handler.raise_heat(&mut state, 10.0);
+        assert_eq!(state.heat_level, 10.0 * SPOOF_HEAT_FACTOR * handler.difficulty().heat_gain);
+    }
+
+    #[tokio::test]
+    async fn test_pivot_requires_compromised_host() {
+        let user = User::new("test".to_string(), "pass").unwrap();
+        let mut handler = CommandHandler::new(&user);
+        handler.game_state().await.network_map.flag_honeypot("10.0.0.5", "SRV-001");
+        
+        handler.execute("pivot 10.0.0.5").await.unwrap();
+        
+        let state = handler.game_state().await;
+        assert!(state.network_map.connections.is_empty());
+        assert_eq!(state.heat_level, 0.0);
+    }
+
+    #[test]
+    fn test_restored_state_keeps_progress() {
+        let mut user = User::new("test".to_string(), "pass").unwrap();
+        let mut saved = GameState::new("test".to_string(), 10);
+        saved.credits = 4200;
+        saved.successful_hacks = 7;
+
+        user.reputation = 350;
+        let state = restore_state(&user, SaveGame::new(saved));
+        assert_eq!(state.credits, 4200);
+        assert_eq!(state.successful_hacks, 7);
+        assert_eq!(state.reputation, 350);
+    }
+}
@@ -0,0 +1,106 @@
+//! Fabricated filesystems on compromised hosts for CRIMSON-REDLINE
+//!
+//! Listings are seeded from the host's IP, so a host shows the same files
+//! every time it's browsed.
+
+use crate::game::state::{NetworkNode, NodeType};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// A file on a remote host
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteFile {
+    pub path: String,
+    pub size: usize,
+    pub permissions: &'static str,
+}
+
+/// Files any host might have
+const COMMON_FILES: &[&str] = &[
+    "/etc/resolv.conf",
+    "/etc/ssh/sshd.conf",
+    "/var/log/auth.log",
+    "/var/log/syslog.log",
+    "/home/admin/notes.txt",
+    "/home/admin/todo.txt",
+    "/tmp/update.bin",
+];
+
+/// Files that only turn up on certain kinds of host
+fn role_files(node_type: &NodeType) -> &'static [&'static str] {
+    match node_type {
+        NodeType::Database => &["/var/lib/db/customers.db", "/var/backups/dump.sql", "/etc/db/server.ini"],
+        NodeType::WebServer => &["/var/www/config.ini", "/var/log/nginx/access.log", "/var/www/uploads/invoice.pdf"],
+        NodeType::MailServer => &["/var/mail/ceo.txt", "/var/log/mail.log", "/etc/postfix/main.cfg"],
+        NodeType::DomainController => &["/ad/ntds.db", "/ad/gpo/password_policy.ini", "/ad/users_export.sql"],
+        NodeType::Workstation => &["/Users/jsmith/budget_2024.doc", "/Users/jsmith/passwords.txt", "/Users/jsmith/chat.log"],
+        NodeType::Router | NodeType::Firewall => &["/cfg/running.conf", "/cfg/firmware.bin", "/logs/traffic.log"],
+        NodeType::Server | NodeType::Unknown => &["/opt/app/settings.cfg", "/opt/app/app.log", "/srv/share/report.pdf"],
+    }
+}
+
+/// List the files on a host
+pub fn list_files(node: &NetworkNode) -> Vec<RemoteFile> {
+    let mut rng = StdRng::seed_from_u64(crate::utils::stable_hash(&node.ip));
+    let mut paths: Vec<&str> = role_files(&node.node_type).to_vec();
+    let extra = rng.gen_range(3..=5);
+    paths.extend(COMMON_FILES.choose_multiple(&mut rng, extra));
+    paths.sort_unstable();
+
+    paths
+        .into_iter()
+        .map(|path| RemoteFile {
+            path: path.to_string(),
+            size: rng.gen_range(256..4_194_304),
+            permissions: if path.starts_with("/etc") || path.starts_with("/ad") {
+                "-rw-------"
+            } else {
+                "-rw-r--r--"
+            },
+        })
+        .collect()
+}
+
+/// Find a file by full path or bare file name
+pub fn find_file(node: &NetworkNode, name: &str) -> Option<RemoteFile> {
+    list_files(node)
+        .into_iter()
+        .find(|file| file.path == name || file.path.rsplit('/').next() == Some(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::SecurityLevel;
+
+    fn node(ip: &str, node_type: NodeType) -> NetworkNode {
+        NetworkNode {
+            ip: ip.to_string(),
+            hostname: "HOST".to_string(),
+            node_type,
+            is_compromised: true,
+            security_level: SecurityLevel::Low,
+            discovered_at: chrono::Utc::now(),
+            suspected_honeypot: false,
+            vulnerabilities: Vec::new(),
+            has_rootkit: false,
+            open_ports: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_listing_is_stable_per_host() {
+        let db = node("10.0.0.7", NodeType::Database);
+        assert_eq!(list_files(&db), list_files(&db));
+        assert!(list_files(&db).iter().any(|f| f.path == "/var/backups/dump.sql"));
+    }
+
+    #[test]
+    fn test_find_by_name_or_path() {
+        let db = node("10.0.0.7", NodeType::Database);
+        assert_eq!(find_file(&db, "dump.sql").unwrap().path, "/var/backups/dump.sql");
+        assert!(find_file(&db, "/var/backups/dump.sql").is_some());
+        assert!(find_file(&db, "missing.txt").is_none());
+    }
+}
@@ -3,11 +3,19 @@
 pub mod scanner;
 pub mod exploit;
 pub mod decrypt;
+pub mod darkweb;
+pub mod tools;
+pub mod payload;
+pub mod netmap;
+pub mod hostfs;
+pub mod completion;
+pub mod script;
 pub mod handler;
 
 pub use handler::{CommandHandler, CommandResult};
 
 use crate::ui::ColorScheme;
+use crate::ui::pager::Pager;
 use anyhow::Result;
 use std::collections::HashMap;
 
@@ -25,6 +33,12 @@ pub struct CommandInfo {
     pub aliases: Vec<String>,
 }
 
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CommandRegistry {
     /// Create and populate the command registry
     pub fn new() -> Self {
@@ -41,8 +55,8 @@ impl CommandRegistry {
         // Scan command
         commands.insert("scan".to_string(), CommandInfo {
             name: "scan".to_string(),
-            description: "Scan network for targets and vulnerabilities".to_string(),
-            usage: "scan [target_ip] [-p ports] [-v verbose]".to_string(),
+            description: "Scan network for targets and vulnerabilities; --deep and --vuln dig further at more heat".to_string(),
+            usage: "scan [target_ip] [-p ports] [-v verbose] [--safe] [--count N] [--deep | --vuln] [--json]".to_string(),
             aliases: vec!["nmap".to_string(), "recon".to_string()],
         });
         
@@ -50,7 +64,7 @@ impl CommandRegistry {
         commands.insert("exploit".to_string(), CommandInfo {
             name: "exploit".to_string(),
             description: "Deploy exploit against identified vulnerability".to_string(),
-            usage: "exploit <target> <vulnerability_id>".to_string(),
+            usage: "exploit <target> <vulnerability_id> [--force]".to_string(),
             aliases: vec!["pwn".to_string(), "attack".to_string()],
         });
         
@@ -58,22 +72,70 @@ impl CommandRegistry {
         commands.insert("decrypt".to_string(), CommandInfo {
             name: "decrypt".to_string(),
             description: "Decrypt intercepted data or files".to_string(),
-            usage: "decrypt <encrypted_data> [-k key] [-m method]".to_string(),
+            usage: "decrypt <encrypted_data> [-k key] [-m method] | decrypt --file <name>".to_string(),
             aliases: vec!["decode".to_string(), "decipher".to_string()],
         });
         
+        // Ping command
+        commands.insert("ping".to_string(), CommandInfo {
+            name: "ping".to_string(),
+            description: "Check whether a host is up".to_string(),
+            usage: "ping <ip>".to_string(),
+            aliases: vec![],
+        });
+        
+        // Connect command
+        commands.insert("connect".to_string(), CommandInfo {
+            name: "connect".to_string(),
+            description: "Open a session on a discovered host".to_string(),
+            usage: "connect <ip>".to_string(),
+            aliases: vec!["ssh".to_string()],
+        });
+        
+        // Disconnect command
+        commands.insert("disconnect".to_string(), CommandInfo {
+            name: "disconnect".to_string(),
+            description: "Close the session on the connected host".to_string(),
+            usage: "disconnect".to_string(),
+            aliases: vec![],
+        });
+        
+        // Ls command
+        commands.insert("ls".to_string(), CommandInfo {
+            name: "ls".to_string(),
+            description: "List files on the connected host".to_string(),
+            usage: "ls".to_string(),
+            aliases: vec!["dir".to_string()],
+        });
+        
+        // Cat command
+        commands.insert("cat".to_string(), CommandInfo {
+            name: "cat".to_string(),
+            description: "Print a file on the connected host".to_string(),
+            usage: "cat <file>".to_string(),
+            aliases: vec!["type".to_string()],
+        });
+        
+        // Crack command
+        commands.insert("crack".to_string(), CommandInfo {
+            name: "crack".to_string(),
+            description: "Recover the password behind a hash".to_string(),
+            usage: "crack [hash]".to_string(),
+            aliases: vec!["hashcat".to_string(), "john".to_string()],
+        });
+        
         // Inject command
         commands.insert("inject".to_string(), CommandInfo {
             name: "inject".to_string(),
             description: "Inject payload into target system".to_string(),
-            usage: "inject <target> <payload_type>".to_string(),
+            usage: "inject <target> [trojan|keylogger|ransomware|rootkit]".to_string(),
             aliases: vec!["payload".to_string(), "implant".to_string()],
         });
         
         // Trace command
         commands.insert("trace".to_string(), CommandInfo {
             name: "trace".to_string(),
-            description: "Trace network route to target".to_string(),
+            description: "Trace network route to target; well-defended hosts may notice".to_string(),
             usage: "trace <target_ip>".to_string(),
             aliases: vec!["traceroute".to_string(), "track".to_string()],
         });
@@ -86,6 +148,22 @@ impl CommandRegistry {
             aliases: vec!["stats".to_string(), "info".to_string()],
         });
         
+        // Events command
+        commands.insert("events".to_string(), CommandInfo {
+            name: "events".to_string(),
+            description: "Review recent random events and your choices".to_string(),
+            usage: "events [count]".to_string(),
+            aliases: vec!["eventlog".to_string()],
+        });
+        
+        // Records command
+        commands.insert("records".to_string(), CommandInfo {
+            name: "records".to_string(),
+            description: "Show lifetime personal records".to_string(),
+            usage: "records".to_string(),
+            aliases: vec!["bests".to_string()],
+        });
+        
         // Mission command
         commands.insert("mission".to_string(), CommandInfo {
             name: "mission".to_string(),
@@ -98,10 +176,18 @@ impl CommandRegistry {
         commands.insert("darkweb".to_string(), CommandInfo {
             name: "darkweb".to_string(),
             description: "Access underground marketplace".to_string(),
-            usage: "darkweb [browse|buy|sell] [item_id]".to_string(),
+            usage: "darkweb [browse|buy] [item_id]".to_string(),
             aliases: vec!["market".to_string(), "underground".to_string()],
         });
         
+        // Tools command
+        commands.insert("tools".to_string(), CommandInfo {
+            name: "tools".to_string(),
+            description: "List unlocked tools and discovered exploits".to_string(),
+            usage: "tools".to_string(),
+            aliases: vec!["inventory".to_string(), "inv".to_string()],
+        });
+        
         // Firewall command
         commands.insert("firewall".to_string(), CommandInfo {
             name: "firewall".to_string(),
@@ -110,6 +196,150 @@ impl CommandRegistry {
             aliases: vec!["fw".to_string(), "barrier".to_string()],
         });
         
+        // Pivot command
+        commands.insert("pivot".to_string(), CommandInfo {
+            name: "pivot".to_string(),
+            description: "Scan hosts adjacent to a compromised node".to_string(),
+            usage: "pivot <compromised_ip>".to_string(),
+            aliases: vec!["lateral".to_string(), "hop".to_string()],
+        });
+        
+        // Map command
+        commands.insert("map".to_string(), CommandInfo {
+            name: "map".to_string(),
+            description: "Show the discovered network, or one host's neighbors".to_string(),
+            usage: "map [ip]".to_string(),
+            aliases: vec!["topology".to_string(), "netmap".to_string()],
+        });
+        
+        // Card command
+        commands.insert("card".to_string(), CommandInfo {
+            name: "card".to_string(),
+            description: "Render a shareable agent card with your stats".to_string(),
+            usage: "card [--plain]".to_string(),
+            aliases: vec!["flex".to_string(), "share".to_string()],
+        });
+        
+        // History command
+        commands.insert("history".to_string(), CommandInfo {
+            name: "history".to_string(),
+            description: "Show previously entered commands".to_string(),
+            usage: "history [count]".to_string(),
+            aliases: vec!["hist".to_string()],
+        });
+        
+        // Passwd command
+        commands.insert("passwd".to_string(), CommandInfo {
+            name: "passwd".to_string(),
+            description: "Change your password".to_string(),
+            usage: "passwd".to_string(),
+            aliases: vec!["password".to_string()],
+        });
+        
+        // Theme command
+        commands.insert("theme".to_string(), CommandInfo {
+            name: "theme".to_string(),
+            description: "Switch the terminal color scheme".to_string(),
+            usage: "theme <name|list>".to_string(),
+            aliases: vec!["colors".to_string(), "skin".to_string()],
+        });
+        
+        // Config command
+        commands.insert("config".to_string(), CommandInfo {
+            name: "config".to_string(),
+            description: "View settings or change them in-session".to_string(),
+            usage: "config [show | reload] | config set <key> <value>".to_string(),
+            aliases: vec!["settings".to_string()],
+        });
+        
+        // Profile command
+        commands.insert("profile".to_string(), CommandInfo {
+            name: "profile".to_string(),
+            description: "Show your full agent dossier".to_string(),
+            usage: "profile".to_string(),
+            aliases: vec!["whoami".to_string(), "dossier".to_string()],
+        });
+        
+        // Difficulty command
+        commands.insert("difficulty".to_string(), CommandInfo {
+            name: "difficulty".to_string(),
+            description: "Show the difficulty setting and what it changes".to_string(),
+            usage: "difficulty".to_string(),
+            aliases: vec!["diff".to_string()],
+        });
+        
+        // Sudo command
+        commands.insert("sudo".to_string(), CommandInfo {
+            name: "sudo".to_string(),
+            description: "Open the admin console (admins only)".to_string(),
+            usage: "sudo console".to_string(),
+            aliases: vec![],
+        });
+        
+        // Rename command
+        commands.insert("rename".to_string(), CommandInfo {
+            name: "rename".to_string(),
+            description: "Change your agent handle".to_string(),
+            usage: "rename <new_username>".to_string(),
+            aliases: vec![],
+        });
+        
+        // Delete account command
+        commands.insert("delete-account".to_string(), CommandInfo {
+            name: "delete-account".to_string(),
+            description: "Delete your account, saves and history for good".to_string(),
+            usage: "delete-account".to_string(),
+            aliases: vec![],
+        });
+        
+        // Two-factor command
+        commands.insert("2fa".to_string(), CommandInfo {
+            name: "2fa".to_string(),
+            description: "Turn two-factor login on or off".to_string(),
+            usage: crate::auth::totp::COMMAND_USAGE.to_string(),
+            aliases: vec!["mfa".to_string()],
+        });
+        
+        // Admin command
+        commands.insert("admin".to_string(), CommandInfo {
+            name: "admin".to_string(),
+            description: "Manage the install's agent accounts (admins only)".to_string(),
+            usage: crate::auth::admin::COMMAND_USAGE.to_string(),
+            aliases: vec![],
+        });
+        
+        // Hint command
+        commands.insert("hint".to_string(), CommandInfo {
+            name: "hint".to_string(),
+            description: "Suggest a useful next action".to_string(),
+            usage: "hint".to_string(),
+            aliases: vec!["tip".to_string(), "advice".to_string()],
+        });
+        
+        // Spoof command
+        commands.insert("spoof".to_string(), CommandInfo {
+            name: "spoof".to_string(),
+            description: "Assume a cover identity for this session".to_string(),
+            usage: "spoof <name> | spoof --reset".to_string(),
+            aliases: vec!["mask".to_string()],
+        });
+        
+        // Stealth command
+        commands.insert("stealth".to_string(), CommandInfo {
+            name: "stealth".to_string(),
+            description: "Work slower and quieter: half the heat, half the reputation".to_string(),
+            usage: "stealth <on|off>".to_string(),
+            aliases: vec!["quiet".to_string()],
+        });
+        
+        // Achievements command
+        commands.insert("achievements".to_string(), CommandInfo {
+            name: "achievements".to_string(),
+            description: "View unlocked achievements".to_string(),
+            usage: "achievements [--recent]".to_string(),
+            aliases: vec!["trophies".to_string(), "ach".to_string()],
+        });
+        
         // Clear command
         commands.insert("clear".to_string(), CommandInfo {
             name: "clear".to_string(),
@@ -123,7 +353,7 @@ impl CommandRegistry {
             name: "logout".to_string(),
             description: "Disconnect from the system".to_string(),
             usage: "logout".to_string(),
-            aliases: vec!["exit".to_string(), "quit".to_string(), "disconnect".to_string()],
+            aliases: vec!["exit".to_string(), "quit".to_string()],
         });
         
         CommandRegistry { commands }
@@ -137,13 +367,21 @@ impl CommandRegistry {
         }
         
         // Check aliases
-        for cmd in self.commands.values() {
-            if cmd.aliases.contains(&name.to_string()) {
-                return Some(cmd);
-            }
-        }
+        self.commands.values().find(|cmd| cmd.aliases.iter().any(|a| a == name))
+    }
+    
+    /// Suggest the closest command name or alias for a mistyped command
+    pub fn closest_match(&self, input: &str) -> Option<String> {
+        let input = input.to_lowercase();
+        let threshold = if input.chars().count() <= 4 { 1 } else { 2 };
         
-        None
+        self.commands
+            .values()
+            .flat_map(|cmd| std::iter::once(&cmd.name).chain(cmd.aliases.iter()))
+            .map(|candidate| (levenshtein(&input, candidate), candidate))
+            .filter(|(distance, _)| *distance > 0 && *distance <= threshold)
+            .min_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)))
+            .map(|(_, candidate)| candidate.clone())
     }
     
     /// Get all commands
@@ -154,37 +392,38 @@ impl CommandRegistry {
     }
     
     /// Display help for all commands
-    pub async fn display_help(&self, color_scheme: &ColorScheme) -> Result<()> {
-        println!();
-        color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        color_scheme.print_bright("                    AVAILABLE COMMANDS                         \n")?;
-        color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        println!();
+    pub async fn display_help(&self, color_scheme: &ColorScheme, page_height: u16) -> Result<()> {
+        let mut pager = Pager::new(page_height);
+        pager.blank();
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.bright("                    AVAILABLE COMMANDS                         \n");
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.blank();
         
         for cmd in self.all_commands() {
             // Command name
-            color_scheme.print_bright(&format!("  {:<12}", cmd.name))?;
+            pager.bright(&format!("  {:<12}", cmd.name));
             
             // Description
-            color_scheme.print_colored(&format!(" - {}\n", cmd.description))?;
+            pager.colored(&format!(" - {}\n", cmd.description));
             
             // Usage
-            color_scheme.print_dim(&format!("               Usage: {}\n", cmd.usage))?;
+            pager.dim(&format!("               Usage: {}\n", cmd.usage));
             
             // Aliases if any
             if !cmd.aliases.is_empty() {
-                color_scheme.print_dim(&format!("               Aliases: {}\n", cmd.aliases.join(", ")))?;
+                pager.dim(&format!("               Aliases: {}\n", cmd.aliases.join(", ")));
             }
             
-            println!();
+            pager.blank();
         }
         
-        color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        color_scheme.print_dim("  Type 'help <command>' for detailed information about a command\n")?;
-        color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-        println!();
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.dim("  Type 'help <command>' for detailed information about a command\n");
+        pager.colored("═══════════════════════════════════════════════════════════════\n");
+        pager.blank();
         
-        Ok(())
+        pager.show(color_scheme)
     }
     
     /// Display help for a specific command
@@ -222,6 +461,23 @@ impl CommandRegistry {
     }
 }
 
+/// Edit distance between two strings
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current.push((previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    
+    previous[b_chars.len()]
+}
+
 /// Parse command arguments
 pub fn parse_args(input: &str) -> (String, Vec<String>) {
     let parts: Vec<String> = input.split_whitespace().map(String::from).collect();
@@ -233,6 +489,32 @@ pub fn parse_args(input: &str) -> (String, Vec<String>) {
     }
 }
 
+/// Get the value following a flag, e.g. `-p 80` -> `Some("80")`
+pub fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+/// Get positional arguments, skipping flags and the values of `value_flags`
+pub fn positional_args<'a>(args: &'a [String], value_flags: &[&str]) -> Vec<&'a str> {
+    let mut positional = Vec::new();
+    let mut skip_next = false;
+    
+    for arg in args {
+        if skip_next {
+            skip_next = false;
+        } else if arg.starts_with('-') {
+            skip_next = value_flags.contains(&arg.as_str());
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+    
+    positional
+}
+
 /// Generate random IP address
 pub fn generate_random_ip() -> String {
     use rand::Rng;
@@ -297,6 +579,16 @@ mod tests {
         assert!(registry.get_command("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_closest_match() {
+        let registry = CommandRegistry::new();
+        assert_eq!(registry.closest_match("scn"), Some("scan".to_string()));
+        assert_eq!(registry.closest_match("explot"), Some("exploit".to_string()));
+        assert_eq!(registry.closest_match("DECRPYT"), Some("decrypt".to_string()));
+        assert_eq!(registry.closest_match("qwxyzzy"), None);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
     #[test]
     fn test_parse_args() {
         let (cmd, args) = parse_args("scan 192.168.1.1 -p 80");
@@ -308,6 +600,14 @@ mod tests {
         assert!(args.is_empty());
     }
 
+    #[test]
+    fn test_flag_parsing() {
+        let (_, args) = parse_args("scan 10.0.0.1 --count 3 --safe");
+        assert_eq!(flag_value(&args, "--count"), Some("3"));
+        assert_eq!(flag_value(&args, "-p"), None);
+        assert_eq!(positional_args(&args, &["--count"]), vec!["10.0.0.1"]);
+    }
+
     #[test]
     fn test_random_generators() {
         let ip = generate_random_ip();
@@ -0,0 +1,175 @@
+//! Network map rendering for CRIMSON-REDLINE
+
+use crate::game::state::{NetworkMap, NetworkNode};
+use std::collections::HashSet;
+
+/// One rendered line of the map, with the node it describes (if any) for coloring
+#[derive(Debug)]
+pub struct MapLine<'a> {
+    pub text: String,
+    pub node: Option<&'a NetworkNode>,
+}
+
+/// Describe a node: hostname, IP, role, security and status
+pub fn describe_node(node: &NetworkNode) -> String {
+    let mut text = format!(
+        "[{}] {}  {:?}  sec:{:?}",
+        node.hostname, node.ip, node.node_type, node.security_level
+    );
+    if node.is_compromised {
+        text.push_str("  [OWNED]");
+    }
+    if node.has_rootkit {
+        text.push_str("  [ROOTKIT]");
+    }
+    if node.suspected_honeypot {
+        text.push_str("  [HONEYPOT?]");
+    }
+    text
+}
+
+/// Render the whole map as an ASCII tree, one tree per scan origin.
+/// Every host is drawn exactly once, including rings of hosts nothing else points to
+pub fn render_tree(map: &NetworkMap) -> Vec<MapLine<'_>> {
+    let mut lines = Vec::new();
+    let mut visited: HashSet<&str> = HashSet::new();
+
+    // Roots are scan origins nothing else points to, then hosts with no links at all
+    let mut roots: Vec<&str> = Vec::new();
+    for conn in &map.connections {
+        let is_child = map.connections.iter().any(|c| c.to == conn.from);
+        if !is_child && !roots.contains(&conn.from.as_str()) {
+            roots.push(&conn.from);
+        }
+    }
+    for node in &map.discovered_nodes {
+        let linked = map.connections.iter().any(|c| c.from == node.ip || c.to == node.ip);
+        if !linked {
+            roots.push(&node.ip);
+        }
+    }
+
+    // A cycle has no root, so start another tree from any host still undrawn
+    let unrooted = map.discovered_nodes.iter().map(|node| node.ip.as_str());
+    for root in roots.into_iter().chain(unrooted) {
+        if !visited.insert(root) {
+            continue;
+        }
+        let node = map.get_node(root);
+        let text = node.map_or_else(|| format!("[{}]", root), describe_node);
+        lines.push(MapLine { text, node });
+        render_children(map, root, "", &mut visited, &mut lines);
+    }
+
+    lines
+}
+
+/// Render the hosts reached from `ip`, skipping any already drawn. Every child is
+/// claimed before any is expanded, so a host two siblings reach sits under their parent
+fn render_children<'a>(
+    map: &'a NetworkMap,
+    ip: &str,
+    prefix: &str,
+    visited: &mut HashSet<&'a str>,
+    lines: &mut Vec<MapLine<'a>>,
+) {
+    let children: Vec<&NetworkNode> = map
+        .connections
+        .iter()
+        .filter(|c| c.from == ip)
+        .filter_map(|c| map.get_node(&c.to))
+        .filter(|n| visited.insert(n.ip.as_str()))
+        .collect();
+
+    for (i, child) in children.iter().enumerate() {
+        let last = i == children.len() - 1;
+        let branch = if last { "└── " } else { "├── " };
+        lines.push(MapLine {
+            text: format!("{}{}{}", prefix, branch, describe_node(child)),
+            node: Some(child),
+        });
+
+        let next_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        render_children(map, &child.ip, &next_prefix, visited, lines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::{NodeType, SecurityLevel};
+
+    fn node(ip: &str, hostname: &str) -> NetworkNode {
+        NetworkNode {
+            ip: ip.to_string(),
+            hostname: hostname.to_string(),
+            node_type: NodeType::Server,
+            is_compromised: false,
+            security_level: SecurityLevel::Medium,
+            discovered_at: chrono::Utc::now(),
+            suspected_honeypot: false,
+            vulnerabilities: Vec::new(),
+            has_rootkit: false,
+            open_ports: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tree_follows_connections() {
+        let mut map = NetworkMap::new();
+        map.add_node(node("10.0.0.1", "GW"));
+        map.add_node(node("10.0.0.2", "WEB"));
+        map.add_node(node("10.0.0.3", "DB"));
+        map.add_node(node("10.0.0.9", "LONER"));
+        map.add_connection("localhost".to_string(), "10.0.0.1".to_string());
+        map.add_connection("10.0.0.1".to_string(), "10.0.0.2".to_string());
+        map.add_connection("10.0.0.1".to_string(), "10.0.0.3".to_string());
+        map.mark_compromised("10.0.0.2");
+
+        let lines: Vec<String> = render_tree(&map).into_iter().map(|l| l.text).collect();
+        assert_eq!(lines[0], "[localhost]");
+        assert!(lines[1].starts_with("└── [GW] 10.0.0.1"));
+        assert!(lines[2].starts_with("    ├── [WEB] 10.0.0.2"));
+        assert!(lines[2].ends_with("[OWNED]"));
+        assert!(lines[3].starts_with("    └── [DB] 10.0.0.3"));
+        assert!(lines[4].starts_with("[LONER] 10.0.0.9"));
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[test]
+    fn test_cycles_are_drawn_once() {
+        let mut map = NetworkMap::new();
+        map.add_node(node("10.0.0.1", "A"));
+        map.add_node(node("10.0.0.2", "B"));
+        map.add_connection("localhost".to_string(), "10.0.0.1".to_string());
+        map.add_connection("10.0.0.1".to_string(), "10.0.0.2".to_string());
+        map.add_connection("10.0.0.2".to_string(), "10.0.0.1".to_string());
+
+        assert_eq!(render_tree(&map).len(), 3);
+    }
+
+    #[test]
+    fn test_shared_hosts_and_rootless_rings_are_drawn_once() {
+        let mut map = NetworkMap::new();
+        map.add_node(node("10.0.0.1", "A"));
+        map.add_node(node("10.0.0.2", "B"));
+        map.add_node(node("10.0.0.3", "SHARED"));
+        map.add_connection("localhost".to_string(), "10.0.0.1".to_string());
+        map.add_connection("localhost".to_string(), "10.0.0.3".to_string());
+        map.add_connection("10.0.0.1".to_string(), "10.0.0.3".to_string());
+        map.add_connection("10.0.0.1".to_string(), "10.0.0.2".to_string());
+        map.add_connection("10.0.0.2".to_string(), "10.0.0.3".to_string());
+
+        // A ring reached from nowhere: every host in it is someone's child
+        map.add_node(node("10.0.1.1", "RING-A"));
+        map.add_node(node("10.0.1.2", "RING-B"));
+        map.add_connection("10.0.1.1".to_string(), "10.0.1.2".to_string());
+        map.add_connection("10.0.1.2".to_string(), "10.0.1.1".to_string());
+
+        let lines: Vec<String> = render_tree(&map).into_iter().map(|l| l.text).collect();
+        assert_eq!(lines.iter().filter(|l| l.contains("[SHARED]")).count(), 1);
+        assert!(lines.iter().any(|l| l.starts_with("[RING-A] 10.0.1.1")));
+        assert!(lines.iter().any(|l| l.contains("└── [RING-B] 10.0.1.2")));
+        assert_eq!(lines.len(), 6);
+    }
+}
@@ -0,0 +1,86 @@
+//! Injectable payloads for CRIMSON-REDLINE
+
+/// A payload the `inject` command can deliver
+#[derive(Debug)]
+pub struct Payload {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Chance the injection lands, before difficulty
+    pub success_chance: f32,
+    /// Heat from a successful injection
+    pub heat: f32,
+    /// Heat from a failed attempt
+    pub failure_heat: f32,
+    /// Scales the configured inject reputation award
+    pub reward_factor: f32,
+    /// Progress steps shown while injecting
+    pub stages: [&'static str; 3],
+}
+
+/// Every payload, with trojan as the default
+pub const PAYLOADS: &[Payload] = &[
+    Payload {
+        name: "trojan",
+        description: "Backdoor disguised as legitimate software",
+        success_chance: 0.5,
+        heat: 20.0,
+        failure_heat: 10.0,
+        reward_factor: 1.0,
+        stages: ["Wrapping payload in a signed installer", "Planting dropper", "Waiting for a user to run it"],
+    },
+    Payload {
+        name: "keylogger",
+        description: "Quietly records every keystroke",
+        success_chance: 0.65,
+        heat: 12.0,
+        failure_heat: 8.0,
+        reward_factor: 0.8,
+        stages: ["Building input hook", "Attaching to the keyboard driver", "Buffering keystrokes"],
+    },
+    Payload {
+        name: "ransomware",
+        description: "Encrypts everything and demands payment - very loud",
+        success_chance: 0.4,
+        heat: 45.0,
+        failure_heat: 25.0,
+        reward_factor: 2.0,
+        stages: ["Generating encryption keys", "Enumerating file shares", "Encrypting volumes"],
+    },
+    Payload {
+        name: "rootkit",
+        description: "Hides in the kernel for persistent access",
+        success_chance: 0.3,
+        heat: 15.0,
+        failure_heat: 20.0,
+        reward_factor: 1.5,
+        stages: ["Patching kernel modules", "Hiding processes and files", "Installing boot persistence"],
+    },
+];
+
+/// Look up a payload by name, ignoring case
+pub fn find_payload(name: &str) -> Option<&'static Payload> {
+    PAYLOADS.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+/// Names of every payload, for usage messages
+pub fn payload_names() -> Vec<&'static str> {
+    PAYLOADS.iter().map(|p| p.name).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_payload() {
+        assert_eq!(find_payload("RootKit").unwrap().name, "rootkit");
+        assert!(find_payload("worm").is_none());
+        assert_eq!(payload_names(), ["trojan", "keylogger", "ransomware", "rootkit"]);
+    }
+
+    #[test]
+    fn test_ransomware_is_loudest() {
+        let ransomware = find_payload("ransomware").unwrap();
+        assert!(PAYLOADS.iter().all(|p| p.heat <= ransomware.heat));
+    }
+}
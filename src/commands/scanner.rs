@@ -1,19 +1,26 @@
 //! Network scanning simulation for CRIMSON-REDLINE
 
+use crate::game::state::{NetworkNode, NodeType, SecurityLevel};
 use anyhow::Result;
 use rand::Rng;
+use serde::{Serialize, Serializer};
 use std::time::Duration;
-use tokio::time::sleep;
 
 /// Scan result structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ScanResult {
     pub devices: Vec<Device>,
+    #[serde(rename = "scan_time_ms", serialize_with = "serialize_millis")]
     pub scan_time: Duration,
 }
 
+/// Write a duration as whole milliseconds, which reads better in JSON than secs/nanos
+fn serialize_millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_millis() as u64)
+}
+
 /// Discovered device information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Device {
     pub ip: String,
     pub hostname: String,
@@ -24,8 +31,64 @@ pub struct Device {
     pub services: Vec<Service>,
 }
 
+impl Device {
+    /// Guess what role the device plays from its OS and open ports
+    pub fn node_type(&self) -> NodeType {
+        let has = |port: u16| self.open_ports.contains(&port);
+        let os = self.os.as_str();
+
+        if os.starts_with("pfSense") {
+            NodeType::Firewall
+        } else if os.starts_with("Cisco") {
+            NodeType::Router
+        } else if os.starts_with("Windows Server") && has(445) && has(53) {
+            NodeType::DomainController
+        } else if has(3306) {
+            NodeType::Database
+        } else if has(25) || has(110) {
+            NodeType::MailServer
+        } else if [80, 443, 8080, 8443].iter().any(|&p| has(p)) {
+            NodeType::WebServer
+        } else if os.starts_with("Windows 1") || os.starts_with("macOS") {
+            NodeType::Workstation
+        } else if os.starts_with("Unknown") {
+            NodeType::Unknown
+        } else {
+            NodeType::Server
+        }
+    }
+
+    /// Rate how well defended the device looks from its known weaknesses
+    pub fn security_level(&self) -> SecurityLevel {
+        match self.vulnerabilities.len() {
+            0 if self.open_ports.len() <= 3 => SecurityLevel::High,
+            0 => SecurityLevel::Medium,
+            1 | 2 => SecurityLevel::Low,
+            // Plaintext remote access on top of several holes
+            _ if self.open_ports.contains(&23) => SecurityLevel::None,
+            _ => SecurityLevel::Low,
+        }
+    }
+
+    /// Convert into a node for the persistent network map
+    pub fn to_network_node(&self) -> NetworkNode {
+        NetworkNode {
+            ip: self.ip.clone(),
+            hostname: self.hostname.clone(),
+            node_type: self.node_type(),
+            is_compromised: false,
+            security_level: self.security_level(),
+            discovered_at: chrono::Utc::now(),
+            suspected_honeypot: false,
+            vulnerabilities: self.vulnerabilities.clone(),
+            has_rootkit: false,
+            open_ports: self.open_ports.clone(),
+        }
+    }
+}
+
 /// Service running on device
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Service {
     pub port: u16,
     pub name: String,
@@ -33,17 +96,176 @@ pub struct Service {
     pub vulnerable: bool,
 }
 
+/// Chance that any scanned host is secretly a honeypot
+pub const HONEYPOT_RATE: f32 = 0.15;
+
+/// Chance a safe scan spots a honeypot, improving with reputation level
+pub fn honeypot_detection_chance(reputation: i32) -> f32 {
+    use crate::game::ReputationLevel;
+    match ReputationLevel::from_reputation(reputation) {
+        ReputationLevel::Nobody | ReputationLevel::Wannabe => 0.3,
+        ReputationLevel::ScriptKiddie | ReputationLevel::Amateur => 0.4,
+        ReputationLevel::Competent | ReputationLevel::Skilled => 0.55,
+        ReputationLevel::Expert | ReputationLevel::Master => 0.7,
+        ReputationLevel::Elite | ReputationLevel::Legendary => 0.85,
+        ReputationLevel::Mythical => 0.95,
+    }
+}
+
+/// Fingerprint a device for honeypot signatures; true if it is flagged
+pub fn detect_honeypot(reputation: i32) -> bool {
+    let mut rng = rand::thread_rng();
+    rng.gen::<f32>() < HONEYPOT_RATE && rng.gen::<f32>() < honeypot_detection_chance(reputation)
+}
+
+/// Upper bound on devices in a single network sweep
+pub const MAX_SCAN_DEVICES: usize = 50;
+
+/// Pick a device count for a network sweep within [min, max], capped
+pub fn network_device_count(min: usize, max: usize) -> usize {
+    let max = max.clamp(1, MAX_SCAN_DEVICES);
+    let min = min.clamp(1, max);
+    rand::thread_rng().gen_range(min..=max)
+}
+
+/// Parse a `--count N` flag, capped at MAX_SCAN_DEVICES
+pub fn parse_count_flag(args: &[String]) -> Result<Option<usize>> {
+    match crate::commands::flag_value(args, "--count") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(count) if count > 0 => Ok(Some(count.min(MAX_SCAN_DEVICES))),
+            _ => anyhow::bail!("Invalid device count: {}", value),
+        },
+        None if args.iter().any(|a| a == "--count") => anyhow::bail!("--count requires a number"),
+        None => Ok(None),
+    }
+}
+
+/// Most ports a single `-p` spec may expand to
+pub const MAX_SCAN_PORTS: usize = 1024;
+
+/// Parse a port spec like `22,80,443` or `1-1024` into sorted, unique ports
+pub fn parse_port_spec(spec: &str) -> Result<Vec<u16>> {
+    let parse_port = |s: &str| match s.trim().parse::<u16>() {
+        Ok(port) if port > 0 => Ok(port),
+        _ => Err(anyhow::anyhow!("Invalid port: '{}' (expected 1-65535)", s.trim())),
+    };
+
+    let mut ports = Vec::new();
+    for part in spec.split(',') {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_port(start)?, parse_port(end)?);
+                if start > end {
+                    anyhow::bail!("Invalid port range: {}", part.trim());
+                }
+                ports.extend(start..=end);
+            }
+            None => ports.push(parse_port(part)?),
+        }
+
+        if ports.len() > MAX_SCAN_PORTS {
+            anyhow::bail!("Too many ports: at most {} per scan", MAX_SCAN_PORTS);
+        }
+    }
+
+    ports.sort_unstable();
+    ports.dedup();
+    Ok(ports)
+}
+
+/// Parse a `-p PORTS` flag
+pub fn parse_ports_flag(args: &[String]) -> Result<Option<Vec<u16>>> {
+    match crate::commands::flag_value(args, "-p") {
+        Some(spec) => parse_port_spec(spec).map(Some),
+        None if args.iter().any(|a| a == "-p") => anyhow::bail!("-p requires a port list, e.g. -p 22,80,443"),
+        None => Ok(None),
+    }
+}
+
+/// Map endpoint that network sweeps originate from
+pub const SCAN_ORIGIN: &str = "localhost";
+
+/// Most hosts a single pivot can reveal behind a compromised node
+pub const PIVOT_MAX_NEIGHBORS: usize = 4;
+
+/// Heat from a pivot scan; traffic from inside the network draws little attention
+pub const PIVOT_HEAT: f32 = 4.0;
+
+/// Echo requests sent by `ping`
+pub const PING_COUNT: usize = 4;
+
+/// Chance an arbitrary host doesn't answer pings
+pub const PING_UNREACHABLE_RATE: f32 = 0.15;
+
+/// Heat from a ping; barely noticeable
+pub const PING_HEAT: f32 = 1.0;
+
+/// Whether a host answers pings; firewalls drop ICMP outright
+pub fn ping_reachable(node: Option<&NetworkNode>) -> bool {
+    match node {
+        Some(node) if matches!(node.node_type, NodeType::Firewall) => false,
+        _ => rand::thread_rng().gen::<f32>() >= PING_UNREACHABLE_RATE,
+    }
+}
+
+/// Initial TTL a host's replies carry, going by its likely OS
+pub fn ping_ttl(node: Option<&NetworkNode>) -> u8 {
+    match node.map(|n| &n.node_type) {
+        Some(NodeType::Router) => 255,
+        Some(NodeType::Workstation | NodeType::DomainController | NodeType::MailServer) => 128,
+        _ => 64,
+    }
+}
+
+/// Heat from a target noticing a trace
+pub const TRACE_DETECTED_HEAT: f32 = 20.0;
+
+/// Chance a trace against a host gets noticed, by how well it's defended
+pub fn trace_detection_chance(level: &SecurityLevel) -> f32 {
+    match level {
+        SecurityLevel::None => 0.0,
+        SecurityLevel::Low => 0.05,
+        SecurityLevel::Medium => 0.15,
+        SecurityLevel::High => 0.3,
+        SecurityLevel::Maximum => 0.5,
+    }
+}
+
+/// The hop a trace of `hops` hops gets noticed at, if it does. Never the first
+/// hop, since the probe hasn't reached the target's network yet
+pub fn roll_trace_detection(hops: u8, chance: f32, rng: &mut impl Rng) -> Option<u8> {
+    if hops < 2 || rng.gen::<f32>() >= chance {
+        return None;
+    }
+    Some(rng.gen_range(2..=hops))
+}
+
+/// Min, average and max round-trip time, if any replies came back
+pub fn ping_summary(times_ms: &[f32]) -> Option<(f32, f32, f32)> {
+    if times_ms.is_empty() {
+        return None;
+    }
+    let min = times_ms.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = times_ms.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let avg = times_ms.iter().sum::<f32>() / times_ms.len() as f32;
+    Some((min, avg, max))
+}
+
 /// Execute a network scan
 pub async fn execute_scan(target: &str) -> Result<ScanResult> {
-    let start = std::time::Instant::now();
-    let mut devices = Vec::new();
-    
-    // Determine scan scope
     let device_count = if target == "network" {
-        rand::thread_rng().gen_range(5..15)
+        network_device_count(5, 14)
     } else {
         1
     };
+    execute_scan_with_count(target, device_count, 1).await
+}
+
+/// Execute a scan that discovers exactly `device_count` devices, each probe
+/// taking `pace` times as long
+pub async fn execute_scan_with_count(target: &str, device_count: usize, pace: u32) -> Result<ScanResult> {
+    let start = std::time::Instant::now();
+    let mut devices = Vec::new();
     
     // Generate discovered devices
     for _ in 0..device_count {
@@ -51,7 +273,7 @@ pub async fn execute_scan(target: &str) -> Result<ScanResult> {
         devices.push(device);
         
         // Simulate scan delay
-        sleep(Duration::from_millis(200)).await;
+        tokio::time::sleep(Duration::from_millis(200) * pace).await;
     }
     
     Ok(ScanResult {
@@ -60,6 +282,26 @@ pub async fn execute_scan(target: &str) -> Result<ScanResult> {
     })
 }
 
+/// Scan only the given ports on a single target at `pace`
+pub async fn execute_scan_ports(target: &str, ports: &[u16], pace: u32) -> Result<ScanResult> {
+    let start = std::time::Instant::now();
+    let mut device = generate_device(true);
+    device.ip = target.to_string();
+    
+    let results = port_scan(target, ports.to_vec(), pace).await?;
+    device.open_ports = results.into_iter()
+        .filter(|&(_, is_open)| is_open)
+        .map(|(port, _)| port)
+        .collect();
+    device.services = generate_services(&device.open_ports);
+    device.vulnerabilities = generate_vulnerabilities(&device.services);
+    
+    Ok(ScanResult {
+        devices: vec![device],
+        scan_time: start.elapsed(),
+    })
+}
+
 /// Generate a random device
 fn generate_device(is_targeted: bool) -> Device {
     let mut rng = rand::thread_rng();
@@ -237,10 +479,10 @@ fn generate_vulnerabilities(services: &[Service]) -> Vec<String> {
     vulnerabilities
 }
 
-/// Perform deep scan on specific target
-pub async fn deep_scan(target: &str) -> Result<Device> {
+/// Perform deep scan on specific target at `pace`
+pub async fn deep_scan(target: &str, pace: u32) -> Result<Device> {
     // Simulate intensive scanning
-    sleep(Duration::from_millis(2000)).await;
+    tokio::time::sleep(Duration::from_millis(2000) * pace).await;
     
     let mut device = generate_device(true);
     device.ip = target.to_string();
@@ -257,7 +499,7 @@ pub async fn deep_scan(target: &str) -> Result<Device> {
 /// Generate advanced vulnerability
 fn generate_advanced_vulnerability() -> String {
     let mut rng = rand::thread_rng();
-    let vulns = vec![
+    let vulns = [
         "Zero-Day Buffer Overflow in Kernel Module",
         "Unpatched Remote Code Execution Vector",
         "Authentication Bypass via Header Injection",
@@ -271,14 +513,15 @@ fn generate_advanced_vulnerability() -> String {
     vulns[rng.gen_range(0..vulns.len())].to_string()
 }
 
-/// Port scan specific ports
-pub async fn port_scan(target: &str, ports: Vec<u16>) -> Result<Vec<(u16, bool)>> {
+/// Port scan specific ports at `pace`
+pub async fn port_scan(_target: &str, ports: Vec<u16>, pace: u32) -> Result<Vec<(u16, bool)>> {
     let mut results = Vec::new();
     let mut rng = rand::thread_rng();
     
+    // Simulate scan delay, keeping wide ranges to a few seconds overall
+    let delay = (3000 / ports.len().max(1) as u64).clamp(2, 100);
     for port in ports {
-        // Simulate scan delay
-        sleep(Duration::from_millis(100)).await;
+        tokio::time::sleep(Duration::from_millis(delay) * pace).await;
         
         // Random chance port is open
         let is_open = rng.gen::<f32>() > 0.4;
@@ -288,10 +531,10 @@ pub async fn port_scan(target: &str, ports: Vec<u16>) -> Result<Vec<(u16, bool)>
     Ok(results)
 }
 
-/// Vulnerability scan
-pub async fn vulnerability_scan(target: &str) -> Result<Vec<String>> {
+/// Vulnerability scan at `pace`
+pub async fn vulnerability_scan(_target: &str, pace: u32) -> Result<Vec<String>> {
     // Simulate vuln scanning
-    sleep(Duration::from_millis(3000)).await;
+    tokio::time::sleep(Duration::from_millis(3000) * pace).await;
     
     let mut rng = rand::thread_rng();
     let vuln_count = rng.gen_range(3..10);
@@ -308,6 +551,54 @@ pub async fn vulnerability_scan(target: &str) -> Result<Vec<String>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_trace_detection_scales_with_security() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        assert!((0..100).all(|_| roll_trace_detection(10, 0.0, &mut rng).is_none()));
+        assert!((0..100).all(|_| roll_trace_detection(10, 1.0, &mut rng).is_some_and(|hop| (2..=10).contains(&hop))));
+        assert_eq!(roll_trace_detection(1, 1.0, &mut rng), None);
+
+        let levels = [SecurityLevel::None, SecurityLevel::Low, SecurityLevel::Medium, SecurityLevel::High, SecurityLevel::Maximum];
+        let chances: Vec<f32> = levels.iter().map(trace_detection_chance).collect();
+        assert!(chances.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_ping_summary() {
+        assert_eq!(ping_summary(&[]), None);
+        assert_eq!(ping_summary(&[10.0, 30.0, 20.0, 40.0]), Some((10.0, 25.0, 40.0)));
+    }
+
+    fn device(os: &str, open_ports: Vec<u16>, vulnerabilities: usize) -> Device {
+        Device {
+            ip: "10.0.0.7".to_string(),
+            hostname: "SRV-007".to_string(),
+            mac: "00:11:22:33:44:55".to_string(),
+            os: os.to_string(),
+            open_ports,
+            vulnerabilities: vec!["CVE-2017-0144: EternalBlue".to_string(); vulnerabilities],
+            services: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_device_becomes_typed_node() {
+        let db = device("Ubuntu 22.04 LTS", vec![22, 3306], 0);
+        let node = db.to_network_node();
+        assert_eq!(node.ip, "10.0.0.7");
+        assert!(matches!(node.node_type, NodeType::Database));
+        assert!(matches!(node.security_level, SecurityLevel::High));
+
+        let dc = device("Windows Server 2019", vec![53, 88, 445], 1);
+        assert!(matches!(dc.node_type(), NodeType::DomainController));
+        assert!(matches!(dc.security_level(), SecurityLevel::Low));
+
+        let router = device("Cisco IOS 15.9", vec![22, 23, 80, 443], 3);
+        assert!(matches!(router.node_type(), NodeType::Router));
+        assert!(matches!(router.security_level(), SecurityLevel::None));
+    }
+
     #[tokio::test]
     async fn test_execute_scan() {
         let result = execute_scan("network").await.unwrap();
@@ -315,6 +606,56 @@ mod tests {
         assert!(result.devices.len() >= 5 && result.devices.len() < 15);
     }
 
+    #[tokio::test]
+    async fn test_scan_count_flag() {
+        let args = vec!["network".to_string(), "--count".to_string(), "3".to_string()];
+        let count = parse_count_flag(&args).unwrap().unwrap();
+        let result = execute_scan_with_count("network", count, 1).await.unwrap();
+        assert_eq!(result.devices.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_count_flag() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(parse_count_flag(&args("network")).unwrap(), None);
+        assert_eq!(parse_count_flag(&args("--count 500")).unwrap(), Some(MAX_SCAN_DEVICES));
+        assert!(parse_count_flag(&args("--count zero")).is_err());
+        assert!(parse_count_flag(&args("--count")).is_err());
+        assert!(parse_count_flag(&args("--count 0")).is_err());
+    }
+
+    #[test]
+    fn test_parse_port_spec() {
+        assert_eq!(parse_port_spec("443,22,80,22").unwrap(), vec![22, 80, 443]);
+        assert_eq!(parse_port_spec("20-23").unwrap(), vec![20, 21, 22, 23]);
+        assert_eq!(parse_port_spec("1-1024").unwrap().len(), 1024);
+        assert!(parse_port_spec("80,abc").is_err());
+        assert!(parse_port_spec("0").is_err());
+        assert!(parse_port_spec("70000").is_err());
+        assert!(parse_port_spec("100-10").is_err());
+        assert!(parse_port_spec("1-65535").is_err());
+        assert!(parse_port_spec("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_port_scan_reports_only_requested_ports() {
+        let ports = [22, 80, 443];
+        let result = execute_scan_ports("10.0.0.5", &ports, 1).await.unwrap();
+        let device = &result.devices[0];
+        assert_eq!(device.ip, "10.0.0.5");
+        assert!(device.open_ports.iter().all(|p| ports.contains(p)));
+    }
+
+    #[test]
+    fn test_network_device_count_bounds() {
+        for _ in 0..20 {
+            let count = network_device_count(2, 4);
+            assert!((2..=4).contains(&count));
+        }
+        assert_eq!(network_device_count(10, 3), 3);
+        assert!(network_device_count(1, 1000) <= MAX_SCAN_DEVICES);
+    }
+
     #[test]
     fn test_generate_device() {
         let device = generate_device(false);
@@ -340,4 +681,11 @@ mod tests {
             assert!(ports.contains(&service.port));
         }
     }
+
+    #[test]
+    fn test_honeypot_detection_scales_with_reputation() {
+        assert!(honeypot_detection_chance(0) < honeypot_detection_chance(500));
+        assert!(honeypot_detection_chance(500) < honeypot_detection_chance(2000));
+        assert!(honeypot_detection_chance(10_000) <= 1.0);
+    }
 }
\ No newline at end of file
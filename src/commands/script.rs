@@ -0,0 +1,114 @@
+//! Non-interactive command scripts for CRIMSON-REDLINE
+//!
+//! A script is a plain file of commands, one per line, fed through the same
+//! `CommandHandler::execute` path as the prompt. Handy for reproducing bugs
+//! and smoke-testing the command layer.
+
+use crate::commands::{CommandHandler, CommandResult};
+use crate::ui::ColorScheme;
+use anyhow::Result;
+
+/// How a script run went
+#[derive(Debug, Default, PartialEq)]
+pub struct ScriptOutcome {
+    /// Commands run, including ones that failed
+    pub executed: usize,
+    /// Commands that returned an error
+    pub failed: usize,
+    /// Whether `logout` or `exit` ended the script before its last line
+    pub stopped_early: bool,
+}
+
+impl ScriptOutcome {
+    /// Process exit code: 0 when every command ran cleanly, 1 otherwise
+    pub fn exit_code(&self) -> i32 {
+        if self.failed == 0 { 0 } else { 1 }
+    }
+}
+
+/// Commands in a script, skipping blank lines and `#` comments
+pub fn parse_script(contents: &str) -> Vec<&str> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+/// Run commands in order, stopping at `logout` or `exit`
+pub async fn run_script(
+    handler: &mut CommandHandler,
+    commands: &[&str],
+    color_scheme: &ColorScheme,
+) -> Result<ScriptOutcome> {
+    let mut outcome = ScriptOutcome::default();
+
+    for (i, command) in commands.iter().enumerate() {
+        color_scheme.print_dim(&format!("\n> {}\n", command))?;
+        outcome.executed += 1;
+
+        let result = match handler.execute(command).await {
+            Ok(result) => result,
+            Err(e) => {
+                color_scheme.print_error(&format!("  [!] {}\n", e))?;
+                outcome.failed += 1;
+                continue;
+            }
+        };
+
+        // No popups here, but unlocks still count
+        for achievement in handler.game_state().await.check_achievements() {
+            color_scheme.print_success(&format!("  [✓] ACHIEVEMENT UNLOCKED: {} (+{} pts)\n", achievement.name, achievement.points))?;
+        }
+
+        match result {
+            CommandResult::Exit | CommandResult::Logout => {
+                outcome.stopped_early = i + 1 < commands.len();
+                break;
+            }
+            CommandResult::Error(message) => {
+                color_scheme.print_error(&format!("  [!] {}\n", message))?;
+                outcome.failed += 1;
+            }
+            CommandResult::AdminConsole
+            | CommandResult::Admin(_)
+            | CommandResult::ChangePassword
+            | CommandResult::Rename(_)
+            | CommandResult::DeleteAccount
+            | CommandResult::TwoFactor(_) => {
+                color_scheme.print_warning("  [!] Interactive command skipped in a script\n")?;
+            }
+            CommandResult::Success(_)
+            | CommandResult::Continue
+            | CommandResult::ThemeChanged(_)
+            | CommandResult::ConfigChanged(_) => {}
+        }
+    }
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::User;
+
+    #[test]
+    fn test_parse_skips_blanks_and_comments() {
+        let script = "# recon first\n\nscan 10.0.0.5\n   \n  # indented comment\n  status  \n";
+        assert_eq!(parse_script(script), vec!["scan 10.0.0.5", "status"]);
+    }
+
+    #[tokio::test]
+    async fn test_script_stops_at_logout() {
+        let user = User::new("scripted".to_string(), "pass").unwrap();
+        let mut handler = CommandHandler::new(&user);
+        let commands = ["mission accept recon-001", "logout", "status"];
+
+        let outcome = run_script(&mut handler, &commands, &ColorScheme::new()).await.unwrap();
+
+        assert_eq!(outcome, ScriptOutcome { executed: 2, failed: 0, stopped_early: true });
+        assert_eq!(outcome.exit_code(), 0);
+        assert_eq!(handler.game_state().await.active_missions.len(), 1);
+    }
+}
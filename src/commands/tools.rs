@@ -0,0 +1,128 @@
+//! Tool and exploit inventory for CRIMSON-REDLINE
+
+use crate::commands::darkweb;
+use crate::game::ReputationLevel;
+
+/// Where a tool comes from, which decides how it's grouped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToolSource {
+    /// Every agent starts with it
+    Starter,
+    /// Bought on the dark web
+    Market,
+    /// Picked up from random events
+    Field,
+}
+
+impl ToolSource {
+    /// Heading for the group in the inventory
+    pub fn heading(&self) -> &'static str {
+        match self {
+            ToolSource::Starter => "STARTER KIT",
+            ToolSource::Market => "BLACK MARKET",
+            ToolSource::Field => "FIELD ACQUISITIONS",
+        }
+    }
+}
+
+/// A tool the agent can hold
+#[derive(Debug, Clone)]
+pub struct ToolInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Rank before the tool can be had at all
+    pub required_level: ReputationLevel,
+    pub source: ToolSource,
+}
+
+/// Tools handed out at the start or by events; market tools come from the catalog
+const KNOWN_TOOLS: &[ToolInfo] = &[
+    ToolInfo {
+        id: "scan",
+        name: "Network Scanner",
+        description: "Port and service discovery",
+        required_level: ReputationLevel::Nobody,
+        source: ToolSource::Starter,
+    },
+    ToolInfo {
+        id: "decrypt",
+        name: "Cipher Suite",
+        description: "Breaks common encodings and weak ciphers",
+        required_level: ReputationLevel::Nobody,
+        source: ToolSource::Starter,
+    },
+    ToolInfo {
+        id: "saved_target",
+        name: "Saved Target",
+        description: "A backdoor left open on a previous job",
+        required_level: ReputationLevel::Nobody,
+        source: ToolSource::Field,
+    },
+    ToolInfo {
+        id: "elite_tools",
+        name: "Elite Toolkit",
+        description: "Handed down by a crew that respects your work",
+        required_level: ReputationLevel::Skilled,
+        source: ToolSource::Field,
+    },
+    ToolInfo {
+        id: "zero_day_pack",
+        name: "Zero-Day Pack",
+        description: "Unpatched exploits traded for a favor",
+        required_level: ReputationLevel::Expert,
+        source: ToolSource::Field,
+    },
+];
+
+/// Every tool in the game, grouped by source
+pub fn all_tools() -> Vec<ToolInfo> {
+    let market = darkweb::CATALOG.iter().map(|item| ToolInfo {
+        id: item.tool,
+        name: item.name,
+        description: item.effect,
+        required_level: item.required_level.clone(),
+        source: ToolSource::Market,
+    });
+
+    let mut tools: Vec<ToolInfo> = KNOWN_TOOLS.iter().cloned().chain(market).collect();
+    tools.sort_by_key(|tool| tool.source as u8);
+    tools
+}
+
+/// Look up a tool by id
+pub fn find_tool(id: &str) -> Option<ToolInfo> {
+    all_tools().into_iter().find(|tool| tool.id == id)
+}
+
+/// Short description of a discovered exploit
+pub fn exploit_description(name: &str) -> &'static str {
+    match name {
+        "AI_Slayer" => "Blinds machine-learning intrusion detection",
+        _ => "Undocumented exploit from the field",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_market_item_is_a_tool() {
+        for item in darkweb::CATALOG {
+            let tool = find_tool(item.tool).unwrap();
+            assert_eq!(tool.source, ToolSource::Market);
+            assert_eq!(tool.required_level, item.required_level);
+        }
+        assert_eq!(find_tool("scan").unwrap().source, ToolSource::Starter);
+        assert!(find_tool("nope").is_none());
+    }
+
+    #[test]
+    fn test_tools_are_grouped_by_source() {
+        let sources: Vec<ToolSource> = all_tools().iter().map(|tool| tool.source).collect();
+        let starters = sources.iter().take_while(|s| **s == ToolSource::Starter).count();
+        assert_eq!(starters, 2);
+        assert_eq!(sources.last(), Some(&ToolSource::Field));
+    }
+}
@@ -0,0 +1,119 @@
+//! Achievement unlock conditions for CRIMSON-REDLINE
+
+use crate::game::GameState;
+use serde::{Deserialize, Serialize};
+
+/// Record of an achievement the agent has unlocked, stored with the save
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockedAchievement {
+    pub id: String,
+    pub unlocked_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// How far the agent is through the achievement list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AchievementProgress {
+    pub unlocked: usize,
+    pub total: usize,
+    pub points: u32,
+    pub max_points: u32,
+}
+
+impl AchievementProgress {
+    /// Share of achievements unlocked, as a percentage
+    pub fn percent(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.unlocked as f32 / self.total as f32 * 100.0
+        }
+    }
+}
+
+/// Tally unlocked achievements and the points they're worth
+pub fn progress(state: &GameState) -> AchievementProgress {
+    let definitions = crate::game::generate_achievements();
+    let unlocked: Vec<_> = definitions.iter().filter(|a| state.has_achievement(&a.id)).collect();
+    AchievementProgress {
+        unlocked: unlocked.len(),
+        total: definitions.len(),
+        points: unlocked.iter().map(|a| a.points).sum(),
+        max_points: definitions.iter().map(|a| a.points).sum(),
+    }
+}
+
+/// Whether the game state meets an achievement's unlock condition
+pub fn is_earned(id: &str, state: &GameState) -> bool {
+    match id {
+        "first_scan" => state.total_scans >= 1,
+        "first_exploit" => state.successful_hacks >= 1,
+        "decrypt_master" => state.files_decrypted >= 50,
+        "reputation_100" => state.reputation >= 100,
+        "reputation_1000" => state.reputation >= 1000,
+        // Remaining achievements are unlocked by specific game moments
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::SaveGame;
+
+    #[test]
+    fn test_first_scan_unlocks_once() {
+        let mut state = GameState::new("agent".to_string(), 0);
+        assert!(state.check_achievements().is_empty());
+
+        state.record_scan();
+        let unlocked = state.check_achievements();
+        assert_eq!(unlocked.len(), 1);
+        assert_eq!(unlocked[0].id, "first_scan");
+        assert!(state.check_achievements().is_empty());
+    }
+
+    #[test]
+    fn test_progress_counts_points() {
+        let mut state = GameState::new("agent".to_string(), 0);
+        assert_eq!(progress(&state).points, 0);
+
+        state.record_scan();
+        state.reputation = 100;
+        state.check_achievements();
+        let progress = progress(&state);
+        assert_eq!(progress.unlocked, 2);
+        assert_eq!(progress.total, 10);
+        // Common scan (10) plus uncommon reputation (25)
+        assert_eq!(progress.points, 35);
+        assert_eq!(progress.percent(), 20.0);
+    }
+
+    #[test]
+    fn test_first_login_unlocks_on_the_first_login_only() {
+        let mut state = GameState::new("agent".to_string(), 0);
+        assert!(state.welcome_achievement(2).is_none());
+        assert!(!state.has_achievement("first_login"));
+
+        assert_eq!(state.welcome_achievement(1).unwrap().name, "Welcome to the Grid");
+        assert!(state.welcome_achievement(1).is_none());
+        assert_eq!(progress(&state).unlocked, 1);
+    }
+
+    #[test]
+    fn test_loaded_save_does_not_reunlock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent.sav");
+
+        let mut state = GameState::new("agent".to_string(), 0);
+        state.record_scan();
+        state.check_achievements();
+        SaveGame::new(state).save_to(&path).unwrap();
+
+        let mut loaded = SaveGame::load_from(&path).unwrap().unwrap().game_state;
+        assert!(loaded.has_achievement("first_scan"));
+
+        loaded.record_scan();
+        assert!(loaded.check_achievements().is_empty());
+        assert_eq!(loaded.unlocked_achievements.len(), 1);
+    }
+}
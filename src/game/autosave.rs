@@ -0,0 +1,107 @@
+//! Autosave scheduling for CRIMSON-REDLINE
+//!
+//! Decides when the ongoing game should be written out mid-session, so a
+//! crash or panic only loses the last few actions.
+
+use std::time::{Duration, Instant};
+
+/// Heat at which any further rise triggers an immediate save
+pub const RISKY_HEAT: f32 = 75.0;
+
+/// Tracks actions, time and heat since the last save
+#[derive(Debug)]
+pub struct Autosaver {
+    every_actions: Option<u32>,
+    interval: Option<Duration>,
+    actions: u32,
+    last_save: Instant,
+    last_heat: f32,
+}
+
+impl Autosaver {
+    /// Save every `every_actions` commands and/or every `interval`; `None` disables either trigger
+    pub fn new(every_actions: Option<u32>, interval: Option<Duration>) -> Self {
+        Autosaver {
+            every_actions,
+            interval,
+            actions: 0,
+            last_save: Instant::now(),
+            last_heat: 0.0,
+        }
+    }
+
+    /// Count a command the player ran
+    pub fn record_action(&mut self) {
+        self.actions += 1;
+    }
+
+    /// Whether a save is due at the current heat
+    pub fn due(&self, heat: f32) -> bool {
+        self.due_at(Instant::now(), heat)
+    }
+
+    /// Whether a save is due at `now`
+    pub fn due_at(&self, now: Instant, heat: f32) -> bool {
+        let by_actions = self.every_actions.is_some_and(|n| self.actions >= n);
+        let by_time = self.actions > 0
+            && self.interval.is_some_and(|interval| now.duration_since(self.last_save) >= interval);
+        // Heat climbing into the danger zone is when a bust is most likely
+        let risky = heat >= RISKY_HEAT && heat > self.last_heat;
+        by_actions || by_time || risky
+    }
+
+    /// Note a completed save
+    pub fn saved(&mut self, heat: f32) {
+        self.saved_at(Instant::now(), heat);
+    }
+
+    /// Note a save completed at `now`
+    pub fn saved_at(&mut self, now: Instant, heat: f32) {
+        self.actions = 0;
+        self.last_save = now;
+        self.last_heat = heat;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saves_every_n_actions() {
+        let mut autosaver = Autosaver::new(Some(3), None);
+        autosaver.record_action();
+        autosaver.record_action();
+        assert!(!autosaver.due(0.0));
+        autosaver.record_action();
+        assert!(autosaver.due(0.0));
+
+        autosaver.saved(0.0);
+        assert!(!autosaver.due(0.0));
+    }
+
+    #[test]
+    fn test_saves_on_interval_only_after_activity() {
+        let start = Instant::now();
+        let mut autosaver = Autosaver::new(None, Some(Duration::from_secs(120)));
+        autosaver.saved_at(start, 0.0);
+        let later = start + Duration::from_secs(180);
+
+        // Nothing changed while idle, so there's nothing to save
+        assert!(!autosaver.due_at(later, 0.0));
+        autosaver.record_action();
+        assert!(!autosaver.due_at(start + Duration::from_secs(60), 0.0));
+        assert!(autosaver.due_at(later, 0.0));
+    }
+
+    #[test]
+    fn test_saves_when_heat_climbs_into_danger() {
+        let mut autosaver = Autosaver::new(None, None);
+        assert!(!autosaver.due(60.0));
+        assert!(autosaver.due(80.0));
+
+        autosaver.saved(80.0);
+        assert!(!autosaver.due(78.0));
+        assert!(autosaver.due(90.0));
+    }
+}
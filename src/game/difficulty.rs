@@ -0,0 +1,154 @@
+//! Difficulty scaling for CRIMSON-REDLINE
+//!
+//! The configured difficulty sets fixed multipliers for heat, events and
+//! success odds. Adaptive difficulty is an optional modifier layered over it:
+//! players who keep getting busted get some breathing room, players who
+//! dominate get pushed.
+
+use crate::game::GameState;
+use crate::utils::config::Difficulty;
+
+/// Lowest factor adaptive difficulty will apply to any value
+pub const MIN_ADAPTIVE_FACTOR: f32 = 0.75;
+
+/// Highest factor adaptive difficulty will apply to any value
+pub const MAX_ADAPTIVE_FACTOR: f32 = 1.25;
+
+/// Hack attempts needed before the success rate is trusted
+const MIN_HACK_SAMPLE: u32 = 5;
+
+/// Multipliers applied on top of the base game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyModifier {
+    /// Scales the random event chance
+    pub event_chance: f32,
+    /// Scales exploit success chances
+    pub success_chance: f32,
+    /// Scales heat gained per action
+    pub heat_gain: f32,
+    /// Scales how quickly heat cools off
+    pub heat_decay: f32,
+}
+
+impl DifficultyModifier {
+    /// Modifier that changes nothing
+    pub const NEUTRAL: DifficultyModifier = DifficultyModifier {
+        event_chance: 1.0,
+        success_chance: 1.0,
+        heat_gain: 1.0,
+        heat_decay: 1.0,
+    };
+
+    /// Fixed multipliers for a difficulty setting; Hacker is the baseline
+    pub fn for_difficulty(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Script => DifficultyModifier {
+                event_chance: 0.5,
+                success_chance: 1.25,
+                heat_gain: 0.6,
+                heat_decay: 1.5,
+            },
+            Difficulty::Hacker => Self::NEUTRAL,
+            Difficulty::Ghost => DifficultyModifier {
+                event_chance: 1.3,
+                success_chance: 0.85,
+                heat_gain: 1.3,
+                heat_decay: 0.75,
+            },
+            Difficulty::Phantom => DifficultyModifier {
+                event_chance: 1.75,
+                success_chance: 0.7,
+                heat_gain: 1.6,
+                heat_decay: 0.5,
+            },
+        }
+    }
+
+    /// Scale a success probability, keeping some chance of failure
+    pub fn scale_success(&self, chance: f32) -> f32 {
+        (chance * self.success_chance).min(0.95)
+    }
+
+    /// Scale a heat decay rate (the share of heat kept per minute)
+    pub fn scale_heat_decay(&self, decay_rate: f32) -> f32 {
+        (1.0 - (1.0 - decay_rate) * self.heat_decay).clamp(0.0, 1.0)
+    }
+}
+
+/// Work out how hard to push the player based on how they've been doing
+pub fn adaptive_modifier(state: &GameState) -> DifficultyModifier {
+    let mut pressure = 0.0;
+
+    // A strong success rate ramps things up, a poor one eases off
+    let attempts = state.successful_hacks + state.failed_hacks;
+    if attempts >= MIN_HACK_SAMPLE {
+        pressure += (state.get_success_rate() / 100.0 - 0.5) * 0.4;
+    }
+
+    // Each bust (up to two) buys the player some slack
+    pressure -= 0.1 * state.times_busted.min(2) as f32;
+
+    DifficultyModifier {
+        event_chance: (1.0 + pressure).clamp(MIN_ADAPTIVE_FACTOR, MAX_ADAPTIVE_FACTOR),
+        success_chance: (1.0 - pressure).clamp(MIN_ADAPTIVE_FACTOR, MAX_ADAPTIVE_FACTOR),
+        ..DifficultyModifier::NEUTRAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with(successes: u32, failures: u32, busts: u32) -> GameState {
+        let mut state = GameState::new("test".to_string(), 0);
+        state.successful_hacks = successes;
+        state.failed_hacks = failures;
+        state.times_busted = busts;
+        state
+    }
+
+    #[test]
+    fn test_new_player_is_neutral() {
+        assert_eq!(adaptive_modifier(&state_with(0, 0, 0)), DifficultyModifier::NEUTRAL);
+        // Too few attempts to judge
+        assert_eq!(adaptive_modifier(&state_with(3, 0, 0)), DifficultyModifier::NEUTRAL);
+    }
+
+    #[test]
+    fn test_dominating_player_is_pushed() {
+        let modifier = adaptive_modifier(&state_with(20, 0, 0));
+        assert!(modifier.event_chance > 1.0);
+        assert!(modifier.success_chance < 1.0);
+    }
+
+    #[test]
+    fn test_busted_player_gets_slack() {
+        let modifier = adaptive_modifier(&state_with(2, 8, 2));
+        assert!(modifier.event_chance < 1.0);
+        assert!(modifier.success_chance > 1.0);
+    }
+
+    #[test]
+    fn test_difficulty_settings_scale() {
+        assert_eq!(DifficultyModifier::for_difficulty(Difficulty::Hacker), DifficultyModifier::NEUTRAL);
+
+        let script = DifficultyModifier::for_difficulty(Difficulty::Script);
+        let phantom = DifficultyModifier::for_difficulty(Difficulty::Phantom);
+        assert!(script.heat_gain < 1.0 && phantom.heat_gain > 1.0);
+        assert!(script.scale_success(0.6) > phantom.scale_success(0.6));
+        assert!(script.scale_heat_decay(0.9) < 0.9 && phantom.scale_heat_decay(0.9) > 0.9);
+        assert_eq!(script.scale_success(0.9), 0.95);
+    }
+
+    #[test]
+    fn test_adjustment_stays_in_bounds() {
+        for modifier in [
+            adaptive_modifier(&state_with(1000, 0, 0)),
+            adaptive_modifier(&state_with(0, 1000, 50)),
+        ] {
+            for factor in [modifier.event_chance, modifier.success_chance] {
+                assert!((MIN_ADAPTIVE_FACTOR..=MAX_ADAPTIVE_FACTOR).contains(&factor));
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Player-authored random events for CRIMSON-REDLINE
+//!
+//! An `events.json` in the data directory holds a JSON array of
+//! [`CustomEvent`] entries, merged with the built-in events at startup.
+//! Entries that don't parse or fail validation are skipped with a warning,
+//! so one bad event doesn't take the whole pack down.
+
+use crate::game::events::{EventChoice, EventCost, EventManager, EventOutcome, EventSeverity, EventType, RandomEvent};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Special outcomes the event manager knows how to play out
+pub const SPECIAL_OUTCOMES: &[&str] = &["negotiation", "race_rival", "ai_battle"];
+
+/// Most choices an event can offer, since they're picked with keys 1-9
+pub const MAX_CHOICES: usize = 9;
+
+/// Longest wait a choice can cost, in seconds
+pub const MAX_TIME_COST_SECS: u32 = 300;
+
+/// When an event can come up
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventPool {
+    /// Heat above 75%
+    HighHeat,
+    /// Reputation above 1000
+    HighReputation,
+    /// Everyone else, half the time
+    Opportunity,
+    /// Everyone else, the other half
+    Threat,
+}
+
+/// One entry in `events.json`, e.g.
+///
+/// ```json
+/// {
+///   "pool": "threat",
+///   "id": "sysadmin_awake",
+///   "title": "SYSADMIN ONLINE",
+///   "description": "Someone just logged in to the box you're on",
+///   "event_type": "Threat",
+///   "severity": "Medium",
+///   "time_limit_secs": 20,
+///   "weight": 2,
+///   "choices": [
+///     { "label": "Freeze", "outcome": { "IncreaseHeat": 5.0 }, "cost": null },
+///     { "label": "Bribe them", "outcome": "SafeExit", "cost": { "Credits": 150 } }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomEvent {
+    pub pool: EventPool,
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub event_type: EventType,
+    pub severity: EventSeverity,
+    pub choices: Vec<EventChoice>,
+    /// Seconds to answer before the event times out; omit for no limit
+    #[serde(default)]
+    pub time_limit_secs: Option<u64>,
+    /// Relative chance of being picked from its pool; omit to go by severity
+    /// (Low 4, Medium 3, High 2, Critical 1)
+    #[serde(default)]
+    pub weight: Option<u32>,
+}
+
+impl CustomEvent {
+    /// Check the event can be played, naming the first problem found
+    pub fn validate(&self) -> Result<()> {
+        if self.id.trim().is_empty() || self.title.trim().is_empty() {
+            anyhow::bail!("id and title must not be empty");
+        }
+        if EventManager::is_builtin_id(&self.id) {
+            anyhow::bail!("id '{}' is taken by a built-in event", self.id);
+        }
+        if self.choices.is_empty() || self.choices.len() > MAX_CHOICES {
+            anyhow::bail!("needs between 1 and {} choices", MAX_CHOICES);
+        }
+        for choice in &self.choices {
+            validate_choice(choice).map_err(|e| anyhow::anyhow!("choice '{}': {}", choice.label, e))?;
+        }
+        Ok(())
+    }
+
+    /// The event as the manager plays it
+    pub fn into_event(self) -> RandomEvent {
+        RandomEvent {
+            id: self.id,
+            title: self.title,
+            description: self.description,
+            event_type: self.event_type,
+            severity: self.severity,
+            choices: self.choices,
+            time_limit: self.time_limit_secs.map(std::time::Duration::from_secs),
+            weight: self.weight,
+        }
+    }
+}
+
+/// Check a choice's outcome and cost make sense
+fn validate_choice(choice: &EventChoice) -> Result<()> {
+    if choice.label.trim().is_empty() {
+        anyhow::bail!("label must not be empty");
+    }
+    match &choice.outcome {
+        EventOutcome::GainCredits(amount) | EventOutcome::GainReputation(amount) if *amount < 0 => {
+            anyhow::bail!("gains must not be negative")
+        }
+        EventOutcome::ReduceHeat(heat) | EventOutcome::IncreaseHeat(heat) if !(0.0..=100.0).contains(heat) => {
+            anyhow::bail!("heat changes must be between 0 and 100")
+        }
+        EventOutcome::UnlockContent(content) if content.trim().is_empty() => {
+            anyhow::bail!("nothing to unlock")
+        }
+        EventOutcome::Special(action) if !SPECIAL_OUTCOMES.contains(&action.as_str()) => {
+            anyhow::bail!("unknown special outcome '{}' (known: {})", action, SPECIAL_OUTCOMES.join(", "))
+        }
+        _ => {}
+    }
+    match &choice.cost {
+        Some(EventCost::Credits(amount)) | Some(EventCost::Reputation(amount)) if *amount < 0 => {
+            anyhow::bail!("costs must not be negative")
+        }
+        Some(EventCost::Heat(heat)) if !(0.0..=100.0).contains(heat) => {
+            anyhow::bail!("heat costs must be between 0 and 100")
+        }
+        Some(EventCost::Time(seconds)) if *seconds > MAX_TIME_COST_SECS => {
+            anyhow::bail!("time costs must be at most {} seconds", MAX_TIME_COST_SECS)
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Events loaded from a pack, plus why any entries were skipped
+#[derive(Debug, Default)]
+pub struct EventPack {
+    pub events: Vec<(EventPool, RandomEvent)>,
+    pub warnings: Vec<String>,
+}
+
+impl EventPack {
+    /// Load `events.json` from the data directory; no file means no extra events
+    pub fn load() -> Result<Self> {
+        Self::load_from(&crate::utils::get_data_dir()?.join("events.json"))
+    }
+
+    /// Load a pack, skipping malformed entries and repeated ids
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let mut pack = EventPack::default();
+        if !path.exists() {
+            return Ok(pack);
+        }
+
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| anyhow::anyhow!("{} must hold a JSON array of events: {}", path.display(), e))?;
+
+        for (i, entry) in entries.into_iter().enumerate() {
+            let checked = serde_json::from_value::<CustomEvent>(entry)
+                .map_err(anyhow::Error::from)
+                .and_then(|event| event.validate().map(|()| event));
+            match checked {
+                Ok(event) if pack.events.iter().any(|(_, e)| e.id == event.id) => {
+                    pack.warnings.push(format!("event #{} ({}): duplicate id, skipped", i + 1, event.id));
+                }
+                Ok(event) => pack.events.push((event.pool, event.into_event())),
+                Err(e) => pack.warnings.push(format!("event #{}: {}, skipped", i + 1, e)),
+            }
+        }
+        Ok(pack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_skips_bad_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.json");
+        std::fs::write(&path, r#"[
+            {"pool": "threat", "id": "sysadmin", "title": "SYSADMIN ONLINE", "description": "",
+             "event_type": "Threat", "severity": "Medium", "time_limit_secs": 20,
+             "choices": [{"label": "Freeze", "outcome": {"IncreaseHeat": 5.0}, "cost": null}]},
+            {"pool": "threat", "id": "empty", "title": "EMPTY", "description": "",
+             "event_type": "Threat", "severity": "Low", "choices": []},
+            {"pool": "opportunity", "id": "party", "title": "PARTY", "description": "",
+             "event_type": "Neutral", "severity": "Low",
+             "choices": [{"label": "Dance", "outcome": {"Special": "dance"}, "cost": null}]},
+            {"pool": "nowhere", "id": "lost"},
+            {"pool": "threat", "id": "nap", "title": "NAP", "description": "",
+             "event_type": "Neutral", "severity": "Low",
+             "choices": [{"label": "Sleep", "outcome": "SafeExit", "cost": {"Time": 3600}}]},
+            {"pool": "threat", "id": "honeypot", "title": "HONEYPOT", "description": "",
+             "event_type": "Threat", "severity": "Low",
+             "choices": [{"label": "Run", "outcome": "SafeExit", "cost": null}]},
+            {"pool": "high_heat", "id": "sysadmin", "title": "AGAIN", "description": "",
+             "event_type": "Threat", "severity": "Low",
+             "choices": [{"label": "Run", "outcome": "SafeExit", "cost": {"Credits": 10}}]}
+        ]"#).unwrap();
+
+        let pack = EventPack::load_from(&path).unwrap();
+        assert_eq!(pack.events.len(), 1);
+        let (pool, event) = &pack.events[0];
+        assert_eq!(*pool, EventPool::Threat);
+        assert_eq!(event.time_limit, Some(std::time::Duration::from_secs(20)));
+
+        assert_eq!(pack.warnings.len(), 6);
+        assert!(pack.warnings[1].contains("unknown special outcome 'dance'"));
+        assert!(pack.warnings[3].contains("at most 300 seconds"));
+        assert!(pack.warnings[4].contains("taken by a built-in event"));
+        assert!(pack.warnings[5].contains("duplicate id"));
+    }
+
+    #[test]
+    fn test_missing_pack_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let pack = EventPack::load_from(&dir.path().join("events.json")).unwrap();
+        assert!(pack.events.is_empty() && pack.warnings.is_empty());
+        assert!(EventPack::load_from(dir.path()).is_err());
+    }
+}
@@ -1,10 +1,21 @@
 //! Random events system for CRIMSON-REDLINE
 
+use crate::game::event_pack::EventPool;
 use crate::ui::ColorScheme;
 use anyhow::Result;
 use rand::Rng;
+use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 
+/// Heat gained when the player lets a timed event run out
+pub const EVENT_TIMEOUT_HEAT: f32 = 10.0;
+
+/// Past events kept in the save for the `events` command
+pub const EVENT_LOG_LIMIT: usize = 50;
+
+/// Minimum gap between random events unless configured otherwise
+pub const DEFAULT_EVENT_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// Event manager for random events
 #[derive(Debug, Clone)]
 pub struct EventManager {
@@ -12,23 +23,64 @@ pub struct EventManager {
     pub event_history: Vec<RandomEvent>,
     pub event_chance: f32,
     pub last_event_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// No new event until this long after the last one
+    pub cooldown: std::time::Duration,
+    pub autosave_before_irreversible: bool,
+    /// Scale event_chance with the player's performance
+    pub adaptive_difficulty: bool,
+    /// Multiplier on event_chance, updated by the ticker when adaptive
+    pub chance_scale: f32,
+    /// Events generated but not yet shown to the player
+    pub new_events: Vec<RandomEvent>,
+    /// Whether random events happen at all
+    pub enabled: bool,
+    /// Extra events from `events.json`, with the pool each belongs to
+    pub custom_events: Vec<(EventPool, RandomEvent)>,
+}
+
+impl Default for EventManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EventManager {
     /// Create new event manager
     pub fn new() -> Self {
+        Self::with_enabled(true)
+    }
+
+    /// Create an event manager, with random events switched on or off
+    pub fn with_enabled(enabled: bool) -> Self {
         EventManager {
             active_events: Vec::new(),
             event_history: Vec::new(),
             event_chance: 0.1, // 10% chance per action
             last_event_time: None,
+            cooldown: DEFAULT_EVENT_COOLDOWN,
+            autosave_before_irreversible: true,
+            adaptive_difficulty: false,
+            chance_scale: 1.0,
+            new_events: Vec::new(),
+            enabled,
+            custom_events: Vec::new(),
         }
     }
 
+    /// Whether the last event was too recent for another
+    pub fn cooling_down(&self) -> bool {
+        self.last_event_time.is_some_and(|last| {
+            (chrono::Utc::now() - last).to_std().map_or(true, |elapsed| elapsed < self.cooldown)
+        })
+    }
+
     /// Check if a random event should trigger
     pub fn should_trigger_event(&self) -> bool {
+        if !self.enabled || self.cooling_down() {
+            return false;
+        }
         let mut rng = rand::thread_rng();
-        rng.gen::<f32>() < self.event_chance
+        rng.gen::<f32>() < self.event_chance * self.chance_scale
     }
 
     /// Generate a random event based on current game state
@@ -57,14 +109,61 @@ impl EventManager {
         self.last_event_time = Some(chrono::Utc::now());
         self.event_history.push(event.clone());
         self.active_events.push(event.clone());
+        self.new_events.push(event.clone());
         
         Some(event)
     }
 
+    /// Take events generated since the last call, for display
+    pub fn take_new_events(&mut self) -> Vec<RandomEvent> {
+        std::mem::take(&mut self.new_events)
+    }
+
+    /// Pick one of the built-in events or custom events for a pool, by weight
+    fn pick(&self, pool: EventPool, builtin: &[RandomEvent]) -> RandomEvent {
+        let custom = self.custom_events.iter().filter(|(p, _)| *p == pool).map(|(_, event)| event);
+        let candidates: Vec<&RandomEvent> = builtin.iter().chain(custom).collect();
+        let mut rng = rand::thread_rng();
+        // Only fails if every weight is zero; fall back to an even pick
+        match candidates.choose_weighted(&mut rng, |event| event.weight()) {
+            Ok(event) => (*event).clone(),
+            Err(_) => candidates[rng.gen_range(0..candidates.len())].clone(),
+        }
+    }
+
     /// Generate high heat event
     fn generate_high_heat_event(&self) -> RandomEvent {
-        let mut rng = rand::thread_rng();
-        let events = vec![
+        self.pick(EventPool::HighHeat, &Self::high_heat_events())
+    }
+
+    /// Generate high reputation event
+    fn generate_high_reputation_event(&self) -> RandomEvent {
+        self.pick(EventPool::HighReputation, &Self::high_reputation_events())
+    }
+
+    /// Generate opportunity event
+    fn generate_opportunity_event(&self) -> RandomEvent {
+        self.pick(EventPool::Opportunity, &Self::opportunity_events())
+    }
+
+    /// Generate threat event
+    fn generate_threat_event(&self) -> RandomEvent {
+        self.pick(EventPool::Threat, &Self::threat_events())
+    }
+
+    /// Whether a built-in event already uses this id
+    pub fn is_builtin_id(id: &str) -> bool {
+        Self::high_heat_events()
+            .into_iter()
+            .chain(Self::high_reputation_events())
+            .chain(Self::opportunity_events())
+            .chain(Self::threat_events())
+            .any(|event| event.id == id)
+    }
+
+    /// Built-in high heat events
+    fn high_heat_events() -> Vec<RandomEvent> {
+        vec![
             RandomEvent {
                 id: "trace_initiated".to_string(),
                 title: "TRACE INITIATED".to_string(),
@@ -89,6 +188,7 @@ impl EventManager {
                     },
                 ],
                 time_limit: Some(std::time::Duration::from_secs(30)),
+                weight: None,
             },
             RandomEvent {
                 id: "system_lockdown".to_string(),
@@ -109,16 +209,14 @@ impl EventManager {
                     },
                 ],
                 time_limit: Some(std::time::Duration::from_secs(20)),
+                weight: None,
             },
-        ];
-        
-        events[rng.gen_range(0..events.len())].clone()
+        ]
     }
 
-    /// Generate high reputation event
-    fn generate_high_reputation_event(&self) -> RandomEvent {
-        let mut rng = rand::thread_rng();
-        let events = vec![
+    /// Built-in high reputation events
+    fn high_reputation_events() -> Vec<RandomEvent> {
+        vec![
             RandomEvent {
                 id: "elite_invitation".to_string(),
                 title: "ELITE INVITATION".to_string(),
@@ -138,6 +236,7 @@ impl EventManager {
                     },
                 ],
                 time_limit: None,
+                weight: None,
             },
             RandomEvent {
                 id: "black_market_deal".to_string(),
@@ -163,16 +262,14 @@ impl EventManager {
                     },
                 ],
                 time_limit: Some(std::time::Duration::from_secs(60)),
+                weight: None,
             },
-        ];
-        
-        events[rng.gen_range(0..events.len())].clone()
+        ]
     }
 
-    /// Generate opportunity event
-    fn generate_opportunity_event(&self) -> RandomEvent {
-        let mut rng = rand::thread_rng();
-        let events = vec![
+    /// Built-in opportunity events
+    fn opportunity_events() -> Vec<RandomEvent> {
+        vec![
             RandomEvent {
                 id: "vulnerable_system".to_string(),
                 title: "VULNERABLE SYSTEM DETECTED".to_string(),
@@ -192,6 +289,7 @@ impl EventManager {
                     },
                 ],
                 time_limit: Some(std::time::Duration::from_secs(45)),
+                weight: None,
             },
             RandomEvent {
                 id: "data_cache".to_string(),
@@ -212,6 +310,7 @@ impl EventManager {
                     },
                 ],
                 time_limit: None,
+                weight: None,
             },
             RandomEvent {
                 id: "backdoor_found".to_string(),
@@ -237,16 +336,14 @@ impl EventManager {
                     },
                 ],
                 time_limit: None,
+                weight: None,
             },
-        ];
-        
-        events[rng.gen_range(0..events.len())].clone()
+        ]
     }
 
-    /// Generate threat event
-    fn generate_threat_event(&self) -> RandomEvent {
-        let mut rng = rand::thread_rng();
-        let events = vec![
+    /// Built-in threat events
+    fn threat_events() -> Vec<RandomEvent> {
+        vec![
             RandomEvent {
                 id: "honeypot".to_string(),
                 title: "HONEYPOT DETECTED".to_string(),
@@ -271,6 +368,7 @@ impl EventManager {
                     },
                 ],
                 time_limit: Some(std::time::Duration::from_secs(15)),
+                weight: None,
             },
             RandomEvent {
                 id: "rival_hacker".to_string(),
@@ -296,6 +394,7 @@ impl EventManager {
                     },
                 ],
                 time_limit: Some(std::time::Duration::from_secs(30)),
+                weight: None,
             },
             RandomEvent {
                 id: "ai_defense".to_string(),
@@ -321,10 +420,9 @@ impl EventManager {
                     },
                 ],
                 time_limit: Some(std::time::Duration::from_secs(20)),
+                weight: None,
             },
-        ];
-        
-        events[rng.gen_range(0..events.len())].clone()
+        ]
     }
 
     /// Handle event choice
@@ -340,6 +438,16 @@ impl EventManager {
             
             if choice_index < event.choices.len() {
                 let choice = &event.choices[choice_index];
+                game_state.record_event(EventRecord::new(&event, Some(choice_index)));
+                
+                // Snapshot before anything permanent happens so the player can reload
+                if self.autosave_before_irreversible && choice.is_irreversible() {
+                    let snapshot = crate::game::SaveGame::new(game_state.clone());
+                    match snapshot.save_slot(crate::game::PRE_EVENT_SLOT) {
+                        Ok(()) => color_scheme.print_dim("  [autosaved] pre-event\n")?,
+                        Err(e) => color_scheme.print_warning(&format!("  [!] Pre-event autosave failed: {}\n", e))?,
+                    }
+                }
                 
                 // Apply cost
                 if let Some(ref cost) = choice.cost {
@@ -351,7 +459,7 @@ impl EventManager {
                             }
                         }
                         EventCost::Reputation(amount) => {
-                            game_state.add_reputation(-(*amount as i32));
+                            game_state.add_reputation(-*amount);
                         }
                         EventCost::Heat(amount) => {
                             game_state.increase_heat(*amount);
@@ -401,6 +509,25 @@ impl EventManager {
         Ok(())
     }
 
+    /// Apply the default outcome when a timed event expires unanswered
+    pub fn handle_timeout(
+        &mut self,
+        event_id: &str,
+        game_state: &mut crate::game::GameState,
+        color_scheme: &ColorScheme,
+    ) -> Result<()> {
+        if let Some(event_idx) = self.active_events.iter().position(|e| e.id == event_id) {
+            let event = self.active_events.remove(event_idx);
+            game_state.record_event(EventRecord::new(&event, None));
+            game_state.increase_heat(EVENT_TIMEOUT_HEAT);
+            color_scheme.print_warning(&format!(
+                "  [!] Too slow! You did nothing. Heat increased by {}%!\n",
+                EVENT_TIMEOUT_HEAT
+            ))?;
+        }
+        Ok(())
+    }
+
     /// Handle special event outcomes
     async fn handle_special_outcome(
         &self,
@@ -411,7 +538,7 @@ impl EventManager {
         match action {
             "negotiation" => {
                 color_scheme.print_colored("  [>] Negotiating price...\n")?;
-                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                crate::ui::animations::pause(std::time::Duration::from_secs(2), color_scheme).await;
                 
                 let mut rng = rand::thread_rng();
                 if rng.gen::<f32>() > 0.5 {
@@ -423,7 +550,7 @@ impl EventManager {
             }
             "race_rival" => {
                 color_scheme.print_colored("  [>] Racing against rival hacker...\n")?;
-                crate::ui::animations::show_processing("Competing for access", 3000).await?;
+                crate::ui::animations::show_processing("Competing for access", 3000, color_scheme).await?;
                 
                 let mut rng = rand::thread_rng();
                 if rng.gen::<f32>() > 0.4 {
@@ -437,7 +564,7 @@ impl EventManager {
             }
             "ai_battle" => {
                 color_scheme.print_colored("  [>] Engaging AI defense system...\n")?;
-                crate::ui::animations::show_processing("Cyber warfare in progress", 5000).await?;
+                crate::ui::animations::show_processing("Cyber warfare in progress", 5000, color_scheme).await?;
                 
                 let mut rng = rand::thread_rng();
                 if rng.gen::<f32>() > 0.3 {
@@ -468,6 +595,46 @@ pub struct RandomEvent {
     pub severity: EventSeverity,
     pub choices: Vec<EventChoice>,
     pub time_limit: Option<std::time::Duration>,
+    /// Relative chance of being picked from its pool; `None` uses the severity's default
+    #[serde(default)]
+    pub weight: Option<u32>,
+}
+
+impl RandomEvent {
+    /// Relative chance of being picked from its pool
+    pub fn weight(&self) -> u32 {
+        self.weight.unwrap_or_else(|| self.severity.default_weight())
+    }
+
+    /// Map a pressed key like '2' to a choice index, if it names a valid choice
+    pub fn choice_for_key(&self, key: char) -> Option<usize> {
+        let number = key.to_digit(10)? as usize;
+        (1..=self.choices.len()).contains(&number).then(|| number - 1)
+    }
+}
+
+/// A past event and how the player answered it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub id: String,
+    pub title: String,
+    pub severity: EventSeverity,
+    /// Index and label of the choice made; `None` when the event timed out
+    pub choice: Option<(usize, String)>,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+impl EventRecord {
+    /// Record an event answered with `choice`, or left to time out
+    pub fn new(event: &RandomEvent, choice: Option<usize>) -> Self {
+        EventRecord {
+            id: event.id.clone(),
+            title: event.title.clone(),
+            severity: event.severity.clone(),
+            choice: choice.and_then(|i| event.choices.get(i).map(|c| (i, c.label.clone()))),
+            at: chrono::Utc::now(),
+        }
+    }
 }
 
 /// Event types
@@ -487,6 +654,18 @@ pub enum EventSeverity {
     Critical,
 }
 
+impl EventSeverity {
+    /// Selection weight for events that don't set one; the worse, the rarer
+    pub fn default_weight(&self) -> u32 {
+        match self {
+            EventSeverity::Low => 4,
+            EventSeverity::Medium => 3,
+            EventSeverity::High => 2,
+            EventSeverity::Critical => 1,
+        }
+    }
+}
+
 /// Event choice
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventChoice {
@@ -495,6 +674,21 @@ pub struct EventChoice {
     pub cost: Option<EventCost>,
 }
 
+impl EventChoice {
+    /// Whether this choice permanently changes state (worth a pre-event save)
+    pub fn is_irreversible(&self) -> bool {
+        let outcome_permanent = matches!(
+            self.outcome,
+            EventOutcome::UnlockContent(_) | EventOutcome::Special(_)
+        );
+        let cost_permanent = matches!(
+            self.cost,
+            Some(EventCost::Credits(_)) | Some(EventCost::Reputation(_))
+        );
+        outcome_permanent || cost_permanent
+    }
+}
+
 /// Event outcomes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EventOutcome {
@@ -537,6 +731,104 @@ mod tests {
         assert!(event.is_some());
         assert_eq!(manager.active_events.len(), 1);
         assert_eq!(manager.event_history.len(), 1);
+        
+        assert_eq!(manager.take_new_events().len(), 1);
+        assert!(manager.take_new_events().is_empty());
+    }
+
+    #[test]
+    fn test_disabled_manager_never_generates() {
+        let mut manager = EventManager::with_enabled(false);
+        manager.event_chance = 1.0;
+        assert!(manager.generate_event(90.0, 0).is_none());
+        assert!(manager.take_new_events().is_empty());
+
+        manager.enabled = true;
+        assert!(manager.generate_event(90.0, 0).is_some());
+    }
+
+    #[test]
+    fn test_events_respect_cooldown() {
+        let mut manager = EventManager::new();
+        manager.event_chance = 1.0;
+        assert!(manager.generate_event(50.0, 0).is_some());
+        assert!(manager.generate_event(50.0, 0).is_none());
+
+        manager.last_event_time = Some(chrono::Utc::now() - chrono::Duration::seconds(31));
+        assert!(manager.generate_event(50.0, 0).is_some());
+
+        manager.cooldown = std::time::Duration::ZERO;
+        assert!(manager.generate_event(50.0, 0).is_some());
+    }
+
+    #[test]
+    fn test_custom_events_join_their_pool() {
+        let mut manager = EventManager::new();
+        let mut custom = manager.generate_threat_event();
+        custom.id = "custom_threat".to_string();
+        manager.custom_events.push((EventPool::Threat, custom));
+
+        let picked: Vec<String> = (0..200).map(|_| manager.generate_threat_event().id).collect();
+        assert!(picked.iter().any(|id| id == "custom_threat"));
+        assert!((0..50).all(|_| manager.generate_opportunity_event().id != "custom_threat"));
+    }
+
+    #[tokio::test]
+    async fn test_choices_are_logged() {
+        let mut manager = EventManager::new();
+        manager.autosave_before_irreversible = false;
+        let event = manager.generate_opportunity_event();
+        manager.active_events.push(event.clone());
+        let mut state = crate::game::GameState::new("test".to_string(), 0);
+        state.credits = 0;
+
+        // Logged even when the choice can't be paid for
+        let last = event.choices.len() - 1;
+        manager.handle_choice(&event.id, last, &mut state, &ColorScheme::new()).await.unwrap();
+        let record = &state.event_log[0];
+        assert_eq!(record.id, event.id);
+        assert_eq!(record.choice, Some((last, event.choices[last].label.clone())));
+    }
+
+    #[test]
+    fn test_weights_favor_milder_events() {
+        let mut manager = EventManager::new();
+        let picks: Vec<String> = (0..600).map(|_| manager.generate_threat_event().id).collect();
+        let count = |id: &str| picks.iter().filter(|p| *p == id).count();
+        // Medium 3 : High 2 : Critical 1
+        assert!(count("rival_hacker") > count("ai_defense"));
+
+        let mut never = manager.generate_threat_event();
+        never.id = "never".to_string();
+        never.weight = Some(0);
+        manager.custom_events.push((EventPool::Threat, never));
+        assert!((0..200).all(|_| manager.generate_threat_event().id != "never"));
+    }
+
+    #[test]
+    fn test_choice_keys_are_validated() {
+        let event = EventManager::new().generate_threat_event();
+        let count = event.choices.len();
+
+        assert_eq!(event.choice_for_key('1'), Some(0));
+        assert_eq!(event.choice_for_key(char::from_digit(count as u32, 10).unwrap()), Some(count - 1));
+        assert_eq!(event.choice_for_key('0'), None);
+        assert_eq!(event.choice_for_key(char::from_digit(count as u32 + 1, 10).unwrap()), None);
+        assert_eq!(event.choice_for_key('x'), None);
+    }
+
+    #[test]
+    fn test_timeout_raises_heat() {
+        let mut manager = EventManager::new();
+        manager.event_chance = 1.0;
+        let event = manager.generate_event(0.0, 0).unwrap();
+        let mut state = crate::game::GameState::new("test".to_string(), 0);
+
+        manager.handle_timeout(&event.id, &mut state, &ColorScheme::new()).unwrap();
+        assert_eq!(state.heat_level, EVENT_TIMEOUT_HEAT);
+        assert!(manager.active_events.is_empty());
+        assert_eq!(state.event_log.len(), 1);
+        assert!(state.event_log[0].choice.is_none());
     }
 
     #[test]
@@ -555,9 +847,33 @@ mod tests {
                 },
             ],
             time_limit: None,
+            weight: None,
         };
         
         assert_eq!(event.id, "test_event");
         assert_eq!(event.choices.len(), 1);
     }
+
+    #[test]
+    fn test_irreversible_choices() {
+        let ai_battle = EventChoice {
+            label: "Fight".to_string(),
+            outcome: EventOutcome::Special("ai_battle".to_string()),
+            cost: None,
+        };
+        let paid = EventChoice {
+            label: "Pay".to_string(),
+            outcome: EventOutcome::ReduceHeat(30.0),
+            cost: Some(EventCost::Credits(100)),
+        };
+        let trivial = EventChoice {
+            label: "Risk it".to_string(),
+            outcome: EventOutcome::IncreaseHeat(20.0),
+            cost: Some(EventCost::Heat(5.0)),
+        };
+
+        assert!(ai_battle.is_irreversible());
+        assert!(paid.is_irreversible());
+        assert!(!trivial.is_irreversible());
+    }
 }
\ No newline at end of file
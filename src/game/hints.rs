@@ -0,0 +1,166 @@
+//! Contextual hint system for CRIMSON-REDLINE
+
+use crate::commands::darkweb;
+use crate::game::{GameState, ReputationManager};
+
+/// A mapped host with open ports, when the agent hasn't landed a hack yet
+fn first_exploit_target(state: &GameState) -> Option<&str> {
+    if state.successful_hacks > 0 {
+        return None;
+    }
+    state.network_map.discovered_nodes.iter()
+        .find(|node| !node.open_ports.is_empty() && !node.is_compromised)
+        .map(|node| node.ip.as_str())
+}
+
+/// Whether the zero-day kit is for sale to this agent, affordable and not yet owned
+fn zero_day_within_reach(state: &GameState) -> bool {
+    darkweb::find_item("zeroday").is_some_and(|item| {
+        item.is_available(state.reputation) && state.credits >= item.price && !state.has_tool(item.tool)
+    })
+}
+
+/// A single hint rule: when `applies` matches, `message` is suggested
+pub struct HintRule {
+    pub id: &'static str,
+    pub applies: fn(&GameState, &ReputationManager) -> bool,
+    pub message: fn(&GameState, &ReputationManager) -> String,
+}
+
+/// Hint rules in priority order - the first matching rule wins
+pub const HINT_RULES: &[HintRule] = &[
+    HintRule {
+        id: "heat_critical",
+        applies: |state, _| state.heat_level >= 75.0,
+        message: |state, _| format!(
+            "Heat is critical ({:.0}%) - stop attacking and lay low before you get traced",
+            state.heat_level
+        ),
+    },
+    HintRule {
+        id: "heat_rising",
+        applies: |state, _| state.heat_level >= 50.0,
+        message: |_, _| "Heat is rising - quieter commands like `decrypt` keep you off the radar".to_string(),
+    },
+    HintRule {
+        id: "first_scan",
+        applies: |state, _| state.total_scans == 0,
+        message: |_, _| "You haven't mapped anything yet - try `scan` to find targets".to_string(),
+    },
+    HintRule {
+        id: "active_mission",
+        applies: |state, _| !state.active_missions.is_empty(),
+        message: |state, _| format!(
+            "You have an active mission ({}) - check `mission` for its objectives",
+            state.active_missions[0].id
+        ),
+    },
+    HintRule {
+        id: "first_exploit",
+        applies: |state, _| first_exploit_target(state).is_some(),
+        message: |state, _| format!(
+            "Your scans found open ports on {0} - try `exploit {0}`",
+            first_exploit_target(state).unwrap_or("<ip>")
+        ),
+    },
+    HintRule {
+        id: "afford_zero_day",
+        applies: |state, _| zero_day_within_reach(state),
+        message: |state, _| format!(
+            "You have {} credits - you can afford a zero-day on the `darkweb`",
+            state.credits
+        ),
+    },
+    HintRule {
+        id: "first_decrypt",
+        applies: |state, _| state.files_decrypted == 0,
+        message: |_, _| "Intercepted data is worth reputation - try `decrypt`".to_string(),
+    },
+    HintRule {
+        id: "near_level_up",
+        applies: |_, rep| rep.reputation_to_next_level().is_some_and(|r| r > 0 && r <= 25),
+        message: |_, rep| format!(
+            "Only {} reputation to the next rank - one more successful hack should do it",
+            rep.reputation_to_next_level().unwrap_or(0)
+        ),
+    },
+];
+
+/// Suggest the next useful action for the current game state
+pub fn suggest_hint(state: &GameState, reputation: &ReputationManager) -> Option<String> {
+    HINT_RULES
+        .iter()
+        .find(|rule| (rule.applies)(state, reputation))
+        .map(|rule| (rule.message)(state, reputation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn veteran_state() -> GameState {
+        let mut state = GameState::new("test".to_string(), 0);
+        state.total_scans = 3;
+        state.successful_hacks = 2;
+        state.files_decrypted = 1;
+        state.credits = 0;
+        state
+    }
+
+    #[test]
+    fn test_new_player_is_told_to_scan() {
+        let state = GameState::new("test".to_string(), 0);
+        let hint = suggest_hint(&state, &ReputationManager::new(0)).unwrap();
+        assert!(hint.contains("scan"));
+    }
+
+    #[test]
+    fn test_high_heat_takes_priority() {
+        let mut state = GameState::new("test".to_string(), 0);
+        state.heat_level = 90.0;
+        let hint = suggest_hint(&state, &ReputationManager::new(0)).unwrap();
+        assert!(hint.contains("Heat is critical"));
+    }
+
+    #[test]
+    fn test_rich_player_is_pointed_at_darkweb() {
+        let mut state = veteran_state();
+        state.credits = 1500;
+        state.reputation = crate::game::ReputationLevel::Expert.reputation_requirement();
+        let hint = suggest_hint(&state, &ReputationManager::new(0)).unwrap();
+        assert!(hint.contains("darkweb"));
+    }
+
+    #[test]
+    fn test_zero_day_hint_waits_for_expert_rank() {
+        let mut state = veteran_state();
+        state.credits = 1500;
+        assert!(suggest_hint(&state, &ReputationManager::new(0)).is_none());
+    }
+
+    #[test]
+    fn test_exploit_hint_needs_a_host_with_open_ports() {
+        let mut state = veteran_state();
+        state.successful_hacks = 0;
+        assert!(suggest_hint(&state, &ReputationManager::new(0)).is_none());
+
+        let device = crate::commands::scanner::Device {
+            ip: "10.0.0.5".to_string(),
+            hostname: "SRV-001".to_string(),
+            mac: "00:11:22:33:44:55".to_string(),
+            os: "Ubuntu 22.04".to_string(),
+            open_ports: vec![22],
+            vulnerabilities: Vec::new(),
+            services: Vec::new(),
+        };
+        state.network_map.add_node(device.to_network_node());
+        let hint = suggest_hint(&state, &ReputationManager::new(0)).unwrap();
+        assert!(hint.contains("exploit 10.0.0.5"));
+    }
+
+    #[test]
+    fn test_no_hint_when_nothing_applies() {
+        let state = veteran_state();
+        assert!(suggest_hint(&state, &ReputationManager::new(0)).is_none());
+    }
+}
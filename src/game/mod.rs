@@ -1,23 +1,58 @@
 //! Game state and mechanics for CRIMSON-REDLINE
 
 pub mod state;
+pub mod achievements;
 pub mod reputation;
 pub mod events;
+pub mod event_pack;
+pub mod difficulty;
+pub mod hints;
+pub mod ticker;
+pub mod autosave;
 
 pub use state::{GameState, PlayerStats};
-pub use reputation::{ReputationLevel, ReputationManager};
+pub use reputation::{ReputationFormula, ReputationLevel, ReputationManager};
 pub use events::{RandomEvent, EventManager};
+pub use hints::suggest_hint;
+pub use achievements::UnlockedAchievement;
+pub use ticker::{SharedGameState, SharedEventManager};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Reserved save slot written right before irreversible event outcomes
+pub const PRE_EVENT_SLOT: &str = "pre-event";
+
+/// Save slot holding an agent's ongoing game between sessions
+pub const MAIN_SLOT: &str = "current";
+
+/// Marks save files that start with a schema header
+const SAVE_MAGIC: &[u8; 4] = b"CRSV";
+
+/// Layout of `SaveGame` on disk; bump when `GameState` changes shape and add a migration
+pub const SAVE_SCHEMA_VERSION: u32 = 2;
 
 /// Save game data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveGame {
     pub game_state: GameState,
     pub timestamp: chrono::DateTime<chrono::Utc>,
-    pub version: String,
+}
+
+/// `SaveGame` as written up to schema 1, when it also carried the app version.
+/// Schema 0 is the same layout without a header
+#[derive(Debug, Serialize, Deserialize)]
+struct SaveGameV1 {
+    game_state: GameState,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    version: String,
+}
+
+impl From<SaveGameV1> for SaveGame {
+    fn from(old: SaveGameV1) -> Self {
+        SaveGame { game_state: old.game_state, timestamp: old.timestamp }
+    }
 }
 
 impl SaveGame {
@@ -26,29 +61,17 @@ impl SaveGame {
         SaveGame {
             game_state,
             timestamp: chrono::Utc::now(),
-            version: crate::APP_VERSION.to_string(),
         }
     }
 
     /// Save to file
     pub fn save(&self) -> Result<()> {
-        let save_path = get_save_path()?;
-        let data = bincode::serialize(self)?;
-        std::fs::write(save_path, data)?;
-        Ok(())
+        self.save_to(&get_save_path()?)
     }
 
     /// Load from file
     pub fn load() -> Result<Option<SaveGame>> {
-        let save_path = get_save_path()?;
-        
-        if !save_path.exists() {
-            return Ok(None);
-        }
-
-        let data = std::fs::read(save_path)?;
-        let save_game: SaveGame = bincode::deserialize(&data)?;
-        Ok(Some(save_game))
+        Self::load_from(&get_save_path()?)
     }
 
     /// Delete save file
@@ -59,6 +82,145 @@ impl SaveGame {
         }
         Ok(())
     }
+
+    /// Save this agent's ongoing game
+    pub fn save_for_user(&self) -> Result<()> {
+        self.save_slot(MAIN_SLOT)
+    }
+
+    /// Load an agent's ongoing game, if they have one
+    pub fn load_for_user(username: &str) -> Result<Option<SaveGame>> {
+        Self::load_slot(username, MAIN_SLOT)
+    }
+
+    /// Save to a named slot for this agent
+    pub fn save_slot(&self, slot: &str) -> Result<()> {
+        let slot_path = get_slot_path(&self.game_state.username, slot)?;
+        self.save_to(&slot_path)
+    }
+
+    /// Load a named slot for an agent
+    pub fn load_slot(username: &str, slot: &str) -> Result<Option<SaveGame>> {
+        let slot_path = get_slot_path(username, slot)?;
+        Self::load_from(&slot_path)
+    }
+
+    /// Move every save slot from one agent's name to another's
+    pub fn move_user_saves(old_username: &str, new_username: &str) -> Result<()> {
+        move_save_dir(&get_user_save_dir(old_username)?, &get_user_save_dir(new_username)?, old_username, new_username)
+    }
+
+    /// Delete every save slot an agent has
+    pub fn delete_user_saves(username: &str) -> Result<()> {
+        let dir = get_user_save_dir(username)?;
+        if dir.exists() {
+            std::fs::remove_dir_all(dir)?;
+        }
+        Ok(())
+    }
+
+    /// Delete an agent's saves and command history, so whoever takes the name next starts clean
+    pub fn delete_agent_files(username: &str) -> Result<()> {
+        Self::delete_user_saves(username)?;
+        crate::utils::history::CommandHistory::delete_for_user(username)
+    }
+
+    /// Save to a specific file, refusing to overwrite a save from a newer build
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if let Some(schema) = std::fs::read(path).ok().and_then(|data| schema_of(&data)) {
+            if schema > SAVE_SCHEMA_VERSION {
+                anyhow::bail!("{} is from a newer build (save schema {}); not overwriting it", path.display(), schema);
+            }
+        }
+        let mut data = SAVE_MAGIC.to_vec();
+        data.extend_from_slice(&SAVE_SCHEMA_VERSION.to_le_bytes());
+        data.extend(bincode::serialize(self)?);
+        
+        // Write atomically so a crash mid-write can't corrupt the existing save
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, data)?;
+        std::fs::rename(temp_path, path)?;
+        Ok(())
+    }
+
+    /// Load from a specific file
+    ///
+    /// A save that can't be read is moved aside as a backup and `None` is
+    /// returned, so the agent starts fresh instead of failing to launch. A save
+    /// from a newer build is an error and stays where it is.
+    pub fn load_from(path: &Path) -> Result<Option<SaveGame>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let data = std::fs::read(path)?;
+        if let Some(schema) = schema_of(&data).filter(|schema| *schema > SAVE_SCHEMA_VERSION) {
+            anyhow::bail!(
+                "{} uses save schema {}, newer than this build supports ({})",
+                path.display(),
+                schema,
+                SAVE_SCHEMA_VERSION
+            );
+        }
+
+        match Self::decode(&data) {
+            Ok(save_game) => Ok(Some(save_game)),
+            Err(e) => {
+                let backup = path.with_extension(format!("bak-{}", chrono::Utc::now().format("%Y%m%d%H%M%S")));
+                std::fs::rename(path, &backup)?;
+                eprintln!(
+                    "Warning: save {} is unreadable ({}); moved to {} and starting fresh",
+                    path.display(),
+                    e,
+                    backup.display()
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Decode a save file, migrating older schemas to the current one
+    fn decode(data: &[u8]) -> Result<SaveGame> {
+        let body = &data[data.len().min(SAVE_MAGIC.len() + 4)..];
+        match schema_of(data) {
+            Some(SAVE_SCHEMA_VERSION) => Ok(bincode::deserialize(body)?),
+            Some(1) => Ok(bincode::deserialize::<SaveGameV1>(body)?.into()),
+            Some(older) => anyhow::bail!("no migration from save schema {}", older),
+            // Saves from before the header was added
+            None => bincode::deserialize::<SaveGameV1>(data)
+                .map(SaveGame::from)
+                .map_err(|e| anyhow::anyhow!("not a save file ({})", e)),
+        }
+    }
+}
+
+/// Move the save directory `from` for `old_username` to `to` for `new_username`
+fn move_save_dir(from: &Path, to: &Path, old_username: &str, new_username: &str) -> Result<()> {
+    if !from.exists() || from == to {
+        return Ok(());
+    }
+    if crate::auth::storage::canonical_username(old_username) == crate::auth::storage::canonical_username(new_username) {
+        // A case-only rename: on a case-insensitive filesystem `to` is `from` itself,
+        // so step through a temporary name rather than mistake it for someone else's saves
+        let temp = from.with_extension("renaming");
+        std::fs::rename(from, &temp)?;
+        std::fs::rename(temp, to)?;
+        return Ok(());
+    }
+    if to.exists() && std::fs::read_dir(to)?.next().is_some() {
+        anyhow::bail!("saves already exist for '{}'", new_username);
+    }
+    std::fs::rename(from, to)?;
+    Ok(())
+}
+
+/// Schema number from a save file's header, if it has one
+fn schema_of(data: &[u8]) -> Option<u32> {
+    let header = data.strip_prefix(SAVE_MAGIC.as_slice())?.get(..4)?;
+    Some(u32::from_le_bytes(header.try_into().ok()?))
 }
 
 /// Get save game file path
@@ -67,6 +229,17 @@ fn get_save_path() -> Result<PathBuf> {
     Ok(data_dir.join(crate::GAME_STATE_FILE))
 }
 
+/// Get the directory holding an agent's save slots
+fn get_user_save_dir(username: &str) -> Result<PathBuf> {
+    let data_dir = crate::utils::get_data_dir()?;
+    Ok(data_dir.join("saves").join(username))
+}
+
+/// Get file path for a named save slot
+fn get_slot_path(username: &str, slot: &str) -> Result<PathBuf> {
+    Ok(get_user_save_dir(username)?.join(format!("{}.sav", slot)))
+}
+
 /// Mission structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Mission {
@@ -80,6 +253,9 @@ pub struct Mission {
     pub time_limit: Option<std::time::Duration>,
     pub is_completed: bool,
     pub is_active: bool,
+    /// When the mission was accepted, which starts its time limit
+    #[serde(default)]
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Mission objective
@@ -91,6 +267,29 @@ pub struct Objective {
     pub progress: u32,
     pub required: u32,
     pub is_completed: bool,
+    /// Player activity that advances this objective; `None` for story-only objectives
+    #[serde(default)]
+    pub kind: Option<ObjectiveKind>,
+    /// Targets already counted towards a `ScanTarget` objective
+    #[serde(default)]
+    pub seen_targets: Vec<String>,
+}
+
+/// Player activity an objective tracks
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ObjectiveKind {
+    Scan,
+    /// Each target scanned that this objective hasn't counted yet
+    ScanTarget,
+    /// Each vulnerability a scan turns up
+    Vulnerability,
+    Decrypt,
+    Exploit,
+    FirewallBypass,
+    Inject,
+    Spoof,
+    /// Held while heat stays below the given percentage
+    HeatBelow(u8),
 }
 
 /// Mission difficulty levels
@@ -124,6 +323,7 @@ impl Mission {
             time_limit: None,
             is_completed: false,
             is_active: false,
+            started_at: None,
         }
     }
 
@@ -137,10 +337,68 @@ impl Mission {
             progress: 0,
             required,
             is_completed: false,
+            kind: None,
+            seen_targets: Vec::new(),
         });
         self
     }
 
+    /// Add an objective that advances on its own as the player acts
+    pub fn add_tracked_objective(&mut self, description: String, required: u32, kind: ObjectiveKind) -> &mut Self {
+        self.add_objective(description, required);
+        if let Some(objective) = self.objectives.last_mut() {
+            objective.kind = Some(kind);
+        }
+        self
+    }
+
+    /// Advance every objective tracking `kind`
+    pub fn advance(&mut self, kind: ObjectiveKind, amount: u32) {
+        let ids: Vec<String> = self.objectives.iter()
+            .filter(|o| o.kind == Some(kind) && !o.is_completed)
+            .map(|o| o.id.clone())
+            .collect();
+        for id in ids {
+            self.update_objective(&id, amount);
+        }
+    }
+
+    /// Advance objectives tracking `kind` that haven't counted this target yet
+    pub fn advance_target(&mut self, kind: ObjectiveKind, target: &str) {
+        let mut ids = Vec::new();
+        for objective in &mut self.objectives {
+            if objective.kind == Some(kind) && !objective.is_completed && !objective.seen_targets.iter().any(|t| t == target) {
+                objective.seen_targets.push(target.to_string());
+                ids.push(objective.id.clone());
+            }
+        }
+        for id in ids {
+            self.update_objective(&id, 1);
+        }
+    }
+
+    /// Time left before the mission expires, if it is timed and has been accepted
+    pub fn time_remaining(&self, now: chrono::DateTime<chrono::Utc>) -> Option<std::time::Duration> {
+        let deadline = self.started_at? + chrono::Duration::from_std(self.time_limit?).ok()?;
+        Some((deadline - now).to_std().unwrap_or_default())
+    }
+
+    /// Whether an accepted, timed mission has run past its limit
+    pub fn is_expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.time_remaining(now) == Some(std::time::Duration::ZERO)
+    }
+
+    /// Re-evaluate heat conditions; they only count while they hold
+    pub fn refresh_conditions(&mut self, heat_level: f32) {
+        for objective in &mut self.objectives {
+            if let Some(ObjectiveKind::HeatBelow(limit)) = objective.kind {
+                objective.is_completed = heat_level < f32::from(limit);
+                objective.progress = if objective.is_completed { objective.required } else { 0 };
+            }
+        }
+        self.is_completed = self.objectives.iter().all(|o| o.is_completed);
+    }
+
     /// Update objective progress
     pub fn update_objective(&mut self, objective_id: &str, progress: u32) {
         if let Some(obj) = self.objectives.iter_mut().find(|o| o.id == objective_id) {
@@ -169,7 +427,7 @@ impl Mission {
             return 0.0;
         }
 
-        (total_progress as f32 / total_required as f32) * 100.0
+        total_progress as f32 * 100.0 / total_required as f32
     }
 }
 
@@ -196,6 +454,19 @@ pub enum AchievementRarity {
     Legendary,
 }
 
+impl AchievementRarity {
+    /// Display color for the rarity tier
+    pub fn color_rgb(&self) -> (u8, u8, u8) {
+        match self {
+            AchievementRarity::Common => (200, 200, 200),
+            AchievementRarity::Uncommon => (50, 205, 50),
+            AchievementRarity::Rare => (30, 144, 255),
+            AchievementRarity::Epic => (186, 85, 211),
+            AchievementRarity::Legendary => (255, 165, 0),
+        }
+    }
+}
+
 impl Achievement {
     /// Create a new achievement
     pub fn new(id: String, name: String, description: String, rarity: AchievementRarity) -> Self {
@@ -228,6 +499,11 @@ impl Achievement {
     }
 }
 
+/// Look up a mission from the catalogue by id, ignoring case
+pub fn find_mission(id: &str) -> Option<Mission> {
+    generate_missions().into_iter().find(|m| m.id.eq_ignore_ascii_case(id))
+}
+
 /// Generate default missions
 pub fn generate_missions() -> Vec<Mission> {
     let mut missions = Vec::new();
@@ -240,8 +516,8 @@ pub fn generate_missions() -> Vec<Mission> {
         MissionDifficulty::Trivial,
         10,
     );
-    mission.add_objective("Scan a network".to_string(), 1);
-    mission.add_objective("Decrypt a file".to_string(), 1);
+    mission.add_tracked_objective("Scan a network".to_string(), 1, ObjectiveKind::Scan);
+    mission.add_tracked_objective("Decrypt a file".to_string(), 1, ObjectiveKind::Decrypt);
     missions.push(mission);
 
     // Mission 2: Network Reconnaissance
@@ -252,8 +528,8 @@ pub fn generate_missions() -> Vec<Mission> {
         MissionDifficulty::Easy,
         25,
     );
-    mission.add_objective("Scan 5 different targets".to_string(), 5);
-    mission.add_objective("Identify 3 vulnerabilities".to_string(), 3);
+    mission.add_tracked_objective("Scan 5 different targets".to_string(), 5, ObjectiveKind::ScanTarget);
+    mission.add_tracked_objective("Identify 3 vulnerabilities".to_string(), 3, ObjectiveKind::Vulnerability);
     missions.push(mission);
 
     // Mission 3: Data Extraction
@@ -264,9 +540,9 @@ pub fn generate_missions() -> Vec<Mission> {
         MissionDifficulty::Medium,
         50,
     );
-    mission.add_objective("Exploit a vulnerability".to_string(), 1);
-    mission.add_objective("Decrypt 3 files".to_string(), 3);
-    mission.add_objective("Maintain heat level below 50%".to_string(), 1);
+    mission.add_tracked_objective("Exploit a vulnerability".to_string(), 1, ObjectiveKind::Exploit);
+    mission.add_tracked_objective("Decrypt 3 files".to_string(), 3, ObjectiveKind::Decrypt);
+    mission.add_tracked_objective("Maintain heat level below 50%".to_string(), 1, ObjectiveKind::HeatBelow(50));
     missions.push(mission);
 
     // Mission 4: Corporate Espionage
@@ -277,9 +553,9 @@ pub fn generate_missions() -> Vec<Mission> {
         MissionDifficulty::Hard,
         100,
     );
-    mission.add_objective("Bypass 2 firewalls".to_string(), 2);
-    mission.add_objective("Successfully exploit 3 systems".to_string(), 3);
-    mission.add_objective("Extract database".to_string(), 1);
+    mission.add_tracked_objective("Bypass 2 firewalls".to_string(), 2, ObjectiveKind::FirewallBypass);
+    mission.add_tracked_objective("Successfully exploit 3 systems".to_string(), 3, ObjectiveKind::Exploit);
+    mission.add_tracked_objective("Extract database".to_string(), 1, ObjectiveKind::Inject);
     mission.time_limit = Some(std::time::Duration::from_secs(600)); // 10 minutes
     missions.push(mission);
 
@@ -291,9 +567,9 @@ pub fn generate_missions() -> Vec<Mission> {
         MissionDifficulty::Extreme,
         200,
     );
-    mission.add_objective("Complete 5 hacks".to_string(), 5);
-    mission.add_objective("Keep heat level at 0%".to_string(), 1);
-    mission.add_objective("Leave no traces".to_string(), 1);
+    mission.add_tracked_objective("Complete 5 hacks".to_string(), 5, ObjectiveKind::Exploit);
+    mission.add_tracked_objective("Keep heat level at 0%".to_string(), 1, ObjectiveKind::HeatBelow(1));
+    mission.add_tracked_objective("Leave no traces".to_string(), 1, ObjectiveKind::Spoof);
     missions.push(mission);
 
     // Mission 6: The Impossible
@@ -383,6 +659,99 @@ pub fn generate_achievements() -> Vec<Achievement> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_save_slot_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("saves").join("agent").join("pre-event.sav");
+
+        let mut state = GameState::new("agent".to_string(), 0);
+        state.add_credits(250);
+        SaveGame::new(state).save_to(&path).unwrap();
+
+        let loaded = SaveGame::load_from(&path).unwrap().unwrap();
+        assert_eq!(loaded.game_state.username, "agent");
+        assert_eq!(loaded.game_state.credits, 1250);
+        assert!(SaveGame::load_from(&dir.path().join("missing.sav")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unreadable_save_is_backed_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("current.sav");
+
+        let mut truncated = SAVE_MAGIC.to_vec();
+        truncated.extend_from_slice(&SAVE_SCHEMA_VERSION.to_le_bytes());
+        for data in [b"garbage".to_vec(), truncated] {
+            std::fs::write(&path, &data).unwrap();
+            assert!(SaveGame::load_from(&path).unwrap().is_none());
+            assert!(!path.exists());
+
+            let backups: Vec<_> = std::fs::read_dir(dir.path()).unwrap().flatten().collect();
+            assert!(backups.iter().any(|entry| std::fs::read(entry.path()).unwrap() == data));
+            for entry in backups {
+                std::fs::remove_file(entry.path()).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_saves_follow_a_rename() {
+        let dir = tempfile::tempdir().unwrap();
+        let slot = |name: &str| dir.path().join(name).join("current.sav");
+        SaveGame::new(GameState::new("ghost".to_string(), 0)).save_to(&slot("ghost")).unwrap();
+        SaveGame::new(GameState::new("taken".to_string(), 0)).save_to(&slot("taken")).unwrap();
+
+        let move_dir = |old: &str, new: &str| move_save_dir(&dir.path().join(old), &dir.path().join(new), old, new);
+        assert!(move_dir("ghost", "taken").is_err());
+        move_dir("ghost", "Ghost").unwrap();
+        assert!(slot("Ghost").exists());
+        assert!(!dir.path().join("ghost.renaming").exists());
+        move_dir("Ghost", "spectre").unwrap();
+        assert!(slot("spectre").exists() && !slot("Ghost").exists());
+    }
+
+    #[test]
+    fn test_older_saves_are_migrated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("current.sav");
+        let old = SaveGameV1 {
+            game_state: GameState::new("veteran".to_string(), 300),
+            timestamp: chrono::Utc::now(),
+            version: "1.0.0".to_string(),
+        };
+        let headerless = bincode::serialize(&old).unwrap();
+        let mut schema_1 = SAVE_MAGIC.to_vec();
+        schema_1.extend_from_slice(&1u32.to_le_bytes());
+        schema_1.extend_from_slice(&headerless);
+
+        for data in [headerless, schema_1] {
+            std::fs::write(&path, data).unwrap();
+            let loaded = SaveGame::load_from(&path).unwrap().unwrap();
+            assert_eq!(loaded.game_state.username, "veteran");
+            assert_eq!(loaded.game_state.reputation, 300);
+            assert_eq!(loaded.timestamp, old.timestamp);
+        }
+
+        // Saving again writes the current schema
+        SaveGame::load_from(&path).unwrap().unwrap().save_to(&path).unwrap();
+        assert_eq!(schema_of(&std::fs::read(&path).unwrap()), Some(SAVE_SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn test_newer_save_is_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("current.sav");
+
+        let mut newer = SAVE_MAGIC.to_vec();
+        newer.extend_from_slice(&(SAVE_SCHEMA_VERSION + 1).to_le_bytes());
+        std::fs::write(&path, &newer).unwrap();
+
+        let err = SaveGame::load_from(&path).unwrap_err();
+        assert!(err.to_string().contains("newer than this build"));
+        assert!(SaveGame::new(GameState::new("agent".to_string(), 0)).save_to(&path).is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), newer);
+    }
+
     #[test]
     fn test_mission_creation() {
         let mut mission = Mission::new(
@@ -405,6 +774,43 @@ mod tests {
         assert!(mission.is_completed);
     }
 
+    #[test]
+    fn test_tracked_objectives_and_conditions() {
+        let mut mission = find_mission("data-001").unwrap();
+        mission.advance(ObjectiveKind::Exploit, 1);
+        mission.advance(ObjectiveKind::Decrypt, 5);
+        assert_eq!(mission.objectives[1].progress, 5);
+        mission.refresh_conditions(80.0);
+        assert!(!mission.is_completed);
+
+        mission.refresh_conditions(20.0);
+        assert!(mission.is_completed);
+        assert_eq!(mission.get_completion_percentage(), 100.0);
+    }
+
+    #[test]
+    fn test_scan_targets_count_once_each() {
+        let mut mission = find_mission("recon-001").unwrap();
+        for target in ["10.0.0.1", "10.0.0.1", "network", "10.0.0.2", "network"] {
+            mission.advance_target(ObjectiveKind::ScanTarget, target);
+        }
+        assert_eq!(mission.objectives[0].progress, 3);
+        assert!(!mission.objectives[0].is_completed);
+    }
+
+    #[test]
+    fn test_timed_missions_expire() {
+        let now = chrono::Utc::now();
+        let mut mission = find_mission("corp-001").unwrap();
+        assert_eq!(mission.time_remaining(now), None);
+
+        mission.started_at = Some(now);
+        assert_eq!(mission.time_remaining(now + chrono::Duration::minutes(4)), Some(std::time::Duration::from_secs(360)));
+        assert!(!mission.is_expired(now + chrono::Duration::minutes(4)));
+        assert!(mission.is_expired(now + chrono::Duration::minutes(11)));
+        assert!(!find_mission("init-001").unwrap().is_expired(now));
+    }
+
     #[test]
     fn test_achievement_unlock() {
         let mut achievement = Achievement::new(
@@ -426,6 +832,6 @@ mod tests {
         let save_game = SaveGame::new(game_state.clone());
         
         assert_eq!(save_game.game_state.username, "testuser");
-        assert_eq!(save_game.version, crate::APP_VERSION);
+        assert_eq!(save_game.game_state.reputation, 100);
     }
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
 //! Reputation system for CRIMSON-REDLINE
 
 use serde::{Deserialize, Serialize};
-use anyhow::Result;
+
+/// Seconds allowed between actions before a streak is lost
+pub const STREAK_WINDOW_SECS: i64 = 300;
 
 /// Reputation manager
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +48,12 @@ impl ReputationManager {
         final_amount
     }
 
+    /// Match reputation changed outside the manager, e.g. restored from the account, keeping the streak
+    pub fn sync(&mut self, reputation: i32) {
+        self.current_reputation = reputation;
+        self.level = ReputationLevel::from_reputation(reputation);
+    }
+
     /// Remove reputation (penalties)
     pub fn remove_reputation(&mut self, amount: i32) {
         self.current_reputation = (self.current_reputation - amount).max(0);
@@ -57,25 +65,26 @@ impl ReputationManager {
     fn update_streak(&mut self) {
         let now = chrono::Utc::now();
         
-        if let Some(last) = self.last_action {
-            let duration = now - last;
+        // First action or action within 5 minutes extends the streak
+        let continues = self
+            .last_action
+            .map(|last| (now - last).num_seconds() < STREAK_WINDOW_SECS)
+            .unwrap_or(true);
+
+        if continues {
+            self.streak += 1;
             
-            // If action within 5 minutes, increase streak
-            if duration.num_seconds() < 300 {
-                self.streak += 1;
-                
-                // Update multiplier based on streak
-                self.multiplier = match self.streak {
-                    0..=4 => 1.0,
-                    5..=9 => 1.1,
-                    10..=19 => 1.25,
-                    20..=29 => 1.5,
-                    30..=49 => 1.75,
-                    _ => 2.0,
-                };
-            } else {
-                self.reset_streak();
-            }
+            // Update multiplier based on streak
+            self.multiplier = match self.streak {
+                0..=4 => 1.0,
+                5..=9 => 1.1,
+                10..=19 => 1.25,
+                20..=29 => 1.5,
+                30..=49 => 1.75,
+                _ => 2.0,
+            };
+        } else {
+            self.reset_streak();
         }
         
         self.last_action = Some(now);
@@ -99,6 +108,15 @@ impl ReputationManager {
         }
     }
 
+    /// Seconds left before the streak lapses, if there is one
+    pub fn streak_seconds_remaining(&self) -> Option<i64> {
+        if self.streak == 0 {
+            return None;
+        }
+        let elapsed = (chrono::Utc::now() - self.last_action?).num_seconds();
+        Some((STREAK_WINDOW_SECS - elapsed).max(0))
+    }
+
     /// Get reputation needed for next level
     pub fn reputation_to_next_level(&self) -> Option<i32> {
         self.level.next_level_requirement().map(|req| req - self.current_reputation)
@@ -112,7 +130,7 @@ impl ReputationManager {
         let progress = self.current_reputation - current_req;
         let total = next_req - current_req;
         
-        (progress as f32 / total as f32 * 100.0).min(100.0).max(0.0)
+        (progress as f32 / total as f32 * 100.0).clamp(0.0, 100.0)
     }
 }
 
@@ -218,6 +236,60 @@ impl ReputationLevel {
         }
     }
 
+    /// Whether this level ranks above another
+    pub fn outranks(&self, other: &ReputationLevel) -> bool {
+        self.reputation_requirement() > other.reputation_requirement()
+    }
+
+    /// Reputation bonus perk, as a fraction of each gain
+    pub fn reputation_bonus(&self) -> f32 {
+        match self {
+            ReputationLevel::Nobody => 0.0,
+            ReputationLevel::Wannabe => 0.05,
+            ReputationLevel::ScriptKiddie => 0.10,
+            ReputationLevel::Amateur => 0.15,
+            ReputationLevel::Competent => 0.20,
+            ReputationLevel::Skilled => 0.25,
+            ReputationLevel::Expert => 0.30,
+            ReputationLevel::Master => 0.40,
+            ReputationLevel::Elite => 0.50,
+            ReputationLevel::Legendary => 0.75,
+            ReputationLevel::Mythical => 1.0,
+        }
+    }
+
+    /// Faster heat decay perk, as a fraction; perks carry over until a higher tier improves them
+    pub fn heat_decay_bonus(&self) -> f32 {
+        match self {
+            ReputationLevel::Nobody | ReputationLevel::Wannabe => 0.0,
+            ReputationLevel::ScriptKiddie | ReputationLevel::Amateur => 0.10,
+            ReputationLevel::Competent | ReputationLevel::Skilled => 0.20,
+            ReputationLevel::Expert | ReputationLevel::Master => 0.30,
+            ReputationLevel::Elite | ReputationLevel::Legendary => 0.40,
+            // Instant decay
+            ReputationLevel::Mythical => f32::INFINITY,
+        }
+    }
+
+    /// Apply the heat decay perk to a decay rate (the share of heat kept per tick)
+    pub fn scale_heat_decay(&self, decay_rate: f32) -> f32 {
+        let shed = (1.0 - decay_rate) * (1.0 + self.heat_decay_bonus());
+        (1.0 - shed).clamp(0.0, 1.0)
+    }
+
+    /// Mission reward bonus perk, as a fraction of the reward
+    pub fn mission_reward_bonus(&self) -> f32 {
+        match self {
+            ReputationLevel::Nobody
+            | ReputationLevel::Wannabe
+            | ReputationLevel::ScriptKiddie
+            | ReputationLevel::Amateur => 0.0,
+            ReputationLevel::Competent | ReputationLevel::Skilled => 0.10,
+            ReputationLevel::Expert | ReputationLevel::Master => 0.20,
+            _ => 0.30,
+        }
+    }
+
     /// Get perks for this level
     pub fn get_perks(&self) -> Vec<String> {
         let mut perks = Vec::new();
@@ -315,27 +387,112 @@ pub enum ReputationEventType {
     TraceCompleted,
 }
 
+/// Base reputation awards for every action, overridable from config for modding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReputationFormula {
+    // Terminal commands
+    pub scan: i32,
+    pub deep_scan: i32,
+    pub vuln_scan: i32,
+    pub exploit_success: i32,
+    pub exploit_failure: i32,
+    pub decrypt: i32,
+    pub crack: i32,
+    pub inject: i32,
+    pub firewall_bypass: i32,
+    pub firewall_disable: i32,
+    
+    // Reputation events
+    pub successful_hack: i32,
+    pub vulnerability_discovered: i32,
+    pub system_compromised: i32,
+    pub data_extracted: i32,
+    pub mission_completed: i32,
+    pub perfect_operation: i32,
+    pub first_blood: i32,
+    pub detection_triggered: i32,
+    pub hack_failed: i32,
+    pub system_locked: i32,
+    pub mission_failed: i32,
+    pub trace_completed: i32,
+}
+
+impl Default for ReputationFormula {
+    fn default() -> Self {
+        Self {
+            scan: 5,
+            deep_scan: 15,
+            vuln_scan: 12,
+            exploit_success: 20,
+            exploit_failure: 0,
+            decrypt: 10,
+            crack: 5,
+            inject: 15,
+            firewall_bypass: 25,
+            firewall_disable: 30,
+            successful_hack: 10,
+            vulnerability_discovered: 5,
+            system_compromised: 15,
+            data_extracted: 8,
+            mission_completed: 25,
+            perfect_operation: 30,
+            first_blood: 50,
+            detection_triggered: -5,
+            hack_failed: -10,
+            system_locked: -15,
+            mission_failed: -25,
+            trace_completed: -20,
+        }
+    }
+}
+
+impl ReputationFormula {
+    /// Base award for a reputation event
+    pub fn base_amount(&self, event_type: &ReputationEventType) -> i32 {
+        match event_type {
+            ReputationEventType::SuccessfulHack => self.successful_hack,
+            ReputationEventType::VulnerabilityDiscovered => self.vulnerability_discovered,
+            ReputationEventType::SystemCompromised => self.system_compromised,
+            ReputationEventType::DataExtracted => self.data_extracted,
+            ReputationEventType::MissionCompleted => self.mission_completed,
+            ReputationEventType::PerfectOperation => self.perfect_operation,
+            ReputationEventType::FirstBlood => self.first_blood,
+            ReputationEventType::DetectionTriggered => self.detection_triggered,
+            ReputationEventType::HackFailed => self.hack_failed,
+            ReputationEventType::SystemLocked => self.system_locked,
+            ReputationEventType::MissionFailed => self.mission_failed,
+            ReputationEventType::TraceCompleted => self.trace_completed,
+        }
+    }
+}
+
 impl ReputationEvent {
-    /// Create reputation event
+    /// Create reputation event using the default formula
     pub fn new(event_type: ReputationEventType) -> Self {
-        let (base_amount, description) = match event_type {
-            ReputationEventType::SuccessfulHack => (10, "Successful hack completed"),
-            ReputationEventType::VulnerabilityDiscovered => (5, "New vulnerability discovered"),
-            ReputationEventType::SystemCompromised => (15, "System successfully compromised"),
-            ReputationEventType::DataExtracted => (8, "Sensitive data extracted"),
-            ReputationEventType::MissionCompleted => (25, "Mission completed"),
-            ReputationEventType::PerfectOperation => (30, "Perfect operation - no detection"),
-            ReputationEventType::FirstBlood => (50, "First successful hack on new target"),
-            ReputationEventType::DetectionTriggered => (-5, "Detection systems triggered"),
-            ReputationEventType::HackFailed => (-10, "Hack attempt failed"),
-            ReputationEventType::SystemLocked => (-15, "Locked out of system"),
-            ReputationEventType::MissionFailed => (-25, "Mission failed"),
-            ReputationEventType::TraceCompleted => (-20, "Traced back to origin"),
+        Self::with_formula(event_type, &ReputationFormula::default())
+    }
+
+    /// Create reputation event using a custom formula
+    pub fn with_formula(event_type: ReputationEventType, formula: &ReputationFormula) -> Self {
+        let description = match event_type {
+            ReputationEventType::SuccessfulHack => "Successful hack completed",
+            ReputationEventType::VulnerabilityDiscovered => "New vulnerability discovered",
+            ReputationEventType::SystemCompromised => "System successfully compromised",
+            ReputationEventType::DataExtracted => "Sensitive data extracted",
+            ReputationEventType::MissionCompleted => "Mission completed",
+            ReputationEventType::PerfectOperation => "Perfect operation - no detection",
+            ReputationEventType::FirstBlood => "First successful hack on new target",
+            ReputationEventType::DetectionTriggered => "Detection systems triggered",
+            ReputationEventType::HackFailed => "Hack attempt failed",
+            ReputationEventType::SystemLocked => "Locked out of system",
+            ReputationEventType::MissionFailed => "Mission failed",
+            ReputationEventType::TraceCompleted => "Traced back to origin",
         };
         
         ReputationEvent {
+            base_amount: formula.base_amount(&event_type),
             event_type,
-            base_amount,
             description: description.to_string(),
         }
     }
@@ -370,6 +527,17 @@ mod tests {
         assert_eq!(manager.level, ReputationLevel::Wannabe);
     }
 
+    #[test]
+    fn test_level_perks() {
+        assert_eq!(ReputationLevel::Nobody.scale_heat_decay(0.9), 0.9);
+        assert!((ReputationLevel::Competent.scale_heat_decay(0.9) - 0.88).abs() < 1e-6);
+        assert_eq!(ReputationLevel::Mythical.scale_heat_decay(0.9), 0.0);
+        assert_eq!(ReputationLevel::Expert.mission_reward_bonus(), 0.20);
+        assert_eq!(ReputationLevel::Mythical.reputation_bonus(), 1.0);
+        assert!(ReputationLevel::Amateur.outranks(&ReputationLevel::ScriptKiddie));
+        assert!(!ReputationLevel::Nobody.outranks(&ReputationLevel::Nobody));
+    }
+
     #[test]
     fn test_streak_multiplier() {
         let mut manager = ReputationManager::new(0);
@@ -386,6 +554,20 @@ mod tests {
         assert_eq!(added, 110); // 100 * 1.1
     }
 
+    #[test]
+    fn test_streak_window() {
+        let mut manager = ReputationManager::new(0);
+        assert_eq!(manager.streak_seconds_remaining(), None);
+
+        manager.add_reputation(10);
+        manager.last_action = Some(chrono::Utc::now() - chrono::Duration::seconds(100));
+        let remaining = manager.streak_seconds_remaining().unwrap();
+        assert!((199..=200).contains(&remaining));
+
+        manager.last_action = Some(chrono::Utc::now() - chrono::Duration::seconds(900));
+        assert_eq!(manager.streak_seconds_remaining(), Some(0));
+    }
+
     #[test]
     fn test_level_progress() {
         let mut manager = ReputationManager::new(75);
@@ -406,4 +588,44 @@ mod tests {
         let negative_event = ReputationEvent::new(ReputationEventType::HackFailed);
         assert_eq!(negative_event.base_amount, -10);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_default_formula_matches_current_awards() {
+        let formula = ReputationFormula::default();
+        let expected = [
+            (ReputationEventType::SuccessfulHack, 10),
+            (ReputationEventType::VulnerabilityDiscovered, 5),
+            (ReputationEventType::SystemCompromised, 15),
+            (ReputationEventType::DataExtracted, 8),
+            (ReputationEventType::MissionCompleted, 25),
+            (ReputationEventType::PerfectOperation, 30),
+            (ReputationEventType::FirstBlood, 50),
+            (ReputationEventType::DetectionTriggered, -5),
+            (ReputationEventType::HackFailed, -10),
+            (ReputationEventType::SystemLocked, -15),
+            (ReputationEventType::MissionFailed, -25),
+            (ReputationEventType::TraceCompleted, -20),
+        ];
+        for (event_type, amount) in expected {
+            assert_eq!(ReputationEvent::new(event_type).base_amount, amount);
+        }
+        
+        assert_eq!(formula.scan, 5);
+        assert_eq!(formula.exploit_success, 20);
+        assert_eq!(formula.exploit_failure, 0);
+        assert_eq!(formula.decrypt, 10);
+        assert_eq!(formula.inject, 15);
+        assert_eq!(formula.firewall_bypass, 25);
+        assert_eq!(formula.firewall_disable, 30);
+    }
+
+    #[test]
+    fn test_partial_formula_override() {
+        let formula: ReputationFormula = serde_json::from_str(r#"{"scan": 50, "hack_failed": -1}"#).unwrap();
+        assert_eq!(formula.scan, 50);
+        assert_eq!(formula.decrypt, 10);
+        
+        let event = ReputationEvent::with_formula(ReputationEventType::HackFailed, &formula);
+        assert_eq!(event.base_amount, -1);
+    }
+}
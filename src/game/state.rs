@@ -1,7 +1,24 @@
 //! Game state management for CRIMSON-REDLINE
 
+use crate::game::events::{EventRecord, EVENT_LOG_LIMIT};
+use crate::game::{Achievement, Mission, ObjectiveKind, ReputationLevel, ReputationManager, UnlockedAchievement};
 use serde::{Deserialize, Serialize};
-use anyhow::Result;
+use std::collections::BTreeMap;
+
+/// Heat multiplier for the agent's own commands while a cover identity is active
+pub const SPOOF_HEAT_FACTOR: f32 = 0.9;
+
+/// Heat above which a run counts as a close call
+pub const CLOSE_CALL_HEAT: f32 = 90.0;
+
+/// Heat below which a successful exploit counts as a perfect, undetected hack
+pub const PERFECT_HACK_HEAT: f32 = 5.0;
+
+/// Share of heat and reputation gains kept while stealth mode is on
+pub const STEALTH_FACTOR: f32 = 0.5;
+
+/// How many times longer scans, cracks, decryption and exploits take in stealth mode
+pub const STEALTH_SLOWDOWN: u32 = 2;
 
 /// Main game state
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +26,8 @@ pub struct GameState {
     pub username: String,
     pub reputation: i32,
     pub heat_level: f32,
+    /// When heat decay last ran; time away from the keyboard counts as lying low
+    pub last_heat_update: chrono::DateTime<chrono::Utc>,
     pub credits: i32,
     pub missions_completed: u32,
     pub successful_hacks: u32,
@@ -16,13 +35,35 @@ pub struct GameState {
     pub total_scans: u32,
     pub files_decrypted: u32,
     pub systems_compromised: u32,
+    pub times_busted: u32,
     pub time_played: std::time::Duration,
+    /// When play time was last counted; reset on each update
     pub session_start: chrono::DateTime<chrono::Utc>,
-    pub active_missions: Vec<String>,
+    /// Play time counted so far in this session
+    #[serde(skip)]
+    pub session_played: std::time::Duration,
+    /// Accepted missions with their live objective progress
+    pub active_missions: Vec<Mission>,
     pub completed_missions: Vec<String>,
     pub unlocked_tools: Vec<String>,
     pub discovered_exploits: Vec<String>,
     pub network_map: NetworkMap,
+    pub transactions: Vec<Transaction>,
+    pub unlocked_achievements: Vec<UnlockedAchievement>,
+    /// Streak tracking for reputation gains
+    pub reputation_manager: ReputationManager,
+    /// Lifetime records shown by `records`
+    pub stats: PlayerStats,
+    /// Recent random events and how they were answered, oldest first
+    pub event_log: Vec<EventRecord>,
+    /// Stealth mode: slower, quieter work that also earns less reputation
+    pub stealth: bool,
+    /// Session-only cover identity shown instead of the real username
+    #[serde(skip)]
+    pub spoofed_name: Option<String>,
+    /// Compromised host the session is connected to, if any
+    #[serde(skip)]
+    pub current_target: Option<String>,
 }
 
 impl GameState {
@@ -32,6 +73,7 @@ impl GameState {
             username,
             reputation: starting_reputation,
             heat_level: 0.0,
+            last_heat_update: chrono::Utc::now(),
             credits: 1000,
             missions_completed: 0,
             successful_hacks: 0,
@@ -39,24 +81,59 @@ impl GameState {
             total_scans: 0,
             files_decrypted: 0,
             systems_compromised: 0,
+            times_busted: 0,
             time_played: std::time::Duration::from_secs(0),
             session_start: chrono::Utc::now(),
+            session_played: std::time::Duration::from_secs(0),
             active_missions: Vec::new(),
             completed_missions: Vec::new(),
             unlocked_tools: vec!["scan".to_string(), "decrypt".to_string()],
             discovered_exploits: Vec::new(),
             network_map: NetworkMap::new(),
+            transactions: Vec::new(),
+            unlocked_achievements: Vec::new(),
+            reputation_manager: ReputationManager::new(starting_reputation),
+            stats: PlayerStats::new(),
+            event_log: Vec::new(),
+            stealth: false,
+            spoofed_name: None,
+            current_target: None,
         }
     }
 
-    /// Add reputation
-    pub fn add_reputation(&mut self, amount: i32) {
-        self.reputation = (self.reputation + amount).max(0);
+    /// Add reputation, applying the level perk and streak multiplier to gains; returns the change made
+    pub fn add_reputation(&mut self, amount: i32) -> i32 {
+        let before = self.reputation;
+        self.reputation_manager.sync(self.reputation);
+        if amount >= 0 {
+            let mut with_perk = amount as f32 * (1.0 + self.reputation_level().reputation_bonus());
+            if self.stealth {
+                with_perk *= STEALTH_FACTOR;
+            }
+            let with_perk = with_perk as i32;
+            self.reputation_manager.add_reputation(with_perk);
+        } else {
+            // Penalties break the streak
+            self.reputation_manager.remove_reputation(-amount);
+        }
+        self.reputation = self.reputation_manager.current_reputation.max(0);
+        let change = self.reputation - before;
+        self.stats.total_reputation_earned += change.max(0);
+        change
+    }
+
+    /// Named reputation tier, which decides the active perks
+    pub fn reputation_level(&self) -> ReputationLevel {
+        ReputationLevel::from_reputation(self.reputation)
     }
 
     /// Increase heat level
     pub fn increase_heat(&mut self, amount: f32) {
+        let before = self.heat_level;
         self.heat_level = (self.heat_level + amount).min(100.0);
+        if before <= CLOSE_CALL_HEAT && self.heat_level > CLOSE_CALL_HEAT {
+            self.stats.close_calls += 1;
+        }
     }
 
     /// Decrease heat level (cooldown)
@@ -64,30 +141,77 @@ impl GameState {
         self.heat_level = (self.heat_level - amount).max(0.0);
     }
 
-    /// Apply heat decay over time
-    pub fn apply_heat_decay(&mut self, decay_rate: f32) {
-        self.heat_level = (self.heat_level * decay_rate).max(0.0);
+    /// Decay heat for the real time elapsed since the last update
+    pub fn decay_heat(&mut self, rate_per_minute: f32) {
+        self.decay_heat_until(chrono::Utc::now(), rate_per_minute);
+    }
+
+    /// Decay heat for the time elapsed up to `now`; `rate_per_minute` is the share of heat kept each minute
+    pub fn decay_heat_until(&mut self, now: chrono::DateTime<chrono::Utc>, rate_per_minute: f32) {
+        let minutes = (now - self.last_heat_update).num_milliseconds().max(0) as f32 / 60_000.0;
+        let rate = self.reputation_level().scale_heat_decay(rate_per_minute);
+        self.heat_level = (self.heat_level * rate.powf(minutes)).max(0.0);
+        self.last_heat_update = now;
     }
 
     /// Add credits
     pub fn add_credits(&mut self, amount: i32) {
         self.credits = (self.credits + amount).max(0);
+        self.stats.total_credits_earned += amount.max(0);
     }
 
     /// Spend credits
     pub fn spend_credits(&mut self, amount: i32) -> bool {
         if self.credits >= amount {
             self.credits -= amount;
+            self.stats.total_credits_spent += amount;
             true
         } else {
             false
         }
     }
 
+    /// Name shown in the UI - the cover identity if one is active
+    pub fn display_name(&self) -> &str {
+        self.spoofed_name.as_deref().unwrap_or(&self.username)
+    }
+
+    /// Record a credit transaction (negative amounts are purchases)
+    pub fn record_transaction(&mut self, description: &str, amount: i32) {
+        self.transactions.push(Transaction {
+            description: description.to_string(),
+            amount,
+            timestamp: chrono::Utc::now(),
+        });
+    }
+
+    /// Record a rootkit planted on a discovered host, counting it as compromised if it wasn't yet
+    pub fn record_rootkit(&mut self, ip: &str) {
+        if self.network_map.install_rootkit(ip) {
+            self.systems_compromised += 1;
+        }
+    }
+
     /// Record successful hack
     pub fn record_successful_hack(&mut self) {
         self.successful_hacks += 1;
         self.systems_compromised += 1;
+        self.advance_missions(ObjectiveKind::Exploit, 1);
+    }
+
+    /// Log an answered or expired event, keeping only the most recent
+    pub fn record_event(&mut self, record: EventRecord) {
+        self.event_log.push(record);
+        if self.event_log.len() > EVENT_LOG_LIMIT {
+            self.event_log.drain(..self.event_log.len() - EVENT_LOG_LIMIT);
+        }
+    }
+
+    /// Bring the lifetime records up to date with the current state
+    pub fn update_stats(&mut self) {
+        let mut stats = std::mem::take(&mut self.stats);
+        stats.update_from_state(self);
+        self.stats = stats;
     }
 
     /// Record failed hack
@@ -95,32 +219,94 @@ impl GameState {
         self.failed_hacks += 1;
     }
 
+    /// Record getting busted at maximum heat; the emergency disconnect burns the trail
+    pub fn record_bust(&mut self) {
+        self.times_busted += 1;
+        self.heat_level = 0.0;
+    }
+
     /// Record scan
     pub fn record_scan(&mut self) {
         self.total_scans += 1;
+        self.advance_missions(ObjectiveKind::Scan, 1);
+    }
+
+    /// Count a scanned target towards missions that want different targets
+    pub fn record_scan_target(&mut self, target: &str) {
+        for mission in &mut self.active_missions {
+            mission.advance_target(ObjectiveKind::ScanTarget, target);
+        }
     }
 
     /// Record decryption
     pub fn record_decryption(&mut self) {
         self.files_decrypted += 1;
+        self.advance_missions(ObjectiveKind::Decrypt, 1);
     }
 
     /// Complete mission
     pub fn complete_mission(&mut self, mission_id: String) {
-        if let Some(pos) = self.active_missions.iter().position(|x| x == &mission_id) {
+        if let Some(pos) = self.active_missions.iter().position(|m| m.id == mission_id) {
             self.active_missions.remove(pos);
             self.completed_missions.push(mission_id);
             self.missions_completed += 1;
         }
     }
 
-    /// Start mission
-    pub fn start_mission(&mut self, mission_id: String) {
-        if !self.active_missions.contains(&mission_id) && !self.completed_missions.contains(&mission_id) {
-            self.active_missions.push(mission_id);
+    /// Start a mission from the catalogue; false if it doesn't exist, is active or is done
+    pub fn start_mission(&mut self, mission_id: String) -> bool {
+        let Some(mut mission) = crate::game::find_mission(&mission_id) else {
+            return false;
+        };
+        if self.get_active_mission(&mission.id).is_some() || self.completed_missions.contains(&mission.id) {
+            return false;
+        }
+        mission.is_active = true;
+        mission.started_at = Some(chrono::Utc::now());
+        self.active_missions.push(mission);
+        true
+    }
+
+    /// An accepted mission, by id
+    pub fn get_active_mission(&self, mission_id: &str) -> Option<&Mission> {
+        self.active_missions.iter().find(|m| m.id.eq_ignore_ascii_case(mission_id))
+    }
+
+    /// Advance objectives tracking `kind` on every active mission
+    pub fn advance_missions(&mut self, kind: ObjectiveKind, amount: u32) {
+        for mission in &mut self.active_missions {
+            mission.advance(kind, amount);
         }
     }
 
+    /// Drop every active mission that ran out of time before `now`; it can be accepted again
+    pub fn expire_missions(&mut self, now: chrono::DateTime<chrono::Utc>) -> Vec<Mission> {
+        let (expired, active) = std::mem::take(&mut self.active_missions)
+            .into_iter()
+            .partition(|mission| mission.is_expired(now));
+        self.active_missions = active;
+        expired
+    }
+
+    /// Complete every active mission whose objectives all hold, paying out its rewards
+    pub fn settle_missions(&mut self) -> Vec<Mission> {
+        let heat_level = self.heat_level;
+        for mission in &mut self.active_missions {
+            mission.refresh_conditions(heat_level);
+        }
+        
+        let mut finished: Vec<Mission> = self.active_missions.iter().filter(|m| m.is_completed).cloned().collect();
+        for mission in &mut finished {
+            self.complete_mission(mission.id.clone());
+            // Report the rewards actually paid, perks included
+            let bonus = 1.0 + self.reputation_level().mission_reward_bonus();
+            mission.reward_reputation = self.add_reputation((mission.reward_reputation as f32 * bonus) as i32);
+            mission.reward_credits = (mission.reward_credits as f32 * bonus) as i32;
+            self.add_credits(mission.reward_credits);
+        }
+        finished
+    }
+
     /// Unlock tool
     pub fn unlock_tool(&mut self, tool: String) {
         if !self.unlocked_tools.contains(&tool) {
@@ -128,6 +314,16 @@ impl GameState {
         }
     }
 
+    /// Multiplier on the time operations take, stretched while stealth mode is on
+    pub fn operation_pace(&self) -> u32 {
+        if self.stealth { STEALTH_SLOWDOWN } else { 1 }
+    }
+
+    /// Check whether a tool has been unlocked
+    pub fn has_tool(&self, tool: &str) -> bool {
+        self.unlocked_tools.iter().any(|t| t == tool)
+    }
+
     /// Discover exploit
     pub fn discover_exploit(&mut self, exploit: String) {
         if !self.discovered_exploits.contains(&exploit) {
@@ -135,11 +331,69 @@ impl GameState {
         }
     }
 
+    /// Check whether an achievement has already been unlocked
+    pub fn has_achievement(&self, id: &str) -> bool {
+        self.unlocked_achievements.iter().any(|a| a.id == id)
+    }
+
+    /// Unlock an achievement by id; returns false if it was already unlocked
+    pub fn unlock_achievement(&mut self, id: &str) -> bool {
+        if self.has_achievement(id) {
+            return false;
+        }
+        self.unlocked_achievements.push(UnlockedAchievement {
+            id: id.to_string(),
+            unlocked_at: chrono::Utc::now(),
+        });
+        true
+    }
+
+    /// Unlock any newly earned achievements, returning them for notification
+    pub fn check_achievements(&mut self) -> Vec<Achievement> {
+        let mut newly_unlocked = Vec::new();
+        
+        for mut achievement in crate::game::generate_achievements() {
+            if !self.has_achievement(&achievement.id)
+                && crate::game::achievements::is_earned(&achievement.id, self)
+            {
+                self.unlock_achievement(&achievement.id);
+                achievement.unlock();
+                newly_unlocked.push(achievement);
+            }
+        }
+        
+        newly_unlocked
+    }
+
+    /// Unlock the first-login achievement on an account's first login, returning it for display
+    pub fn welcome_achievement(&mut self, login_count: u32) -> Option<Achievement> {
+        if login_count != 1 || !self.unlock_achievement("first_login") {
+            return None;
+        }
+        let mut achievement = crate::game::generate_achievements().into_iter().find(|a| a.id == "first_login")?;
+        achievement.unlock();
+        Some(achievement)
+    }
+
+    /// Most recently unlocked achievements, newest first
+    pub fn recent_achievements(&self, count: usize) -> Vec<&UnlockedAchievement> {
+        let mut recent: Vec<&UnlockedAchievement> = self.unlocked_achievements.iter().collect();
+        recent.sort_by_key(|a| std::cmp::Reverse(a.unlocked_at));
+        recent.truncate(count);
+        recent
+    }
+
     /// Update time played
     pub fn update_time_played(&mut self) {
-        let elapsed = chrono::Utc::now() - self.session_start;
-        self.time_played += elapsed.to_std().unwrap_or_default();
-        self.session_start = chrono::Utc::now();
+        self.update_time_played_until(chrono::Utc::now());
+    }
+
+    /// Count play time up to `now`
+    pub fn update_time_played_until(&mut self, now: chrono::DateTime<chrono::Utc>) {
+        let elapsed = (now - self.session_start).to_std().unwrap_or_default();
+        self.time_played += elapsed;
+        self.session_played += elapsed;
+        self.session_start = now;
     }
 
     /// Get success rate percentage
@@ -191,6 +445,14 @@ impl GameState {
     }
 }
 
+/// Credit transaction record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub description: String,
+    pub amount: i32,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
 /// Network map for discovered systems
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkMap {
@@ -198,6 +460,12 @@ pub struct NetworkMap {
     pub connections: Vec<Connection>,
 }
 
+impl Default for NetworkMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl NetworkMap {
     /// Create new network map
     pub fn new() -> Self {
@@ -207,10 +475,14 @@ impl NetworkMap {
         }
     }
 
-    /// Add discovered node
+    /// Add discovered node; rescanning a known node refreshes its vulnerabilities and open ports
     pub fn add_node(&mut self, node: NetworkNode) {
-        if !self.discovered_nodes.iter().any(|n| n.ip == node.ip) {
-            self.discovered_nodes.push(node);
+        match self.get_node_mut(&node.ip) {
+            Some(existing) => {
+                existing.vulnerabilities = node.vulnerabilities;
+                existing.open_ports = node.open_ports;
+            }
+            None => self.discovered_nodes.push(node),
         }
     }
 
@@ -227,6 +499,66 @@ impl NetworkMap {
         self.discovered_nodes.iter().find(|n| n.ip == ip)
     }
 
+    /// Get mutable node by IP
+    pub fn get_node_mut(&mut self, ip: &str) -> Option<&mut NetworkNode> {
+        self.discovered_nodes.iter_mut().find(|n| n.ip == ip)
+    }
+
+    /// Get a node by IP, adding a placeholder for it if unseen
+    fn node_entry(&mut self, ip: &str, hostname: &str) -> &mut NetworkNode {
+        let index = match self.discovered_nodes.iter().position(|n| n.ip == ip) {
+            Some(index) => index,
+            None => {
+                self.discovered_nodes.push(NetworkNode {
+                    ip: ip.to_string(),
+                    hostname: hostname.to_string(),
+                    node_type: NodeType::Unknown,
+                    is_compromised: false,
+                    security_level: SecurityLevel::Medium,
+                    discovered_at: chrono::Utc::now(),
+                    suspected_honeypot: false,
+                    vulnerabilities: Vec::new(),
+                    has_rootkit: false,
+                    open_ports: Vec::new(),
+                });
+                self.discovered_nodes.len() - 1
+            }
+        };
+        &mut self.discovered_nodes[index]
+    }
+
+    /// Flag a node as a likely honeypot, adding it to the map if unseen
+    pub fn flag_honeypot(&mut self, ip: &str, hostname: &str) {
+        self.node_entry(ip, hostname).suspected_honeypot = true;
+    }
+
+    /// Mark a node as compromised, adding it to the map if unseen
+    pub fn mark_compromised(&mut self, ip: &str) {
+        self.node_entry(ip, ip).is_compromised = true;
+    }
+
+    /// Plant a rootkit on a discovered node, leaving it owned for good.
+    /// Returns whether this took over a node that wasn't already compromised
+    pub fn install_rootkit(&mut self, ip: &str) -> bool {
+        let Some(node) = self.get_node_mut(ip) else {
+            return false;
+        };
+        let newly_owned = !node.is_compromised;
+        node.is_compromised = true;
+        node.has_rootkit = true;
+        newly_owned
+    }
+
+    /// Check whether a node has been compromised
+    pub fn is_compromised(&self, ip: &str) -> bool {
+        self.get_node(ip).is_some_and(|n| n.is_compromised)
+    }
+
+    /// Check whether a node has been flagged as a likely honeypot
+    pub fn is_suspected_honeypot(&self, ip: &str) -> bool {
+        self.get_node(ip).is_some_and(|n| n.suspected_honeypot)
+    }
+
     /// Get all connected nodes
     pub fn get_connected_nodes(&self, ip: &str) -> Vec<&NetworkNode> {
         let mut connected = Vec::new();
@@ -256,6 +588,16 @@ pub struct NetworkNode {
     pub is_compromised: bool,
     pub security_level: SecurityLevel,
     pub discovered_at: chrono::DateTime<chrono::Utc>,
+    pub suspected_honeypot: bool,
+    /// Weaknesses reported by the last scan of this host
+    #[serde(default)]
+    pub vulnerabilities: Vec<String>,
+    /// A rootkit keeps access open across sessions
+    #[serde(default)]
+    pub has_rootkit: bool,
+    /// Ports the last scan of this host found open
+    #[serde(default)]
+    pub open_ports: Vec<u16>,
 }
 
 /// Node types
@@ -303,6 +645,18 @@ pub struct PlayerStats {
     pub unique_exploits_used: u32,
     pub perfect_hacks: u32, // No detection
     pub close_calls: u32, // Heat > 90%
+    /// Successful hacks per target IP, for `most_hacked_system`
+    pub hacks_by_system: BTreeMap<String, u32>,
+    /// Every exploit that has landed at least once
+    pub exploits_used: Vec<String>,
+    /// Times each command has been run, by canonical name
+    pub command_counts: BTreeMap<String, u32>,
+}
+
+impl Default for PlayerStats {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PlayerStats {
@@ -320,20 +674,40 @@ impl PlayerStats {
             unique_exploits_used: 0,
             perfect_hacks: 0,
             close_calls: 0,
+            hacks_by_system: BTreeMap::new(),
+            exploits_used: Vec::new(),
+            command_counts: BTreeMap::new(),
         }
     }
 
-    /// Update stats from game state
+    /// Update the high-water marks from game state
     pub fn update_from_state(&mut self, state: &GameState) {
-        self.total_reputation_earned = self.total_reputation_earned.max(state.reputation);
         self.highest_heat_level = self.highest_heat_level.max(state.heat_level);
-        
-        if state.heat_level == 0.0 && state.successful_hacks > 0 {
-            self.perfect_hacks += 1;
+        self.longest_session = self.longest_session.max(state.session_played);
+    }
+
+    /// Count a run of a command and refresh `favorite_command`
+    pub fn record_command(&mut self, name: &str) {
+        *self.command_counts.entry(name.to_string()).or_insert(0) += 1;
+        if let Some((command, _)) = self.command_counts.iter().max_by_key(|(_, count)| **count) {
+            self.favorite_command = command.clone();
         }
-        
-        if state.heat_level > 90.0 {
-            self.close_calls += 1;
+    }
+
+    /// Record a successful exploit; `undetected` when heat was under `PERFECT_HACK_HEAT` going in
+    pub fn record_hack(&mut self, target: &str, exploit_id: &str, undetected: bool) {
+        *self.hacks_by_system.entry(target.to_string()).or_insert(0) += 1;
+        if let Some((system, _)) = self.hacks_by_system.iter().max_by_key(|(_, count)| **count) {
+            self.most_hacked_system = system.clone();
+        }
+
+        if !self.exploits_used.iter().any(|id| id == exploit_id) {
+            self.exploits_used.push(exploit_id.to_string());
+            self.unique_exploits_used = self.exploits_used.len() as u32;
+        }
+
+        if undetected {
+            self.perfect_hacks += 1;
         }
     }
 }
@@ -355,7 +729,7 @@ mod tests {
     fn test_reputation_management() {
         let mut state = GameState::new("testuser".to_string(), 100);
         state.add_reputation(50);
-        assert_eq!(state.reputation, 150);
+        assert_eq!(state.reputation, 152); // Wannabe: +5% reputation bonus
         
         state.add_reputation(-200);
         assert_eq!(state.reputation, 0); // Can't go below 0
@@ -373,10 +747,98 @@ mod tests {
         state.decrease_heat(30.0);
         assert_eq!(state.heat_level, 70.0);
         
-        state.apply_heat_decay(0.5);
+        let minute_later = state.last_heat_update + chrono::Duration::minutes(1);
+        state.decay_heat_until(minute_later, 0.5);
         assert_eq!(state.heat_level, 35.0);
     }
 
+    #[test]
+    fn test_heat_decays_with_elapsed_time() {
+        let mut state = GameState::new("testuser".to_string(), 0);
+        let start = state.last_heat_update;
+        state.heat_level = 80.0;
+
+        // No time passed, no decay no matter how often it runs
+        for _ in 0..10 {
+            state.decay_heat_until(start, 0.5);
+        }
+        assert_eq!(state.heat_level, 80.0);
+
+        state.decay_heat_until(start + chrono::Duration::minutes(2), 0.5);
+        assert!((state.heat_level - 20.0).abs() < 1e-4);
+
+        // A clock going backwards doesn't heat things up
+        state.decay_heat_until(start, 0.5);
+        assert!((state.heat_level - 20.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_time_played_accumulates() {
+        let mut state = GameState::new("testuser".to_string(), 0);
+        let start = state.session_start;
+        state.update_time_played_until(start + chrono::Duration::seconds(90));
+        state.update_time_played_until(start + chrono::Duration::seconds(150));
+        assert_eq!(state.time_played.as_secs(), 150);
+
+        let mut stats = PlayerStats::new();
+        stats.update_from_state(&state);
+        assert_eq!(stats.longest_session.as_secs(), 150);
+
+        // A shorter later session doesn't lower the record
+        state.session_played = std::time::Duration::from_secs(10);
+        stats.update_from_state(&state);
+        assert_eq!(stats.longest_session.as_secs(), 150);
+    }
+
+    #[test]
+    fn test_records_track_actions() {
+        let mut state = GameState::new("testuser".to_string(), 0);
+        state.increase_heat(85.0);
+        state.increase_heat(10.0);
+        state.increase_heat(3.0);
+        assert_eq!(state.stats.close_calls, 1);
+
+        state.decrease_heat(50.0);
+        state.increase_heat(50.0);
+        state.update_stats();
+        assert_eq!(state.stats.close_calls, 2);
+        assert_eq!(state.stats.highest_heat_level, 98.0);
+
+        state.stats.record_hack("10.0.0.5", "EXP-001", true);
+        state.stats.record_hack("10.0.0.9", "EXP-001", false);
+        state.stats.record_hack("10.0.0.9", "EXP-002", false);
+        assert_eq!(state.stats.most_hacked_system, "10.0.0.9");
+        assert_eq!(state.stats.unique_exploits_used, 2);
+        assert_eq!(state.stats.perfect_hacks, 1);
+
+        for command in ["scan", "exploit", "scan"] {
+            state.stats.record_command(command);
+        }
+        assert_eq!(state.stats.favorite_command, "scan");
+        assert_eq!(state.stats.command_counts["exploit"], 1);
+
+        assert!(state.spend_credits(400));
+        state.add_credits(150);
+        assert_eq!((state.stats.total_credits_spent, state.stats.total_credits_earned), (400, 150));
+    }
+
+    #[test]
+    fn test_level_perks_apply() {
+        let mut state = GameState::new("agent".to_string(), 500);
+        // Competent: +20% reputation, 20% faster heat decay
+        assert_eq!(state.add_reputation(100), 120);
+        assert_eq!(state.reputation_manager.streak, 1);
+
+        state.heat_level = 50.0;
+        let minute_later = state.last_heat_update + chrono::Duration::minutes(1);
+        state.decay_heat_until(minute_later, 0.9);
+        assert!((state.heat_level - 44.0).abs() < 1e-4);
+
+        // Penalties aren't boosted and reset the streak
+        assert_eq!(state.add_reputation(-20), -20);
+        assert_eq!(state.reputation_manager.streak, 0);
+    }
+
     #[test]
     fn test_level_calculation() {
         let mut state = GameState::new("testuser".to_string(), 0);
@@ -413,6 +875,10 @@ mod tests {
             is_compromised: false,
             security_level: SecurityLevel::Medium,
             discovered_at: chrono::Utc::now(),
+            suspected_honeypot: false,
+            vulnerabilities: Vec::new(),
+            has_rootkit: false,
+            open_ports: Vec::new(),
         };
         
         let node2 = NetworkNode {
@@ -422,6 +888,10 @@ mod tests {
             is_compromised: false,
             security_level: SecurityLevel::High,
             discovered_at: chrono::Utc::now(),
+            suspected_honeypot: false,
+            vulnerabilities: Vec::new(),
+            has_rootkit: false,
+            open_ports: Vec::new(),
         };
         
         map.add_node(node1);
@@ -435,4 +905,108 @@ mod tests {
         assert_eq!(connected.len(), 1);
         assert_eq!(connected[0].ip, "192.168.1.10");
     }
+
+    #[test]
+    fn test_flag_honeypot() {
+        let mut map = NetworkMap::new();
+        assert!(!map.is_suspected_honeypot("10.0.0.5"));
+
+        map.flag_honeypot("10.0.0.5", "SRV-001");
+        assert!(map.is_suspected_honeypot("10.0.0.5"));
+        assert_eq!(map.discovered_nodes.len(), 1);
+
+        // Flagging again doesn't duplicate the node
+        map.flag_honeypot("10.0.0.5", "SRV-001");
+        assert_eq!(map.discovered_nodes.len(), 1);
+    }
+
+    #[test]
+    fn test_mark_compromised() {
+        let mut map = NetworkMap::new();
+        map.flag_honeypot("10.0.0.5", "SRV-001");
+        assert!(!map.is_compromised("10.0.0.5"));
+
+        map.mark_compromised("10.0.0.5");
+        map.mark_compromised("10.0.0.9");
+        assert!(map.is_compromised("10.0.0.5"));
+        assert!(map.is_suspected_honeypot("10.0.0.5"));
+        assert!(map.is_compromised("10.0.0.9"));
+        assert_eq!(map.discovered_nodes.len(), 2);
+    }
+
+    #[test]
+    fn test_rootkits_need_a_discovered_host() {
+        let mut state = GameState::new("agent".to_string(), 0);
+        state.record_rootkit("10.0.0.99");
+        assert!(state.network_map.get_node("10.0.0.99").is_none());
+        assert_eq!(state.systems_compromised, 0);
+        
+        state.network_map.flag_honeypot("10.0.0.5", "SRV-001");
+        state.record_rootkit("10.0.0.5");
+        state.record_rootkit("10.0.0.5");
+        assert!(state.network_map.get_node("10.0.0.5").is_some_and(|n| n.has_rootkit && n.is_compromised));
+        assert_eq!(state.systems_compromised, 1);
+    }
+
+    #[test]
+    fn test_stealth_halves_reputation_gains() {
+        let mut state = GameState::new("agent".to_string(), 0);
+        state.stealth = true;
+        assert_eq!(state.add_reputation(100), 50);
+        assert_eq!(state.add_reputation(-20), -20);
+        assert_eq!(state.operation_pace(), STEALTH_SLOWDOWN);
+        state.stealth = false;
+        assert_eq!(state.operation_pace(), 1);
+    }
+
+    #[test]
+    fn test_spoofed_identity_is_display_only() {
+        let mut state = GameState::new("realname".to_string(), 0);
+        assert_eq!(state.display_name(), "realname");
+
+        state.spoofed_name = Some("ghost".to_string());
+        assert_eq!(state.display_name(), "ghost");
+        assert_eq!(state.username, "realname");
+
+        // Only the agent's own commands are masked; outside heat lands in full
+        state.increase_heat(10.0);
+        assert_eq!(state.heat_level, 10.0);
+    }
+
+    #[test]
+    fn test_mission_progress_and_rewards() {
+        let mut state = GameState::new("agent".to_string(), 0);
+        assert!(!state.start_mission("NOPE-404".to_string()));
+        assert!(state.start_mission("init-001".to_string()));
+        assert!(!state.start_mission("INIT-001".to_string()));
+
+        state.record_scan();
+        assert!(state.settle_missions().is_empty());
+        assert_eq!(state.get_active_mission("INIT-001").unwrap().get_completion_percentage(), 50.0);
+
+        state.record_decryption();
+        let finished = state.settle_missions();
+        assert_eq!(finished.len(), 1);
+        assert!(state.active_missions.is_empty());
+        assert_eq!(state.completed_missions, vec!["INIT-001".to_string()]);
+        assert_eq!(state.reputation, 10);
+        assert_eq!(state.credits, 1100);
+
+        // Finished missions can't be taken again
+        assert!(!state.start_mission("INIT-001".to_string()));
+    }
+
+    #[test]
+    fn test_expired_missions_are_dropped() {
+        let mut state = GameState::new("agent".to_string(), 0);
+        assert!(state.start_mission("CORP-001".to_string()));
+        assert!(state.start_mission("RECON-001".to_string()));
+        assert!(state.expire_missions(chrono::Utc::now()).is_empty());
+
+        let expired = state.expire_missions(chrono::Utc::now() + chrono::Duration::minutes(11));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, "CORP-001");
+        assert_eq!(state.active_missions.len(), 1);
+        assert!(state.start_mission("CORP-001".to_string()));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,99 @@
+//! Time-based game mechanics for CRIMSON-REDLINE
+//!
+//! Heat decay and random event rolls run on a fixed interval instead of per
+//! prompt. Decay is measured in wall-clock time, so idle players cool down and
+//! fast typers don't get extra decay. The decay rate arrives over a watch
+//! channel, so settings changed mid-session reach a running ticker.
+
+use crate::game::{EventManager, GameState};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Mutex};
+use tokio::task::JoinHandle;
+
+/// Game state shared between the prompt and the background ticker
+pub type SharedGameState = Arc<Mutex<GameState>>;
+
+/// Event manager shared between the prompt and the background ticker
+pub type SharedEventManager = Arc<Mutex<EventManager>>;
+
+/// How often time-based mechanics run
+pub const TICK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Apply one tick of time-based mechanics; `decay_rate` is the share of heat kept per minute
+pub async fn tick(state: &SharedGameState, events: &SharedEventManager, decay_rate: f32) {
+    let mut state = state.lock().await;
+    state.decay_heat(decay_rate);
+
+    let mut events = events.lock().await;
+    if events.adaptive_difficulty {
+        events.chance_scale = crate::game::difficulty::adaptive_modifier(&state).event_chance;
+    }
+    events.generate_event(state.heat_level, state.reputation);
+}
+
+/// Spawn the background ticker, reading the current decay rate on every tick;
+/// abort the returned handle to stop it
+pub fn spawn_ticker(
+    state: SharedGameState,
+    events: SharedEventManager,
+    decay_rate: watch::Receiver<f32>,
+    period: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        // The first tick completes immediately; skip it so decay starts one period in
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let rate = *decay_rate.borrow();
+            tick(&state, &events, rate).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shared(heat: f32) -> (SharedGameState, SharedEventManager) {
+        let mut state = GameState::new("test".to_string(), 0);
+        state.heat_level = heat;
+        let mut events = EventManager::new();
+        events.event_chance = 0.0;
+        (Arc::new(Mutex::new(state)), Arc::new(Mutex::new(events)))
+    }
+
+    #[tokio::test]
+    async fn test_tick_decays_heat() {
+        let (state, events) = shared(50.0);
+        state.lock().await.last_heat_update -= chrono::Duration::minutes(1);
+        tick(&state, &events, 0.5).await;
+        let heat = state.lock().await.heat_level;
+        assert!(heat <= 25.0 && heat > 24.9);
+    }
+
+    #[tokio::test]
+    async fn test_ticker_runs_without_input() {
+        let (state, events) = shared(80.0);
+        let (_rate, decay_rate) = watch::channel(0.5);
+        let handle = spawn_ticker(state.clone(), events, decay_rate, Duration::from_millis(10));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        handle.abort();
+        assert!(state.lock().await.heat_level < 80.0);
+    }
+
+    #[tokio::test]
+    async fn test_ticker_follows_a_changed_decay_rate() {
+        let (state, events) = shared(80.0);
+        let (rate, decay_rate) = watch::channel(1.0);
+        let handle = spawn_ticker(state.clone(), events, decay_rate, Duration::from_millis(10));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(state.lock().await.heat_level, 80.0);
+        
+        rate.send(0.0).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        handle.abort();
+        assert!(state.lock().await.heat_level < 80.0);
+    }
+}
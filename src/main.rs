@@ -5,21 +5,74 @@ use crimson_redline::*;
 use anyhow::Result;
 use crossterm::{
     event::{self, Event, KeyCode},
-    terminal,
-    execute,
-    cursor,
+    cursor, execute, terminal,
 };
 use std::io::{self, Write};
-use tokio::time::{sleep, Duration};
+use unicode_width::UnicodeWidthStr;
+use tokio::time::Duration;
+
+/// How long an achievement popup stays up
+const ACHIEVEMENT_POPUP_MS: u64 = 1500;
+
+/// Command-line launch options
+struct LaunchOptions {
+    no_motd: bool,
+    /// Type text instantly and skip animations, whatever the display settings say
+    fast: bool,
+    /// Run commands from this file (`-` for stdin) instead of the interactive prompt
+    script: Option<String>,
+    /// Really delete the agent's session logs on logout
+    wipe_logs: bool,
+    /// Give this account admin rights and exit
+    grant_admin: Option<String>,
+}
+
+impl LaunchOptions {
+    /// Parse options from the process arguments
+    fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        LaunchOptions {
+            no_motd: args.iter().any(|a| a == "--no-motd"),
+            fast: args.iter().any(|a| a == "--fast"),
+            script: args.iter().position(|a| a == "--script").and_then(|i| args.get(i + 1)).cloned(),
+            wipe_logs: args.iter().any(|a| a == "--wipe-logs"),
+            grant_admin: args.iter().position(|a| a == "--grant-admin").and_then(|i| args.get(i + 1)).cloned(),
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let options = LaunchOptions::from_args();
+    
+    if let Some(username) = &options.grant_admin {
+        let granted = auth::AuthSystem::new().and_then(|mut auth| auth::admin::grant_from_operator(&mut auth, username));
+        match granted {
+            Ok(()) => println!("[SYSTEM] '{}' is now an admin", username),
+            Err(e) => {
+                eprintln!("[ERROR] Could not grant admin: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+    
+    if let Some(path) = &options.script {
+        let code = run_script_file(path).await.unwrap_or_else(|e| {
+            eprintln!("[ERROR] Script failed: {}", e);
+            1
+        });
+        std::process::exit(code);
+    }
+    
+    // Restores the terminal on every exit path, panics included
+    let terminal_guard = ui::TerminalGuard::install();
+    
     // Initialize the application
-    let result = run_application().await;
+    let result = run_application(&options).await;
     
-    // Ensure terminal is restored on exit
-    let _ = terminal::disable_raw_mode();
-    let _ = execute!(io::stdout(), cursor::Show);
+    // Restore the terminal before reporting anything
+    drop(terminal_guard);
     
     if let Err(e) = result {
         eprintln!("\n[ERROR] Application crashed: {}", e);
@@ -30,44 +83,71 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Run a command script without the interactive prompt, returning the exit code.
+/// Scripts play as a throwaway agent, so no save or history is touched
+async fn run_script_file(path: &str) -> Result<i32> {
+    let contents = if path == "-" {
+        io::read_to_string(io::stdin())?
+    } else {
+        std::fs::read_to_string(path)?
+    };
+    
+    // Nobody is watching, so skip animations and typing delays
+    let mut config = utils::Config::load()?;
+    config.display.use_animations = false;
+    ui::animations::set_typing_speed(0);
+    ui::ascii_art::set_ascii_only(config.display.ascii_only || !ui::ascii_art::locale_supports_unicode());
+    let color_scheme = ui::ColorScheme::from_display(&config.display);
+    
+    let user = auth::User::new("script".to_string(), "script")?;
+    let mut handler = commands::CommandHandler::with_config(&user, config);
+    handler.set_interactive(false);
+    let script = commands::script::parse_script(&contents);
+    let outcome = commands::script::run_script(&mut handler, &script, &color_scheme).await?;
+    
+    color_scheme.print_dim(&format!("\n[script] {} commands run, {} failed\n", outcome.executed, outcome.failed))?;
+    Ok(outcome.exit_code())
+}
+
+/// Push display settings into the typing and glyph globals and the UI color scheme
+fn apply_display_settings(display: &utils::config::DisplayConfig, ui: &mut ui::RedlineUI) {
+    ui::animations::set_typing_speed(display.typing_speed_ms);
+    ui::ascii_art::set_ascii_only(display.ascii_only || !ui::ascii_art::locale_supports_unicode());
+    ui.set_color_scheme(ui::ColorScheme::from_display(display));
+}
+
 /// Main application loop
-async fn run_application() -> Result<()> {
+async fn run_application(options: &LaunchOptions) -> Result<()> {
+    // Load configuration
+    let mut config = utils::Config::load()?;
+    if options.fast {
+        config.display.typing_speed_ms = 0;
+        config.display.use_animations = false;
+    }
+    
     // Initialize UI
     let mut ui = ui::RedlineUI::new()?;
+    apply_display_settings(&config.display, &mut ui);
     
-    // Show intro animation on a separate screen
-    execute!(
-        io::stdout(),
-        terminal::EnterAlternateScreen,
-        cursor::Hide
-    )?;
-    
-    // Show intro animation
-    ui.initialize().await?;
-    
-    // Clear and exit alternate screen after intro
-    execute!(
-        io::stdout(),
-        terminal::LeaveAlternateScreen,
-        cursor::Show
-    )?;
+    // Show intro animation on a separate screen; the guard leaves it even if the intro fails
+    {
+        let _alternate_screen = ui::AlternateScreenGuard::enter()?;
+        ui.initialize().await?;
+    }
     
     // Small delay to ensure clean transition
-    sleep(Duration::from_millis(100)).await;
+    ui::animations::pause(Duration::from_millis(100), ui.color_scheme()).await;
     
     // Clear main screen
     utils::clear_screen()?;
     
-    // Load configuration
-    let _config = utils::Config::load()?;
-    
     // Initialize authentication system
     let mut auth_system = auth::AuthSystem::new()?;
     
     // Main application loop
     loop {
         // Show entry menu (CREATE NEW USER / LOGIN / EXIT)
-        let menu_action = show_entry_menu(&mut auth_system, ui.color_scheme()).await?;
+        let menu_action = show_entry_menu(&mut auth_system, &mut ui).await?;
         
         match menu_action {
             ui::menu::MenuAction::Exit => {
@@ -77,7 +157,7 @@ async fn run_application() -> Result<()> {
             ui::menu::MenuAction::Continue => {
                 // User successfully logged in, enter main terminal
                 if let Some(user) = auth_system.current_user() {
-                    run_terminal_session(user.clone(), &mut auth_system, &mut ui).await?;
+                    run_terminal_session(user.clone(), &mut auth_system, &mut ui, &mut config, options).await?;
                 }
             }
             _ => continue,
@@ -92,7 +172,7 @@ async fn run_application() -> Result<()> {
 /// Show entry menu and handle authentication (FIXED FOR NO FLICKERING)
 async fn show_entry_menu(
     auth_system: &mut auth::AuthSystem,
-    color_scheme: &ui::ColorScheme,
+    ui: &mut ui::RedlineUI,
 ) -> Result<ui::menu::MenuAction> {
     let mut menu = ui::menu::MainMenu::entry_menu();
     let mut needs_redraw = true;
@@ -101,12 +181,19 @@ async fn show_entry_menu(
         // Only redraw when needed
         if needs_redraw {
             terminal::enable_raw_mode()?;
-            menu.display(color_scheme).await?;
+            menu.display(ui.color_scheme()).await?;
             needs_redraw = false;
         }
         
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Resize(width, height) = event {
+                ui.resize(width, height);
+                utils::clear_screen()?;
+                needs_redraw = true;
+                continue;
+            }
+            if let Event::Key(key) = event {
                 let action = menu.handle_input(key);
                 
                 match action {
@@ -116,11 +203,11 @@ async fn show_entry_menu(
                     }
                     ui::menu::MenuAction::CreateNewUser => {
                         terminal::disable_raw_mode()?;
-                        let success = auth::register::run_registration(auth_system, color_scheme).await?;
+                        let success = auth::register::run_registration(auth_system, ui.color_scheme()).await?;
                         if success {
                             utils::clear_screen()?;
-                            color_scheme.print_success("\n  [✓] Registration successful! Please login with your new credentials.\n\n")?;
-                            sleep(Duration::from_secs(2)).await;
+                            ui.color_scheme().print_success("\n  [✓] Registration successful! Please login with your new credentials.\n\n")?;
+                            ui::animations::pause(Duration::from_secs(2), ui.color_scheme()).await;
                         }
                         utils::clear_screen()?;  // Clear screen before showing menu again
                         needs_redraw = true;  // Force menu redraw
@@ -128,7 +215,7 @@ async fn show_entry_menu(
                     }
                     ui::menu::MenuAction::Login => {
                         terminal::disable_raw_mode()?;
-                        let success = auth::login::run_login(auth_system, color_scheme).await?;
+                        let success = auth::login::run_login(auth_system, ui.color_scheme()).await?;
                         if success {
                             return Ok(ui::menu::MenuAction::Continue);  // Only return on successful login
                         }
@@ -152,125 +239,441 @@ async fn show_entry_menu(
 
 /// Run the main terminal session after login
 async fn run_terminal_session(
-    user: auth::User,
+    mut user: auth::User,
     auth_system: &mut auth::AuthSystem,
     ui: &mut ui::RedlineUI,
+    config: &mut utils::Config,
+    options: &LaunchOptions,
 ) -> Result<()> {
     // Clear screen and show welcome
     utils::clear_screen()?;
-    show_welcome_message(&user, ui.color_scheme()).await?;
+    let motd = if config.display.show_motd && !options.no_motd {
+        Some(utils::motd::message_of_the_day())
+    } else {
+        None
+    };
+    
+    // Resume the agent's saved game, or start a fresh one
+    let mut command_handler = match commands::CommandHandler::load_or_new_with_config(&user, config.clone()) {
+        Ok(handler) => handler,
+        Err(e) => {
+            // Playing on would mean a session that can never be saved
+            ui.color_scheme().print_error(&format!("\n  [!] Could not load your saved game: {}\n", e))?;
+            ui.color_scheme().print_dim("  Your save is untouched. Log in with a build that can read it.\n")?;
+            auth_system.logout();
+            ui::animations::pause(Duration::from_secs(3), ui.color_scheme()).await;
+            return Ok(());
+        }
+    };
+    let welcome = command_handler.game_state().await.welcome_achievement(user.login_count);
+    show_welcome_message(&user, auth_system.previous_login(), motd.as_deref(), welcome.as_ref(), ui.color_scheme()).await?;
     
-    // Initialize command handler with user's game state
-    let mut command_handler = commands::CommandHandler::new(&user);
+    let mut session_log = utils::session_log::SessionLog::for_user(&user.username, &config.logging).unwrap_or_else(|e| {
+        eprintln!("Warning: could not open session log: {}", e);
+        None
+    });
+    log_activity(&session_log, "session", &format!("login as {}", user.username));
     
     // Initialize event manager
-    let mut event_manager = game::events::EventManager::new();
+    let mut event_manager = game::events::EventManager::with_enabled(config.game.enable_random_events);
+    event_manager.autosave_before_irreversible = config.game.pre_event_autosave_enabled();
+    event_manager.adaptive_difficulty = config.game.adaptive_difficulty;
+    let difficulty = game::difficulty::DifficultyModifier::for_difficulty(config.game.difficulty);
+    event_manager.event_chance = config.game.event_chance() * difficulty.event_chance;
+    event_manager.cooldown = config.game.event_cooldown();
+    match game::event_pack::EventPack::load() {
+        Ok(pack) => {
+            for warning in &pack.warnings {
+                ui.color_scheme().print_warning(&format!("  [!] events.json: {}\n", warning))?;
+            }
+            event_manager.custom_events = pack.events;
+        }
+        Err(e) => ui.color_scheme().print_warning(&format!("  [!] Custom events not loaded: {}\n", e))?,
+    }
+    let event_manager = std::sync::Arc::new(tokio::sync::Mutex::new(event_manager));
+    
+    // Heat decay and event rolls run on a timer, independent of typing;
+    // the decay rate is sent again whenever settings change
+    let (decay_rate, decay_rate_rx) = tokio::sync::watch::channel(difficulty.scale_heat_decay(config.game.heat_decay_rate));
+    let ticker = game::ticker::spawn_ticker(
+        command_handler.shared_state(),
+        event_manager.clone(),
+        decay_rate_rx,
+        game::ticker::TICK_INTERVAL,
+    );
+    
+    // How often the input loop wakes while waiting for a key
+    let input_tick = Duration::from_millis(config.game.input_tick_ms.max(1));
+    
+    // Command prompt
+    let mut prompt = ui::menu::CommandPrompt::new(user.username.clone());
+    let mut completer = commands::completion::Completer::new(command_handler.registry());
     
-    // Command prompt (removed mut as it's not needed)
-    let prompt = ui::menu::CommandPrompt::new(user.username.clone());
+    // Idle sessions expire; any keystroke counts as activity
+    let session_timeout = config.security.session_timeout();
+    let mut last_activity = std::time::Instant::now();
+    
+    // Periodic saves so a crash doesn't lose the whole session
+    let mut autosaver = game::autosave::Autosaver::new(
+        config.game.autosave_every_actions(),
+        config.game.autosave_interval(),
+    );
+    
+    // Last auto-hint shown, so the same tip isn't repeated every prompt
+    let mut last_hint: Option<String> = None;
+    
+    // Set once the agent deletes their account, so nothing is saved back
+    let mut account_deleted = false;
     
     // Main terminal loop
     loop {
-        // Check for random events
-        if let Some(event) = event_manager.generate_event(
-            command_handler.game_state().heat_level,
-            command_handler.game_state().reputation,
-        ) {
+        // Show random events raised by the ticker since the last prompt
+        let new_events = event_manager.lock().await.take_new_events();
+        for event in new_events {
             display_random_event(&event, ui.color_scheme()).await?;
+            log_activity(&session_log, "event", &format!("{} ({})", event.title, event.id));
             
-            // Handle timed events
-            if event.time_limit.is_some() {
-                ui.color_scheme().print_warning("  [!] This event has a time limit!\n")?;
+            // Timed events apply a default outcome if left unanswered
+            if let Some(limit) = event.time_limit {
+                ui.color_scheme().print_warning(&format!(
+                    "  [!] Respond within {} seconds or the choice is made for you!\n",
+                    limit.as_secs()
+                ))?;
+            }
+            
+            if event.choices.is_empty() {
+                continue;
+            }
+            let choice = read_event_choice(&event, ui.color_scheme())?;
+            let picked = choice.and_then(|i| event.choices.get(i)).map_or("timed out", |c| c.label.as_str());
+            log_activity(&session_log, "event", &format!("{}: {}", event.id, picked));
+            let mut state = command_handler.game_state().await;
+            let mut manager = event_manager.lock().await;
+            match choice {
+                Some(index) => manager.handle_choice(&event.id, index, &mut state, ui.color_scheme()).await?,
+                None => manager.handle_timeout(&event.id, &mut state, ui.color_scheme())?,
             }
         }
+        announce_achievements(&command_handler, ui).await?;
         
-        // Apply heat decay
-        let mut game_state = command_handler.game_state().clone();
-        game_state.apply_heat_decay(0.99);
-        command_handler.update_game_state(game_state);
+        let heat = command_handler.game_state().await.heat_level;
+        if autosaver.due(heat) {
+            match command_handler.save_game().await {
+                Ok(()) => ui.color_scheme().print_dim("  [autosaved]\n")?,
+                Err(e) => ui.color_scheme().print_warning(&format!("  [!] Autosave failed: {}\n", e))?,
+            }
+            // Failures wait for the next trigger rather than retrying every prompt
+            autosaver.saved(heat);
+        }
         
-        // Display command prompt
-print!("\n");
-prompt.display(ui.color_scheme())?;
-
-// Get user input with proper debouncing
-terminal::enable_raw_mode()?;
-let mut input = String::new();
-
-loop {
-    // Use blocking read to get exactly one key event
-    let key = loop {
-        if let Ok(Event::Key(k)) = event::read() {
-            break k;
+        // Auto-hints for new players
+        if config.game.auto_hints && command_handler.game_state().await.get_level() == 1 {
+            if let Some(hint) = command_handler.current_hint().await {
+                if last_hint.as_ref() != Some(&hint) {
+                    ui.color_scheme().print_dim(&format!("\n  [hint] {}\n", hint))?;
+                    last_hint = Some(hint);
+                }
+            }
         }
-    };
-    
-    match key.code {
-        KeyCode::Enter => {
-            terminal::disable_raw_mode()?;
-            println!();
-            break;
+        
+        // Display command prompt
+        println!();
+        {
+            let state = command_handler.game_state().await;
+            prompt.set_username(state.display_name());
+            prompt.set_host(state.current_target.as_deref());
+            prompt.set_stealth(state.stealth);
         }
-        KeyCode::Backspace => {
-            if !input.is_empty() {
-                input.pop();
-                print!("\x08 \x08");
-                io::stdout().flush()?;
+        prompt.display(ui.color_scheme())?;
+        
+        // Get user input with proper debouncing
+        terminal::enable_raw_mode()?;
+        // Typed text can include secrets such as decryption keys, so it is zeroed on drop;
+        // replace it wholesale rather than assigning through the buffer
+        let mut input = zeroize::Zeroizing::new(String::new());
+        let mut escaped = false;
+        let mut expired = false;
+        
+        loop {
+            // Poll instead of blocking so this task wakes every tick and the
+            // background ticker keeps running while the player is thinking
+            let key = loop {
+                if let Some(k) = ui::next_typeahead() {
+                    break Some(k);
+                }
+                if event::poll(input_tick)? {
+                    match event::read()? {
+                        Event::Key(k) => break Some(k),
+                        Event::Resize(width, height) => {
+                            // Keep later layout in step and redraw the line being typed
+                            ui.resize(width, height);
+                            execute!(io::stdout(), cursor::MoveToColumn(0), terminal::Clear(terminal::ClearType::CurrentLine))?;
+                            prompt.display(ui.color_scheme())?;
+                            print!("{}", input.as_str());
+                            io::stdout().flush()?;
+                        }
+                        _ => {}
+                    }
+                } else if session_timeout.is_some_and(|timeout| last_activity.elapsed() >= timeout) {
+                    break None;
+                } else {
+                    tokio::task::yield_now().await;
+                }
+            };
+            let Some(key) = key else {
+                terminal::disable_raw_mode()?;
+                expired = true;
+                break;
+            };
+            last_activity = std::time::Instant::now();
+            
+            if key.code != KeyCode::Tab {
+                completer.reset();
+            }
+            
+            match key.code {
+                KeyCode::Enter => {
+                    terminal::disable_raw_mode()?;
+                    println!();
+                    break;
+                }
+                KeyCode::Tab => {
+                    let targets: Vec<String> = command_handler.game_state().await
+                        .network_map.discovered_nodes.iter()
+                        .map(|node| node.ip.clone())
+                        .collect();
+                    if let Some(completion) = completer.complete(&input, &targets) {
+                        if completion.candidates.is_empty() {
+                            print!("{}{}", ui::erase_columns(input.width()), completion.line);
+                        } else {
+                            // List the options below, then redraw the prompt
+                            print!("\r\n  {}\r\n", completion.candidates.join("  "));
+                            prompt.display(ui.color_scheme())?;
+                            print!("{}", completion.line);
+                        }
+                        io::stdout().flush()?;
+                        input = zeroize::Zeroizing::new(completion.line);
+                    }
+                }
+                KeyCode::Backspace if !input.is_empty() => {
+                    let columns = ui::pop_char(&mut input);
+                    print!("{}", ui::erase_columns(columns));
+                    io::stdout().flush()?;
+                }
+                KeyCode::Char(c) => {
+                    input.push(c);
+                    print!("{}", c);
+                    io::stdout().flush()?;
+                }
+                KeyCode::Up | KeyCode::Down => {
+                    let history = command_handler.history_mut();
+                    let recalled = if key.code == KeyCode::Up { history.older() } else { history.newer() };
+                    let recalled = recalled.unwrap_or_default().to_string();
+                    
+                    // Erase what's typed and show the recalled command in its place
+                    print!("{}{}", ui::erase_columns(input.width()), recalled);
+                    io::stdout().flush()?;
+                    input = zeroize::Zeroizing::new(recalled);
+                }
+                KeyCode::Esc => {
+                    terminal::disable_raw_mode()?;
+                    input = zeroize::Zeroizing::new("logout".to_string());
+                    escaped = true;
+                    println!();
+                    break;
+                }
+                _ => {}
+            }
+            
+            // Clear any buffered events to prevent key repeat
+            while event::poll(Duration::from_millis(0))? {
+                let _ = event::read()?;
             }
         }
-        KeyCode::Char(c) => {
-            input.push(c);
-            print!("{}", c);
-            io::stdout().flush()?;
-        }
-        KeyCode::Esc => {
-            terminal::disable_raw_mode()?;
-            input = "logout".to_string();
+        
+        if expired {
             println!();
+            println!();
+            ui.color_scheme().print_error("  [!] SESSION EXPIRED - reauthenticate\n")?;
+            ui.color_scheme().print_dim("      Logged out after a period of inactivity\n")?;
+            sync_reputation(auth_system, &user, &command_handler).await?;
+            auth_system.logout();
+            ui::animations::pause(Duration::from_millis(1500), ui.color_scheme()).await;
             break;
         }
-        _ => {}
-    }
-    
-    // Clear any buffered events to prevent key repeat
-    while event::poll(Duration::from_millis(0))? {
-        let _ = event::read()?;
-    }
-}
         
         // Process command
+        // Long output pages to the terminal as it is now
+        ui.refresh_dimensions()?;
+        command_handler.set_page_height(ui.dimensions().1);
+        log_activity(&session_log, "cmd", &utils::session_log::redact_command(&input));
+        let before = state_summary(&*command_handler.game_state().await);
         let result = command_handler.execute(&input).await?;
+        let after = state_summary(&*command_handler.game_state().await);
+        if after != before {
+            log_activity(&session_log, "state", &after);
+        }
+        autosaver.record_action();
+        announce_achievements(&command_handler, ui).await?;
+        
+        // Every branch below may end or change the session, so bank reputation first
+        sync_reputation(auth_system, &user, &command_handler).await?;
         
         match result {
             commands::CommandResult::Logout => {
+                // Esc is easy to hit by accident, so it asks first by default
+                if config.game.confirm_logout(escaped)
+                    && !ui::menu::confirm("Log out? Traces not yet cleared", ui.color_scheme())?
+                {
+                    continue;
+                }
                 auth_system.logout();
+                log_activity(&session_log, "session", "logout");
                 show_logout_sequence(&user, ui.color_scheme()).await?;
+                if let (true, Some(log)) = (options.wipe_logs, &session_log) {
+                    match log.wipe() {
+                        Ok(removed) => ui.color_scheme().print_dim(&format!("  [>] Shredded {} log file(s)\n", removed))?,
+                        Err(e) => ui.color_scheme().print_warning(&format!("  [!] Could not wipe logs: {}\n", e))?,
+                    }
+                }
                 break;
             }
             commands::CommandResult::Exit => {
                 break;
             }
-            _ => {
-                // Update user reputation in auth system
-                auth_system.update_reputation(
-                    command_handler.game_state().reputation - user.reputation
-                )?;
+            commands::CommandResult::AdminConsole => {
+                auth::admin::run_console(auth_system, ui.color_scheme()).await?;
+            }
+            commands::CommandResult::Admin(args) => {
+                auth::admin::run_command(auth_system, &args, ui.color_scheme())?;
+            }
+            commands::CommandResult::TwoFactor(args) => {
+                auth::totp::run_command(auth_system, &args, ui.color_scheme())?;
+            }
+            commands::CommandResult::ChangePassword => {
+                auth::passwd::run(auth_system, ui.color_scheme())?;
+            }
+            commands::CommandResult::DeleteAccount => {
+                if let Some(username) = auth::passwd::delete_account(auth_system, ui.color_scheme())? {
+                    let wiped = command_handler.delete_agent_files().await;
+                    let log_wiped = session_log.take().map_or(Ok(0), |log| log.wipe());
+                    if let Err(e) = wiped.and(log_wiped.map(|_| ())) {
+                        ui.color_scheme().print_warning(&format!("  [!] Some files could not be removed: {}\n", e))?;
+                    }
+                    ui.color_scheme().print_success(&format!("  [✓] Agent {} erased. No traces left.\n", username))?;
+                    ui::animations::pause(Duration::from_millis(1500), ui.color_scheme()).await;
+                    account_deleted = true;
+                    break;
+                }
+            }
+            commands::CommandResult::Rename(new_username) => {
+                match auth_system.rename_current_user(&new_username) {
+                    Ok(renamed) => {
+                        let old_username = std::mem::replace(&mut user, renamed).username;
+                        log_activity(&session_log, "session", &format!("renamed {} to {}", old_username, user.username));
+                        let moved = command_handler.rename_agent(&user.username).await;
+                        let log_moved = match &mut session_log {
+                            Some(log) => utils::session_log::SessionLog::path_for_user(&user.username).and_then(|path| log.move_to(path)),
+                            None => Ok(()),
+                        };
+                        if let Err(e) = moved.and(log_moved) {
+                            ui.color_scheme().print_warning(&format!("  [!] Some files kept the old name: {}\n", e))?;
+                        }
+                        ui.color_scheme().print_success(&format!("  [✓] You are now agent {}\n", user.username))?;
+                    }
+                    Err(e) => ui.color_scheme().print_error(&format!("  [!] {}\n", e))?,
+                }
+            }
+            commands::CommandResult::ThemeChanged(theme) => {
+                config.display.color_theme = theme;
+                ui.set_color_scheme(ui::ColorScheme::from_display(&config.display));
             }
+            commands::CommandResult::ConfigChanged(changed) => {
+                *config = *changed;
+                if options.fast {
+                    config.display.typing_speed_ms = 0;
+                    config.display.use_animations = false;
+                }
+                apply_display_settings(&config.display, ui);
+                let difficulty = game::difficulty::DifficultyModifier::for_difficulty(config.game.difficulty);
+                decay_rate.send_replace(difficulty.scale_heat_decay(config.game.heat_decay_rate));
+                let mut manager = event_manager.lock().await;
+                manager.enabled = config.game.enable_random_events;
+                manager.adaptive_difficulty = config.game.adaptive_difficulty;
+                manager.event_chance = config.game.event_chance() * difficulty.event_chance;
+                manager.cooldown = config.game.event_cooldown();
+            }
+            _ => {}
         }
         
         // Check if heat is critical
-        if command_handler.game_state().heat_level >= 100.0 {
+        if command_handler.game_state().await.heat_level >= 100.0 {
+            command_handler.game_state().await.record_bust();
+            log_activity(&session_log, "session", "busted");
             show_busted_sequence(ui.color_scheme()).await?;
             auth_system.logout();
             break;
         }
     }
     
+    ticker.abort();
+    
+    // Logout, exit and busts all end up here, so progress is saved exactly once
+    if !account_deleted {
+        command_handler.save_game().await?;
+    }
+    Ok(())
+}
+
+/// Copy reputation earned since the last sync, from commands and events alike,
+/// onto the logged-in account so the next login restores it
+async fn sync_reputation(
+    auth_system: &mut auth::AuthSystem,
+    user: &auth::User,
+    command_handler: &commands::CommandHandler,
+) -> Result<()> {
+    let stored = auth_system.current_user().map_or(user.reputation, |u| u.reputation);
+    let current = command_handler.game_state().await.reputation;
+    auth_system.update_reputation(current - stored)
+}
+
+/// Write to the session log if it's enabled. Best effort: a full disk
+/// shouldn't end the session
+fn log_activity(log: &Option<utils::session_log::SessionLog>, kind: &str, message: &str) {
+    if let Some(log) = log {
+        let _ = log.record(kind, message);
+    }
+}
+
+/// One-line summary of the numbers worth logging when they change
+fn state_summary(state: &GameState) -> String {
+    format!(
+        "level {} | rep {} | credits {} | heat {:.0}%",
+        state.get_level(), state.reputation, state.credits, state.heat_level
+    )
+}
+
+/// Pop up a notification for each achievement earned since the last check
+async fn announce_achievements(command_handler: &commands::CommandHandler, ui: &ui::RedlineUI) -> Result<()> {
+    let unlocked = command_handler.game_state().await.check_achievements();
+    for achievement in unlocked {
+        println!();
+        ui.show_notification(
+            &format!("{} ACHIEVEMENT UNLOCKED: {} (+{} pts)", achievement.icon, achievement.name, achievement.points),
+            ACHIEVEMENT_POPUP_MS,
+        ).await?;
+    }
     Ok(())
 }
 
 /// Show welcome message after login
-async fn show_welcome_message(user: &auth::User, color_scheme: &ui::ColorScheme) -> Result<()> {
+async fn show_welcome_message(
+    user: &auth::User,
+    previous_login: Option<&auth::LastLogin>,
+    motd: Option<&str>,
+    achievement: Option<&game::Achievement>,
+    color_scheme: &ui::ColorScheme,
+) -> Result<()> {
     color_scheme.print_colored("\n")?;
     color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
     color_scheme.print_bright(&format!("    WELCOME BACK, AGENT {}\n", user.username.to_uppercase()))?;
@@ -279,7 +682,7 @@ async fn show_welcome_message(user: &auth::User, color_scheme: &ui::ColorScheme)
     
     ui::animations::type_text_effect("Initializing encrypted channel...", 20, color_scheme).await?;
     println!();
-    sleep(Duration::from_millis(500)).await;
+    ui::animations::pause(Duration::from_millis(500), color_scheme).await;
     
     color_scheme.print_success("  [✓] Secure connection established\n")?;
     color_scheme.print_success("  [✓] Identity verified\n")?;
@@ -287,8 +690,12 @@ async fn show_welcome_message(user: &auth::User, color_scheme: &ui::ColorScheme)
     
     println!();
     color_scheme.print_colored("  Last login: ")?;
-    if let Some(last_login) = user.last_login {
-        color_scheme.print_secondary(&format!("{}\n", last_login.format("%Y-%m-%d %H:%M:%S UTC")))?;
+    if let Some(previous) = previous_login {
+        color_scheme.print_secondary(&format!("{}\n", previous.at.format("%Y-%m-%d %H:%M:%S UTC")))?;
+        if let Some(host) = &previous.host {
+            color_scheme.print_colored("  Last login from: ")?;
+            color_scheme.print_secondary(&format!("{}\n", host))?;
+        }
     } else {
         color_scheme.print_secondary("First login\n")?;
     }
@@ -299,6 +706,25 @@ async fn show_welcome_message(user: &auth::User, color_scheme: &ui::ColorScheme)
     color_scheme.print_colored("  Login count: ")?;
     color_scheme.print_secondary(&format!("{}\n", user.login_count))?;
     
+    if let Some(achievement) = achievement {
+        println!();
+        color_scheme.print_success(&format!(
+            "  {} ACHIEVEMENT UNLOCKED: {} (+{} pts)\n",
+            achievement.icon, achievement.name, achievement.points
+        ))?;
+        color_scheme.print_dim(&format!("    {}\n", achievement.description))?;
+    }
+    
+    if let Some(motd) = motd {
+        println!();
+        color_scheme.print_bright("  MESSAGE OF THE DAY\n")?;
+        for line in motd.lines() {
+            color_scheme.print_colored("  > ")?;
+            ui::animations::type_text_effect(line, 15, color_scheme).await?;
+            println!();
+        }
+    }
+    
     println!();
     color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
     color_scheme.print_dim("  Type 'help' for available commands\n")?;
@@ -334,6 +760,18 @@ async fn display_random_event(
     color_scheme.print_secondary(&format!("  {}\n", event.description))?;
     println!();
     
+    display_event_choices(event, color_scheme)?;
+    
+    color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
+    
+    Ok(())
+}
+
+/// Print the numbered choices for a random event
+fn display_event_choices(
+    event: &game::events::RandomEvent,
+    color_scheme: &ui::ColorScheme,
+) -> Result<()> {
     for (i, choice) in event.choices.iter().enumerate() {
         color_scheme.print_colored(&format!("  [{}] {}", i + 1, choice.label))?;
         
@@ -356,31 +794,102 @@ async fn display_random_event(
         println!();
     }
     
-    color_scheme.print_colored("═══════════════════════════════════════════════════════════════\n")?;
-    
     Ok(())
 }
 
+/// Print the choice prompt, with the seconds left when the event is timed
+fn print_choice_prompt(
+    event: &game::events::RandomEvent,
+    remaining: Option<Duration>,
+    color_scheme: &ui::ColorScheme,
+) -> Result<()> {
+    let prompt = match remaining {
+        // Round up so the timer never reads 0s while the player can still answer
+        Some(left) => format!(
+            "\r  Choose [1-{}] ({:>2}s left): ",
+            event.choices.len(),
+            (left.as_millis() as u64).div_ceil(1000)
+        ),
+        None => format!("  Choose [1-{}]: ", event.choices.len()),
+    };
+    color_scheme.print_bright(&prompt)?;
+    io::stdout().flush()?;
+    Ok(())
+}
+
+/// Wait for the player to pick one of an event's choices by number.
+/// Returns `None` if the event's time limit runs out first.
+fn read_event_choice(
+    event: &game::events::RandomEvent,
+    color_scheme: &ui::ColorScheme,
+) -> Result<Option<usize>> {
+    let deadline = event.time_limit.map(|limit| std::time::Instant::now() + limit);
+    let remaining = || deadline.map(|d| d.saturating_duration_since(std::time::Instant::now()));
+    
+    print_choice_prompt(event, remaining(), color_scheme)?;
+    
+    terminal::enable_raw_mode()?;
+    let choice = loop {
+        // Wake at least once a second so the countdown keeps ticking
+        let wait = match remaining() {
+            Some(left) if left.is_zero() => break None,
+            Some(left) => left.min(Duration::from_secs(1)),
+            None => Duration::from_secs(1),
+        };
+        
+        if !event::poll(wait)? {
+            if deadline.is_some() {
+                print_choice_prompt(event, remaining(), color_scheme)?;
+            }
+            continue;
+        }
+        
+        if let Event::Key(key) = event::read()? {
+            if let KeyCode::Char(c) = key.code {
+                if let Some(index) = event.choice_for_key(c) {
+                    break Some(index);
+                }
+            }
+            
+            // Anything else reprints the choices rather than picking one
+            terminal::disable_raw_mode()?;
+            println!();
+            color_scheme.print_error("  [!] Invalid choice\n")?;
+            display_event_choices(event, color_scheme)?;
+            print_choice_prompt(event, remaining(), color_scheme)?;
+            terminal::enable_raw_mode()?;
+        }
+    };
+    terminal::disable_raw_mode()?;
+    
+    match choice {
+        Some(index) => println!("{}", index + 1),
+        None => println!(),
+    }
+    
+    Ok(choice)
+}
+
 /// Show logout sequence
 async fn show_logout_sequence(user: &auth::User, color_scheme: &ui::ColorScheme) -> Result<()> {
     println!();
     color_scheme.print_colored("  [>] Initiating logout sequence...\n")?;
-    sleep(Duration::from_millis(500)).await;
+    ui::animations::pause(Duration::from_millis(500), color_scheme).await;
     
     color_scheme.print_colored("  [>] Saving session data...\n")?;
-    sleep(Duration::from_millis(300)).await;
+    ui::animations::pause(Duration::from_millis(300), color_scheme).await;
     
     color_scheme.print_colored("  [>] Clearing traces...\n")?;
-    ui::animations::show_processing("Wiping logs", 1000).await?;
+    ui::animations::show_processing("Wiping logs", 1000, color_scheme).await?;
     
     color_scheme.print_colored("  [>] Disconnecting from CRIMSON-REDLINE network...\n")?;
-    sleep(Duration::from_millis(500)).await;
+    ui::animations::pause(Duration::from_millis(500), color_scheme).await;
     
     println!();
     color_scheme.print_success(&format!("  [✓] Agent {} successfully logged out\n", user.username))?;
     color_scheme.print_dim("  [*] Connection terminated\n")?;
     
-    sleep(Duration::from_secs(2)).await;
+    ui::animations::pause(Duration::from_secs(2), color_scheme).await;
     utils::clear_screen()?;
     
     Ok(())
@@ -392,13 +901,13 @@ async fn show_exit_sequence(color_scheme: &ui::ColorScheme) -> Result<()> {
     ui::animations::glitch_transition(color_scheme).await?;
     
     color_scheme.print_colored("  [>] Shutting down CRIMSON-REDLINE system...\n")?;
-    sleep(Duration::from_millis(500)).await;
+    ui::animations::pause(Duration::from_millis(500), color_scheme).await;
     
     color_scheme.print_colored("  [>] Terminating all connections...\n")?;
-    sleep(Duration::from_millis(300)).await;
+    ui::animations::pause(Duration::from_millis(300), color_scheme).await;
     
     color_scheme.print_colored("  [>] Wiping temporary data...\n")?;
-    sleep(Duration::from_millis(300)).await;
+    ui::animations::pause(Duration::from_millis(300), color_scheme).await;
     
     color_scheme.print_colored("  [>] System shutdown complete.\n")?;
     println!();
@@ -409,7 +918,7 @@ async fn show_exit_sequence(color_scheme: &ui::ColorScheme) -> Result<()> {
         println!();
     }
     
-    sleep(Duration::from_secs(1)).await;
+    ui::animations::pause(Duration::from_secs(1), color_scheme).await;
     
     Ok(())
 }
@@ -421,13 +930,13 @@ async fn show_busted_sequence(color_scheme: &ui::ColorScheme) -> Result<()> {
     color_scheme.print_error("                    !!! SYSTEM COMPROMISED !!!                 \n")?;
     color_scheme.print_error("═══════════════════════════════════════════════════════════════\n")?;
     
-    ui::animations::show_error("HEAT LEVEL CRITICAL - LOCATION TRACED").await?;
+    ui::animations::show_error("HEAT LEVEL CRITICAL - LOCATION TRACED", color_scheme).await?;
     
     color_scheme.print_error("\n  [✗] Your location has been traced!\n")?;
     color_scheme.print_error("  [✗] Security forces have been dispatched!\n")?;
     color_scheme.print_error("  [✗] Emergency disconnect initiated!\n")?;
     
-    sleep(Duration::from_secs(3)).await;
+    ui::animations::pause(Duration::from_secs(3), color_scheme).await;
     
     ui::animations::glitch_transition(color_scheme).await?;
     
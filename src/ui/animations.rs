@@ -1,15 +1,46 @@
 //! Animation effects for CRIMSON-REDLINE
 
 use crate::ui::{ColorScheme, ascii_art};
+use crate::ui::colors::Tone;
 use crossterm::{
+    event::{self, Event, KeyCode, KeyModifiers},
     terminal::{Clear, ClearType},
     cursor,
-    execute,
+    execute, queue,
 };
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
 use anyhow::Result;
 use rand::Rng;
 
+/// Typing speed the per-effect delays were tuned for
+pub const DEFAULT_TYPING_SPEED_MS: u64 = 15;
+
+/// Configured typing speed, from `display.typing_speed_ms`
+static TYPING_SPEED_MS: AtomicU64 = AtomicU64::new(DEFAULT_TYPING_SPEED_MS);
+
+/// Set the base typing speed that scales every typing effect; 0 types instantly
+pub fn set_typing_speed(speed_ms: u64) {
+    TYPING_SPEED_MS.store(speed_ms, Ordering::Relaxed);
+}
+
+/// Scale an effect's per-character delay to the given typing speed
+pub fn scale_typing_delay(delay_ms: u64, speed_ms: u64) -> u64 {
+    delay_ms * speed_ms / DEFAULT_TYPING_SPEED_MS
+}
+
+/// Per-character delay for an effect tuned at `delay_ms`, at the configured speed
+pub fn typing_delay(delay_ms: u64) -> std::time::Duration {
+    std::time::Duration::from_millis(scale_typing_delay(delay_ms, TYPING_SPEED_MS.load(Ordering::Relaxed)))
+}
+
+/// Wait out a cosmetic delay; returns immediately when the scheme has animations off
+pub async fn pause(duration: std::time::Duration, color_scheme: &ColorScheme) {
+    if color_scheme.animations() {
+        tokio::time::sleep(duration).await;
+    }
+}
+
 /// Show the intro animation
 pub async fn show_intro(color_scheme: &ColorScheme) -> Result<()> {
     execute!(
@@ -39,17 +70,17 @@ pub async fn show_intro(color_scheme: &ColorScheme) -> Result<()> {
                 print!(" ");
             }
             io::stdout().flush()?;
-            tokio::time::sleep(tokio::time::Duration::from_micros(500)).await;
+            pause(tokio::time::Duration::from_micros(500), color_scheme).await;
         }
         println!();
     }
     
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    pause(tokio::time::Duration::from_millis(500), color_scheme).await;
     
     // System initialization text
     execute!(io::stdout(), cursor::MoveTo(0, 15))?;
     type_text_effect("INITIALIZING CRIMSON-REDLINE SYSTEM...", 30, color_scheme).await?;
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    pause(tokio::time::Duration::from_millis(500), color_scheme).await;
     
     println!("\n");
     let init_messages = vec![
@@ -64,10 +95,10 @@ pub async fn show_intro(color_scheme: &ColorScheme) -> Result<()> {
         color_scheme.print_success("  ")?;
         type_text_effect(msg, 15, color_scheme).await?;
         println!();
-        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        pause(tokio::time::Duration::from_millis(200), color_scheme).await;
     }
     
-    tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+    pause(tokio::time::Duration::from_millis(1000), color_scheme).await;
     
     execute!(io::stdout(), cursor::Show)?;
     Ok(())
@@ -75,6 +106,10 @@ pub async fn show_intro(color_scheme: &ColorScheme) -> Result<()> {
 
 /// Matrix rain effect
 pub async fn matrix_rain(color_scheme: &ColorScheme, duration_ms: u64) -> Result<()> {
+    if !color_scheme.animations() {
+        return Ok(());
+    }
+    
     let (width, height) = crossterm::terminal::size()?;
     let mut rng = rand::thread_rng();
     
@@ -87,72 +122,100 @@ pub async fn matrix_rain(color_scheme: &ColorScheme, duration_ms: u64) -> Result
     let duration = tokio::time::Duration::from_millis(duration_ms);
     
     while start.elapsed() < duration {
-        for (x, y) in columns.iter_mut().enumerate() {
-            if *y >= 0 && *y < height as i16 {
-                execute!(io::stdout(), cursor::MoveTo(x as u16, *y as u16))?;
-                
-                // Character selection
-                let ch = if rng.gen::<f32>() < 0.7 {
-                    rng.gen_range(0x30..0x39) as u8 as char  // Numbers
-                } else {
-                    rng.gen_range(0x41..0x5A) as u8 as char  // Letters
-                };
-                
-                // Brightness based on position
-                if *y == height as i16 - 1 {
-                    color_scheme.print_bright(&ch.to_string())?;
-                } else if *y > height as i16 - 5 {
-                    color_scheme.print_colored(&ch.to_string())?;
-                } else {
-                    color_scheme.print_dim(&ch.to_string())?;
-                }
-            }
+        let frame = rain_frame(&mut columns, height, &mut rng, color_scheme)?;
+        write_frame(&frame)?;
+        pause(tokio::time::Duration::from_millis(50), color_scheme).await;
+    }
+    
+    Ok(())
+}
+
+/// Draw one frame of matrix rain into a buffer, moving every column down a row
+fn rain_frame(columns: &mut [i16], height: u16, rng: &mut impl Rng, color_scheme: &ColorScheme) -> Result<Vec<u8>> {
+    let mut frame = Vec::new();
+    
+    for (x, y) in columns.iter_mut().enumerate() {
+        if *y >= 0 && *y < height as i16 {
+            queue!(frame, cursor::MoveTo(x as u16, *y as u16))?;
             
-            *y += 1;
+            // Character selection
+            let ch = if rng.gen::<f32>() < 0.7 {
+                rng.gen_range(0x30..0x39) as u8 as char  // Numbers
+            } else {
+                rng.gen_range(0x41..0x5A) as u8 as char  // Letters
+            };
             
-            // Reset column when it goes off screen
-            if *y >= height as i16 + rng.gen_range(5..15) {
-                *y = -(rng.gen_range(5..20));
-            }
+            // Brightness based on position
+            let tone = if *y == height as i16 - 1 {
+                Tone::Bright
+            } else if *y > height as i16 - 5 {
+                Tone::Primary
+            } else {
+                Tone::Dim
+            };
+            color_scheme.queue_tone(&mut frame, tone, ch.encode_utf8(&mut [0; 4]))?;
         }
         
-        io::stdout().flush()?;
-        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        *y += 1;
+        
+        // Reset column when it goes off screen
+        if *y >= height as i16 + rng.gen_range(5..15) {
+            *y = -(rng.gen_range(5..20));
+        }
     }
     
+    Ok(frame)
+}
+
+/// Send a whole frame to the terminal in one write
+fn write_frame(frame: &[u8]) -> Result<()> {
+    let mut out = io::stdout().lock();
+    out.write_all(frame)?;
+    out.flush()?;
     Ok(())
 }
 
-/// Show loading animation
-pub async fn show_loading(message: &str, duration_ms: u64) -> Result<()> {
-    let frames = vec!["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
-    let color_scheme = ColorScheme::new();
-    
-    let start = tokio::time::Instant::now();
-    let duration = tokio::time::Duration::from_millis(duration_ms);
-    
-    let mut frame_idx = 0;
-    
-    while start.elapsed() < duration {
-        execute!(io::stdout(), cursor::SavePosition)?;
-        color_scheme.print_colored(&format!("{} {}", frames[frame_idx], message))?;
-        execute!(io::stdout(), cursor::RestorePosition)?;
-        
-        frame_idx = (frame_idx + 1) % frames.len();
-        tokio::time::sleep(tokio::time::Duration::from_millis(80)).await;
+/// Frames of the spinner shown while waiting on work of unknown length
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Time each spinner frame stays on screen
+const SPINNER_FRAME_MS: u64 = 80;
+
+/// Spin next to `message` until `work` finishes, then return its output.
+/// With animations off the message is printed once instead
+pub async fn with_spinner<F: std::future::Future>(message: &str, color_scheme: &ColorScheme, work: F) -> Result<F::Output> {
+    if !color_scheme.animations() {
+        color_scheme.print_colored(&format!("  [*] {}...\n", message))?;
+        return Ok(work.await);
     }
     
-    // Clear the loading message
-    execute!(io::stdout(), cursor::SavePosition)?;
-    print!("{}", " ".repeat(message.len() + 3));
-    execute!(io::stdout(), cursor::RestorePosition)?;
+    tokio::pin!(work);
+    let mut ticks = tokio::time::interval(tokio::time::Duration::from_millis(SPINNER_FRAME_MS));
+    let mut frame_idx = 0;
+    let output = loop {
+        tokio::select! {
+            output = &mut work => break output,
+            _ = ticks.tick() => {
+                color_scheme.print_colored(&format!("\r  {} {}...", SPINNER_FRAMES[frame_idx], message))?;
+                io::stdout().flush()?;
+                frame_idx = (frame_idx + 1) % SPINNER_FRAMES.len();
+            }
+        }
+    };
     
-    Ok(())
+    // Settle the spinner into a finished line
+    execute!(io::stdout(), Clear(ClearType::CurrentLine))?;
+    color_scheme.print_colored(&format!("\r  [*] {}... done\n", message))?;
+    Ok(output)
+}
+
+/// Show a spinner for a fixed time
+pub async fn show_loading(message: &str, duration_ms: u64, color_scheme: &ColorScheme) -> Result<()> {
+    with_spinner(message, color_scheme, pause(tokio::time::Duration::from_millis(duration_ms), color_scheme)).await
 }
 
 /// Show processing animation with progress
-pub async fn show_processing(task: &str, duration_ms: u64) -> Result<()> {
-    let color_scheme = ColorScheme::new();
+pub async fn show_processing(task: &str, duration_ms: u64, color_scheme: &ColorScheme) -> Result<()> {
     let steps = 20;
     let step_duration = duration_ms / steps;
     
@@ -160,6 +223,12 @@ pub async fn show_processing(task: &str, duration_ms: u64) -> Result<()> {
     color_scheme.print_colored(&format!("  [*] {}...\n", task))?;
     print!("  [");
     
+    if !color_scheme.animations() {
+        color_scheme.print_bright(&"█".repeat(steps as usize))?;
+        println!("]");
+        return Ok(());
+    }
+    
     for i in 0..steps {
         if i < steps / 3 {
             color_scheme.print_dim("█")?;
@@ -169,7 +238,7 @@ pub async fn show_processing(task: &str, duration_ms: u64) -> Result<()> {
             color_scheme.print_bright("█")?;
         }
         io::stdout().flush()?;
-        tokio::time::sleep(tokio::time::Duration::from_millis(step_duration)).await;
+        pause(tokio::time::Duration::from_millis(step_duration), color_scheme).await;
     }
     
     println!("]");
@@ -178,56 +247,83 @@ pub async fn show_processing(task: &str, duration_ms: u64) -> Result<()> {
 
 /// Type text effect
 pub async fn type_text_effect(text: &str, delay_ms: u64, color_scheme: &ColorScheme) -> Result<()> {
+    let delay = typing_delay(delay_ms);
+    if !color_scheme.animations() || delay.is_zero() {
+        color_scheme.print_colored(text)?;
+        io::stdout().flush()?;
+        return Ok(());
+    }
+    
     for ch in text.chars() {
         color_scheme.print_colored(&ch.to_string())?;
         io::stdout().flush()?;
-        tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
+        pause(delay, color_scheme).await;
     }
     Ok(())
 }
 
 /// Glitch transition effect
 pub async fn glitch_transition(color_scheme: &ColorScheme) -> Result<()> {
+    if !color_scheme.animations() {
+        execute!(io::stdout(), Clear(ClearType::All))?;
+        return Ok(());
+    }
+    
     let (width, height) = crossterm::terminal::size()?;
     let mut rng = rand::thread_rng();
     
     for _ in 0..10 {
-        execute!(io::stdout(), Clear(ClearType::All))?;
-        
-        for _ in 0..rng.gen_range(50..200) {
-            let x = rng.gen_range(0..width);
-            let y = rng.gen_range(0..height);
-            execute!(io::stdout(), cursor::MoveTo(x, y))?;
-            
-            let glitch_char = ['█', '▓', '▒', '░', '▀', '▄'][rng.gen_range(0..6)];
-            color_scheme.print_glitched(&glitch_char.to_string(), 0.5)?;
-        }
-        
-        io::stdout().flush()?;
-        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        let frame = glitch_frame(width, height, &mut rng, color_scheme)?;
+        write_frame(&frame)?;
+        pause(tokio::time::Duration::from_millis(50), color_scheme).await;
     }
     
     execute!(io::stdout(), Clear(ClearType::All))?;
     Ok(())
 }
 
+/// Draw one frame of the glitch transition into a buffer: a cleared screen
+/// scattered with block characters, half of them swapped for bright ones
+fn glitch_frame(width: u16, height: u16, rng: &mut impl Rng, color_scheme: &ColorScheme) -> Result<Vec<u8>> {
+    const GLITCH_CHARS: [char; 6] = ['█', '▓', '▒', '░', '▀', '▄'];
+    let mut frame = Vec::new();
+    queue!(frame, Clear(ClearType::All))?;
+    if width == 0 || height == 0 {
+        return Ok(frame);
+    }
+    
+    for _ in 0..rng.gen_range(50..200) {
+        let x = rng.gen_range(0..width);
+        let y = rng.gen_range(0..height);
+        queue!(frame, cursor::MoveTo(x, y))?;
+        
+        let glitch_char = GLITCH_CHARS[rng.gen_range(0..GLITCH_CHARS.len())];
+        let (tone, ch) = if rng.gen::<f32>() < 0.5 {
+            (Tone::Bright, GLITCH_CHARS[rng.gen_range(0..GLITCH_CHARS.len())])
+        } else {
+            (Tone::Primary, glitch_char)
+        };
+        color_scheme.queue_tone(&mut frame, tone, ch.encode_utf8(&mut [0; 4]))?;
+    }
+    
+    Ok(frame)
+}
+
 /// Show success message with animation
-pub async fn show_success(message: &str) -> Result<()> {
-    let color_scheme = ColorScheme::new();
+pub async fn show_success(message: &str, color_scheme: &ColorScheme) -> Result<()> {
     
     println!();
     print!("  ");
     color_scheme.print_success("[✓] ")?;
-    type_text_effect(message, 20, &color_scheme).await?;
+    type_text_effect(message, 20, color_scheme).await?;
     println!();
     
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    pause(tokio::time::Duration::from_millis(500), color_scheme).await;
     Ok(())
 }
 
 /// Show error message with animation
-pub async fn show_error(message: &str) -> Result<()> {
-    let color_scheme = ColorScheme::new();
+pub async fn show_error(message: &str, color_scheme: &ColorScheme) -> Result<()> {
     
     println!();
     print!("  ");
@@ -241,11 +337,11 @@ pub async fn show_error(message: &str) -> Result<()> {
             color_scheme.print_error(&ch.to_string())?;
         }
         io::stdout().flush()?;
-        tokio::time::sleep(tokio::time::Duration::from_millis(15)).await;
+        pause(tokio::time::Duration::from_millis(15), color_scheme).await;
     }
     println!();
     
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    pause(tokio::time::Duration::from_millis(500), color_scheme).await;
     Ok(())
 }
 
@@ -265,31 +361,107 @@ pub async fn scanning_animation(target: &str, color_scheme: &ColorScheme) -> Res
     for line in scan_lines {
         type_text_effect(line, 10, color_scheme).await?;
         println!();
-        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+        pause(tokio::time::Duration::from_millis(300), color_scheme).await;
     }
     
     color_scheme.print_success("\n  [✓] Scan complete.\n")?;
     Ok(())
 }
 
-/// Decryption animation
-pub async fn decryption_animation(data: &str, color_scheme: &ColorScheme) -> Result<()> {
+/// Stages of the exploit animation with their duration in milliseconds
+pub const EXPLOIT_STAGES: &[(&str, u64)] = &[
+    ("Fingerprinting target", 1000),
+    ("Crafting payload", 1500),
+    ("Delivering exploit", 1000),
+    ("Escalating privileges", 1200),
+];
+
+/// Multi-stage exploit animation; returns false if the player aborts with Esc or Ctrl+C
+pub async fn exploit_animation(target: &str, cve: &str, color_scheme: &ColorScheme) -> Result<bool> {
+    let steps = 20;
+    
+    println!();
+    color_scheme.print_colored(&format!("  [>] Targeting: {}\n", target))?;
+    color_scheme.print_colored(&format!("  [>] Exploit: {}\n", cve))?;
+    color_scheme.print_dim("      (press Esc to abort)\n")?;
+    
+    for (stage, (task, duration_ms)) in EXPLOIT_STAGES.iter().enumerate() {
+        println!();
+        color_scheme.print_colored(&format!("  [>] Stage {}/{}: {}...\n", stage + 1, EXPLOIT_STAGES.len(), task))?;
+        print!("  [");
+        
+        for i in 0..steps {
+            if abort_requested()? {
+                println!("]");
+                return Ok(false);
+            }
+            
+            if i < steps / 2 {
+                color_scheme.print_colored("█")?;
+            } else {
+                color_scheme.print_bright("█")?;
+            }
+            io::stdout().flush()?;
+            pause(tokio::time::Duration::from_millis(*duration_ms / steps), color_scheme).await;
+        }
+        
+        println!("]");
+    }
+    
+    Ok(true)
+}
+
+/// Check for a pending Esc or Ctrl+C without blocking; other keys are kept for the next prompt
+fn abort_requested() -> Result<bool> {
+    // Without a terminal there is no one to press Esc, and raw mode would fail
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    
+    // Raw mode only for the poll, so line endings in the animation still render
+    let _raw_mode = crate::ui::RawModeGuard::enable()?;
+    let mut abort = false;
+    while event::poll(tokio::time::Duration::from_millis(0))? {
+        if let Event::Key(key) = event::read()? {
+            if key.code == KeyCode::Esc
+                || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+            {
+                abort = true;
+            } else {
+                crate::ui::save_typeahead(key);
+            }
+        }
+    }
+    Ok(abort)
+}
+
+/// Decryption animation stretched over `duration`; returns false if the player aborts
+pub async fn decryption_animation(data: &str, duration: tokio::time::Duration, color_scheme: &ColorScheme) -> Result<bool> {
     let mut rng = rand::thread_rng();
     let target = data;
-    let mut current = String::new();
+    let frames_per_char = 5;
+    let length = target.chars().count().max(1) as u32;
+    let frame_delay = duration / (length * frames_per_char);
     
-    // Fill with random characters initially
-    for _ in target.chars() {
-        current.push(rng.gen_range(33..126) as u8 as char);
+    if !color_scheme.animations() {
+        println!();
+        color_scheme.print_success(&format!("  {}\n\n", target))?;
+        color_scheme.print_success("  [✓] Decryption complete.\n")?;
+        return Ok(true);
     }
     
     println!();
-    color_scheme.print_colored("  [>] Decrypting data...\n")?;
+    color_scheme.print_colored("  [>] Decrypting data... (press Esc to abort)\n")?;
     print!("  ");
     
     // Gradually reveal the target text
-    for i in 0..target.len() {
-        for _ in 0..5 {
+    for i in 0..target.chars().count() {
+        if abort_requested()? {
+            println!("\n");
+            return Ok(false);
+        }
+        
+        for _ in 0..frames_per_char {
             execute!(io::stdout(), cursor::SavePosition)?;
             
             // Update random characters for undecrypted positions
@@ -304,19 +476,70 @@ pub async fn decryption_animation(data: &str, color_scheme: &ColorScheme) -> Res
             
             execute!(io::stdout(), cursor::RestorePosition)?;
             io::stdout().flush()?;
-            tokio::time::sleep(tokio::time::Duration::from_millis(30)).await;
+            pause(frame_delay, color_scheme).await;
         }
     }
     
     println!("\n");
     color_scheme.print_success("  [✓] Decryption complete.\n")?;
-    Ok(())
+    Ok(true)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_disabled_animations_return_immediately() {
+        let color_scheme = ColorScheme::new().with_animations(false);
+        let start = std::time::Instant::now();
+        
+        type_text_effect("A fairly long line of text", 500, &color_scheme).await.unwrap();
+        show_processing("Slow task", 10_000, &color_scheme).await.unwrap();
+        show_loading("Loading", 10_000, &color_scheme).await.unwrap();
+        matrix_rain(&color_scheme, 10_000).await.unwrap();
+        let decrypted = decryption_animation("secret", std::time::Duration::from_secs(10), &color_scheme).await.unwrap();
+        pause(std::time::Duration::from_secs(10), &color_scheme).await;
+        
+        assert!(decrypted);
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_frames_are_built_in_one_buffer() {
+        use rand::SeedableRng;
+        let color_scheme = ColorScheme::new();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        
+        // Only the two columns on screen are drawn, but every column falls
+        let mut columns = vec![0, -3, 3];
+        let frame = String::from_utf8(rain_frame(&mut columns, 4, &mut rng, &color_scheme).unwrap()).unwrap();
+        assert_eq!(frame.matches("\x1b[1;1H").count() + frame.matches("\x1b[4;3H").count(), 2);
+        assert_eq!(&columns[..2], &[1, -2]);
+        
+        let glitch = String::from_utf8(glitch_frame(20, 5, &mut rng, &color_scheme).unwrap()).unwrap();
+        assert!(glitch.starts_with("\x1b[2J"));
+        assert!(glitch_frame(0, 0, &mut rng, &color_scheme).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_spinner_hands_back_the_result() {
+        let color_scheme = ColorScheme::new();
+        let work = async {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            42
+        };
+        assert_eq!(with_spinner("Cracking", &color_scheme, work).await.unwrap(), 42);
+        assert_eq!(with_spinner("Instant", &color_scheme, async { "done" }).await.unwrap(), "done");
+    }
+
+    #[test]
+    fn test_typing_delay_scales_with_speed() {
+        assert_eq!(scale_typing_delay(30, DEFAULT_TYPING_SPEED_MS), 30);
+        assert_eq!(scale_typing_delay(30, 30), 60);
+        assert_eq!(scale_typing_delay(20, 0), 0);
+    }
+
     #[tokio::test]
     async fn test_type_text_effect() {
         let color_scheme = ColorScheme::new();
@@ -1,5 +1,63 @@
 //! ASCII art assets for CRIMSON-REDLINE
 
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether output is restricted to plain ASCII; set from `display.ascii_only` or the locale
+static ASCII_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Turn ASCII-only output on or off for the whole application
+pub fn set_ascii_only(ascii_only: bool) {
+    ASCII_ONLY.store(ascii_only, Ordering::Relaxed);
+}
+
+/// Whether output is currently restricted to plain ASCII
+pub fn ascii_only() -> bool {
+    ASCII_ONLY.load(Ordering::Relaxed)
+}
+
+/// Whether the process locale (`LC_ALL`, `LC_CTYPE`, then `LANG`) can display UTF-8
+pub fn locale_supports_unicode() -> bool {
+    locale_is_unicode(|name| std::env::var(name).ok())
+}
+
+/// Check the first locale variable that is set; with none set, only non-Unix terminals are trusted
+fn locale_is_unicode(lookup: impl Fn(&str) -> Option<String>) -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| lookup(name).filter(|value| !value.is_empty()))
+        .map_or(!cfg!(unix), |locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+/// Replace box-drawing with `+-|`, block bars with `#`/`.`, and drop emoji and other symbols
+pub fn to_ascii(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            c if c.is_ascii() => Some(c),
+            '═' | '─' | '━' | '╌' | '┄' => Some('-'),
+            '║' | '│' | '┃' | '╎' | '┆' => Some('|'),
+            '\u{2500}'..='\u{257F}' => Some('+'),
+            '█' | '▓' | '▒' | '■' | '▀' | '▄' | '▌' | '▐' => Some('#'),
+            '░' | '□' | '▪' | '▫' => Some('.'),
+            '•' | '●' => Some('*'),
+            '…' => Some('.'),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Pass text through unchanged, or converted with `to_ascii` in ASCII-only mode
+pub fn render(text: &str) -> Cow<'_, str> {
+    if ascii_only() {
+        Cow::Owned(to_ascii(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
 /// Large skull ASCII art
 pub const SKULL_LARGE: &str = r#"
         ███████████████████████████
@@ -183,7 +241,7 @@ pub const BOOT_SEQUENCE: &str = r#"
 /// Get random glitch pattern
 pub fn get_random_glitch() -> &'static str {
     use rand::Rng;
-    let patterns = vec![
+    let patterns = [
         "▓▓▒▒░░  ░░▒▒▓▓",
         "█▀▄▀█▀▄▀█▀▄▀█",
         "╬╬╬╬╬╬╬╬╬╬╬╬╬",
@@ -200,42 +258,29 @@ pub fn get_random_glitch() -> &'static str {
 
 /// Create a border box
 pub fn create_box(width: usize, height: usize, title: Option<&str>) -> String {
+    let inner = width.saturating_sub(2);
     let mut result = String::new();
     
-    // Top border
+    // Top border; titles too long for the box are cut with '…'
     result.push('╔');
-    if let Some(t) = title {
-        let title_str = format!("═[ {} ]═", t);
-        result.push_str(&title_str);
-        for _ in title_str.len()..width-2 {
-            result.push('═');
-        }
-    } else {
-        for _ in 0..width-2 {
-            result.push('═');
-        }
-    }
+    result.push_str(&crate::ui::top_border(inner, title));
     result.push('╗');
     result.push('\n');
     
     // Middle rows
-    for _ in 0..height-2 {
+    for _ in 0..height.saturating_sub(2) {
         result.push('║');
-        for _ in 0..width-2 {
-            result.push(' ');
-        }
+        result.push_str(&" ".repeat(inner));
         result.push('║');
         result.push('\n');
     }
     
     // Bottom border
     result.push('╚');
-    for _ in 0..width-2 {
-        result.push('═');
-    }
+    result.push_str(&"═".repeat(inner));
     result.push('╝');
     
-    result
+    render(&result).into_owned()
 }
 
 /// Create a progress bar
@@ -252,7 +297,7 @@ pub fn create_progress_bar(progress: f32, width: usize) -> String {
     }
     bar.push(']');
     
-    format!("{} {:.0}%", bar, progress * 100.0)
+    render(&format!("{} {:.0}%", bar, progress * 100.0)).into_owned()
 }
 
 #[cfg(test)]
@@ -261,12 +306,38 @@ mod tests {
 
     #[test]
     fn test_create_box() {
-        let box_str = create_box(10, 5, Some("TEST"));
+        let box_str = create_box(16, 5, Some("TEST"));
         assert!(box_str.contains("TEST"));
         assert!(box_str.contains('╔'));
         assert!(box_str.contains('╝'));
     }
 
+    /// Every row of a box should span exactly `width` columns
+    fn assert_rows_are(box_str: &str, width: usize) {
+        use unicode_width::UnicodeWidthStr;
+        for row in box_str.lines() {
+            assert_eq!(row.width(), width, "misaligned row: {}", row);
+        }
+    }
+
+    #[test]
+    fn test_box_title_widths() {
+        let ascii = create_box(20, 3, Some("STATUS"));
+        assert!(ascii.starts_with("╔═[ STATUS ]═══════╗"));
+        assert_rows_are(&ascii, 20);
+
+        let wide = create_box(24, 3, Some("ＩＮＴＥＬ ─ é"));
+        assert!(wide.contains("ＩＮＴＥＬ ─ é"));
+        assert_rows_are(&wide, 24);
+
+        let long = create_box(16, 3, Some("A TITLE FAR TOO LONG FOR THIS BOX"));
+        assert!(long.starts_with("╔═[ A TITLE… ]═╗"));
+        assert_rows_are(&long, 16);
+        
+        // Degenerate sizes draw what they can rather than underflowing
+        assert_eq!(create_box(1, 1, Some("X")), "╔╗\n╚╝");
+    }
+
     #[test]
     fn test_progress_bar() {
         let bar = create_progress_bar(0.5, 10);
@@ -275,6 +346,25 @@ mod tests {
         assert!(bar.contains('░'));
     }
 
+    #[test]
+    fn test_to_ascii() {
+        assert_eq!(to_ascii("╔═[ OK ]═╗"), "+-[ OK ]-+");
+        assert_eq!(to_ascii("║ │"), "| |");
+        assert_eq!(to_ascii("[██░░] 50%"), "[##..] 50%");
+        assert_eq!(to_ascii("  🏆 First Blood"), "   First Blood");
+    }
+
+    #[test]
+    fn test_locale_detection() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+        };
+        assert!(locale_is_unicode(env(&[("LANG", "en_US.UTF-8")])));
+        assert!(locale_is_unicode(env(&[("LC_ALL", "C.utf8"), ("LANG", "C")])));
+        assert!(!locale_is_unicode(env(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")])));
+        assert!(!locale_is_unicode(env(&[("LANG", "POSIX")])));
+    }
+
     #[test]
     fn test_random_glitch() {
         let glitch = get_random_glitch();
@@ -0,0 +1,55 @@
+//! Shareable agent card for CRIMSON-REDLINE
+
+use crate::ui::ascii_art::create_box;
+use unicode_width::UnicodeWidthStr;
+
+/// Narrowest card, so short stats still look like a card
+const MIN_CARD_WIDTH: usize = 40;
+
+/// Render a bordered card with one aligned `label  value` row per field
+pub fn render_card(title: &str, fields: &[(&str, String)]) -> Vec<String> {
+    let label_width = fields.iter().map(|(label, _)| label.width()).max().unwrap_or(0);
+    let rows: Vec<String> = fields
+        .iter()
+        .map(|(label, value)| format!("{}{}  {}", label, " ".repeat(label_width - label.width()), value))
+        .collect();
+    
+    // Room for the rows plus "║ " and " ║", and for the "═[ title ]═" header
+    let content_width = rows.iter().map(|row| row.width()).max().unwrap_or(0);
+    let width = (content_width + 4).max(title.width() + 8).max(MIN_CARD_WIDTH);
+    
+    let frame = create_box(width, rows.len() + 4, Some(title));
+    let mut lines: Vec<String> = frame.lines().map(String::from).collect();
+    for (i, row) in rows.iter().enumerate() {
+        lines[i + 2] = format!("║ {}{} ║", row, " ".repeat(width - 4 - row.width()));
+    }
+    
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_rows_align() {
+        let card = render_card("AGENT CARD", &[
+            ("AGENT", "ゴースト".to_string()),
+            ("RANK", "Expert Hacker".to_string()),
+            ("CREDITS", "1000".to_string()),
+        ]);
+        
+        assert_eq!(card.len(), 7);
+        let width = card[0].width();
+        assert!(card.iter().all(|line| line.width() == width));
+        assert!(card[2].contains("AGENT    ゴースト"));
+    }
+
+    #[test]
+    fn test_card_grows_for_long_values() {
+        let long = "x".repeat(60);
+        let card = render_card("AGENT CARD", &[("AGENT", long.clone())]);
+        assert_eq!(card[0].width(), card[2].width());
+        assert!(card[2].contains(&long));
+    }
+}
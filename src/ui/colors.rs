@@ -1,12 +1,24 @@
 //! Color management and theming for CRIMSON-REDLINE
 
 use crossterm::{
-    style::{Color, SetForegroundColor, ResetColor, Attribute, SetAttribute},
-    execute,
+    style::{Color, SetForegroundColor, ResetColor, Attribute, SetAttribute, Print},
+    execute, queue,
 };
 use std::io::{self, Write};
 use anyhow::Result;
-use crate::utils::config::ColorTheme;
+use crate::utils::config::{ColorTheme, CustomColors, DisplayConfig};
+
+/// Which ColorScheme style a piece of text is printed in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tone {
+    Primary,
+    Secondary,
+    Bright,
+    Dim,
+    Success,
+    Warning,
+    Error,
+}
 
 /// Color scheme handler for the terminal
 #[derive(Debug, Clone)]
@@ -19,6 +31,14 @@ pub struct ColorScheme {
     dim: Color,
     bright: Color,
     theme: ColorTheme,
+    /// Whether effects drawn in this scheme animate, from `display.use_animations`
+    animations: bool,
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ColorScheme {
@@ -27,9 +47,47 @@ impl ColorScheme {
         Self::from_theme(ColorTheme::Crimson)
     }
 
+    /// Create the color scheme configured in the display settings
+    pub fn from_display(display: &DisplayConfig) -> Self {
+        let scheme = match (&display.color_theme, &display.custom_colors) {
+            (ColorTheme::Custom, Some(colors)) => Self::from_custom(colors),
+            (theme, _) => Self::from_theme(theme.clone()),
+        };
+        scheme.with_animations(display.use_animations)
+    }
+
+    /// The same scheme with animations turned on or off
+    pub fn with_animations(mut self, animations: bool) -> Self {
+        self.animations = animations;
+        self
+    }
+
+    /// Whether animations play; when off, effects print their final state at once
+    pub fn animations(&self) -> bool {
+        self.animations
+    }
+
+    /// Create a color scheme from a custom palette
+    pub fn from_custom(colors: &CustomColors) -> Self {
+        let rgb = |[r, g, b]: [u8; 3]| Color::Rgb { r, g, b };
+        ColorScheme {
+            primary: rgb(colors.primary),
+            secondary: rgb(colors.secondary),
+            error: rgb(colors.error),
+            warning: rgb(colors.warning),
+            success: rgb(colors.success),
+            dim: rgb(colors.dim),
+            bright: rgb(colors.bright),
+            theme: ColorTheme::Custom,
+            animations: true,
+        }
+    }
+
     /// Create color scheme from theme
     pub fn from_theme(theme: ColorTheme) -> Self {
         match theme {
+            // Without a palette to read, custom falls back to crimson
+            ColorTheme::Custom => Self::from_theme(ColorTheme::Crimson),
             ColorTheme::Crimson => ColorScheme {
                 primary: Color::Rgb { r: 220, g: 20, b: 60 },      // Crimson
                 secondary: Color::Rgb { r: 178, g: 34, b: 34 },    // Firebrick
@@ -39,6 +97,7 @@ impl ColorScheme {
                 dim: Color::Rgb { r: 139, g: 0, b: 0 },            // Dark red
                 bright: Color::Rgb { r: 255, g: 69, b: 0 },        // Red-orange
                 theme,
+                animations: true,
             },
             ColorTheme::Blood => ColorScheme {
                 primary: Color::Rgb { r: 136, g: 8, b: 8 },        // Blood red
@@ -49,6 +108,7 @@ impl ColorScheme {
                 dim: Color::Rgb { r: 64, g: 0, b: 0 },             // Very dark red
                 bright: Color::Rgb { r: 170, g: 0, b: 0 },         // Medium blood
                 theme,
+                animations: true,
             },
             ColorTheme::Neon => ColorScheme {
                 primary: Color::Rgb { r: 255, g: 16, b: 70 },      // Neon red
@@ -59,6 +119,7 @@ impl ColorScheme {
                 dim: Color::Rgb { r: 199, g: 21, b: 133 },         // Medium violet
                 bright: Color::Rgb { r: 255, g: 20, b: 147 },      // Deep pink
                 theme,
+                animations: true,
             },
             ColorTheme::Terminal => ColorScheme {
                 primary: Color::Rgb { r: 0, g: 255, b: 0 },        // Classic green
@@ -69,6 +130,7 @@ impl ColorScheme {
                 dim: Color::Rgb { r: 0, g: 128, b: 0 },            // Dark green
                 bright: Color::Rgb { r: 127, g: 255, b: 0 },       // Chartreuse
                 theme,
+                animations: true,
             },
         }
     }
@@ -79,7 +141,7 @@ impl ColorScheme {
             io::stdout(),
             SetForegroundColor(self.primary)
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             ResetColor
@@ -93,7 +155,7 @@ impl ColorScheme {
             io::stdout(),
             SetForegroundColor(self.secondary)
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             ResetColor
@@ -108,7 +170,7 @@ impl ColorScheme {
             SetForegroundColor(self.error),
             SetAttribute(Attribute::Bold)
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             SetAttribute(Attribute::Reset),
@@ -123,7 +185,7 @@ impl ColorScheme {
             io::stdout(),
             SetForegroundColor(self.warning)
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             ResetColor
@@ -138,7 +200,7 @@ impl ColorScheme {
             SetForegroundColor(self.success),
             SetAttribute(Attribute::Bold)
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             SetAttribute(Attribute::Reset),
@@ -154,7 +216,7 @@ impl ColorScheme {
             SetForegroundColor(self.dim),
             SetAttribute(Attribute::Dim)
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             SetAttribute(Attribute::Reset),
@@ -170,7 +232,7 @@ impl ColorScheme {
             SetForegroundColor(self.bright),
             SetAttribute(Attribute::Bold)
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             SetAttribute(Attribute::Reset),
@@ -186,7 +248,7 @@ impl ColorScheme {
             SetForegroundColor(self.primary),
             SetAttribute(Attribute::SlowBlink)
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             SetAttribute(Attribute::Reset),
@@ -202,7 +264,7 @@ impl ColorScheme {
             SetForegroundColor(self.primary),
             SetAttribute(Attribute::Underlined)
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             SetAttribute(Attribute::Reset),
@@ -211,6 +273,31 @@ impl ColorScheme {
         Ok(())
     }
 
+    /// Queue text in a tone onto a writer without flushing, so many small
+    /// pieces can go out in one write; styled the same as the `print_*` methods
+    pub fn queue_tone<W: Write>(&self, out: &mut W, tone: Tone, text: &str) -> Result<()> {
+        let (color, attribute) = match tone {
+            Tone::Primary => (self.primary, None),
+            Tone::Secondary => (self.secondary, None),
+            Tone::Bright => (self.bright, Some(Attribute::Bold)),
+            Tone::Dim => (self.dim, Some(Attribute::Dim)),
+            Tone::Success => (self.success, Some(Attribute::Bold)),
+            Tone::Warning => (self.warning, None),
+            Tone::Error => (self.error, Some(Attribute::Bold)),
+        };
+        
+        queue!(out, SetForegroundColor(color))?;
+        if let Some(attribute) = attribute {
+            queue!(out, SetAttribute(attribute))?;
+        }
+        queue!(out, Print(crate::ui::ascii_art::render(text)))?;
+        if attribute.is_some() {
+            queue!(out, SetAttribute(Attribute::Reset))?;
+        }
+        queue!(out, ResetColor)?;
+        Ok(())
+    }
+
     /// Set terminal to primary color (doesn't print)
     pub fn set_primary(&self) -> Result<()> {
         execute!(
@@ -245,7 +332,7 @@ impl ColorScheme {
             io::stdout(),
             SetForegroundColor(Color::Rgb { r, g, b })
         )?;
-        print!("{}", text);
+        print!("{}", crate::ui::ascii_art::render(text));
         execute!(
             io::stdout(),
             ResetColor
@@ -304,6 +391,19 @@ mod tests {
         assert!(matches!(scheme.theme(), ColorTheme::Crimson));
     }
 
+    #[test]
+    fn test_queue_tone_buffers_styled_text() {
+        let scheme = ColorScheme::new();
+        let mut out = Vec::new();
+        scheme.queue_tone(&mut out, Tone::Bright, "7").unwrap();
+        scheme.queue_tone(&mut out, Tone::Warning, "A").unwrap();
+        
+        let written = String::from_utf8(out).unwrap();
+        assert!(written.contains("\x1b[1m7\x1b[0m"));
+        assert!(written.contains('A'));
+        assert!(written.ends_with("A\x1b[0m"));
+    }
+
     #[test]
     fn test_theme_switching() {
         let blood_scheme = ColorScheme::from_theme(ColorTheme::Blood);
@@ -312,4 +412,37 @@ mod tests {
         let neon_scheme = ColorScheme::from_theme(ColorTheme::Neon);
         assert!(matches!(neon_scheme.theme(), ColorTheme::Neon));
     }
+
+    #[test]
+    fn test_scheme_carries_the_animation_setting() {
+        assert!(ColorScheme::new().animations());
+        let display = DisplayConfig {
+            use_animations: false,
+            ..DisplayConfig::default()
+        };
+        assert!(!ColorScheme::from_display(&display).animations());
+    }
+
+    #[test]
+    fn test_custom_theme_falls_back_to_crimson() {
+        let mut display = DisplayConfig {
+            color_theme: ColorTheme::Custom,
+            ..DisplayConfig::default()
+        };
+        let fallback = ColorScheme::from_display(&display);
+        assert!(matches!(fallback.theme(), ColorTheme::Crimson));
+
+        display.custom_colors = Some(CustomColors {
+            primary: [1, 2, 3],
+            secondary: [0, 0, 0],
+            error: [0, 0, 0],
+            warning: [0, 0, 0],
+            success: [0, 0, 0],
+            dim: [0, 0, 0],
+            bright: [0, 0, 0],
+        });
+        let custom = ColorScheme::from_display(&display);
+        assert!(matches!(custom.theme(), ColorTheme::Custom));
+        assert_eq!(custom.primary_color(), Color::Rgb { r: 1, g: 2, b: 3 });
+    }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 //! Menu system for CRIMSON-REDLINE
 
 use crate::ui::{ColorScheme, ascii_art};
+use crate::utils::secret::{password_buffer, PasswordBuffer, MAX_PASSWORD_LEN};
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent},
     terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
@@ -9,6 +10,7 @@ use crossterm::{
 };
 use std::io::{self, Write};
 use anyhow::Result;
+use zeroize::Zeroize;
 
 /// Main menu structure
 pub struct MainMenu {
@@ -47,6 +49,14 @@ pub enum MenuAction {
     Mission,
     DarkWeb,
     Firewall,
+    // Admin console
+    AdminListUsers,
+    AdminStats,
+    AdminLockUser,
+    AdminUnlockUser,
+    AdminResetReputation,
+    AdminDeleteUser,
+    AdminSetPassword,
 }
 
 impl MainMenu {
@@ -147,6 +157,31 @@ impl MainMenu {
         }
     }
 
+    /// Create the admin console menu
+    pub fn admin_menu() -> Self {
+        let option = |label: &str, action: MenuAction, description: &str| MenuOption {
+            label: label.to_string(),
+            action,
+            description: description.to_string(),
+        };
+        
+        MainMenu {
+            options: vec![
+                option("LIST USERS", MenuAction::AdminListUsers, "Show every agent with their stats"),
+                option("STORAGE STATS", MenuAction::AdminStats, "Summarize the user database"),
+                option("LOCK USER", MenuAction::AdminLockUser, "Suspend an agent's account"),
+                option("UNLOCK USER", MenuAction::AdminUnlockUser, "Restore a locked-out account"),
+                option("RESET REPUTATION", MenuAction::AdminResetReputation, "Set an agent's reputation to zero"),
+                option("DELETE USER", MenuAction::AdminDeleteUser, "Permanently remove an agent"),
+                option("SET MASTER PASSWORD", MenuAction::AdminSetPassword, "Require a second password to open the console"),
+                option("EXIT CONSOLE", MenuAction::Back, "Return to the terminal"),
+            ],
+            selected_index: 0,
+            title: "ADMIN CONSOLE".to_string(),
+            show_skull: false,
+        }
+    }
+
     /// Display the menu (optimized to prevent flickering)
     pub async fn display(&self, color_scheme: &ColorScheme) -> Result<()> {
         // Move cursor to home position instead of clearing entire screen
@@ -261,6 +296,10 @@ pub struct CommandPrompt {
     history: Vec<String>,
     history_index: Option<usize>,
     username: String,
+    /// Host the session is connected to, shown in place of `crimson`
+    host: Option<String>,
+    /// Whether stealth mode is on, flagged ahead of the label
+    stealth: bool,
 }
 
 impl CommandPrompt {
@@ -271,12 +310,42 @@ impl CommandPrompt {
             history: Vec::new(),
             history_index: None,
             username,
+            host: None,
+            stealth: false,
         }
     }
 
+    /// Change the name shown in the prompt
+    pub fn set_username(&mut self, username: &str) {
+        self.username = username.to_string();
+    }
+
+    /// Change the host shown in the prompt; `None` for the home machine
+    pub fn set_host(&mut self, host: Option<&str>) {
+        self.host = host.map(String::from);
+    }
+
+    /// Flag the prompt while stealth mode is on
+    pub fn set_stealth(&mut self, stealth: bool) {
+        self.stealth = stealth;
+    }
+
+    /// The `user@host` part of the prompt
+    pub fn label(&self) -> String {
+        format!("{}@{}", self.username, self.host.as_deref().unwrap_or("crimson"))
+    }
+
+    /// Tag shown before the label, if any
+    pub fn tag(&self) -> Option<&'static str> {
+        self.stealth.then_some("[stealth] ")
+    }
+
     /// Display the command prompt
     pub fn display(&self, color_scheme: &ColorScheme) -> Result<()> {
-        color_scheme.print_colored(&format!("{}@crimson", self.username))?;
+        if let Some(tag) = self.tag() {
+            color_scheme.print_dim(tag)?;
+        }
+        color_scheme.print_colored(&self.label())?;
         color_scheme.print_bright(":~# ")?;
         color_scheme.print_colored(&self.command)?;
         color_scheme.print_colored("█")?;
@@ -315,7 +384,7 @@ impl CommandPrompt {
                 }
             }
             KeyCode::Backspace => {
-                self.command.pop();
+                crate::ui::pop_char(&mut self.command);
                 None
             }
             KeyCode::Up => {
@@ -364,6 +433,82 @@ impl CommandPrompt {
     }
 }
 
+/// Ask a yes/no question and wait for a single key; anything but 'y' is no
+pub fn confirm(question: &str, color_scheme: &ColorScheme) -> Result<bool> {
+    color_scheme.print_warning(&format!("  [?] {} [y/N] ", question))?;
+    io::stdout().flush()?;
+    
+    let answer = {
+        let _raw_mode = crate::ui::RawModeGuard::enable()?;
+        loop {
+            if let Event::Key(key) = event::read()? {
+                break matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y'));
+            }
+        }
+    };
+    
+    println!("{}", if answer { "y" } else { "n" });
+    Ok(answer)
+}
+
+/// Read a line of input with echo
+pub fn read_line(prompt: &str, color_scheme: &ColorScheme) -> Result<String> {
+    color_scheme.print_colored(&format!("  [>] {}: ", prompt))?;
+    io::stdout().flush()?;
+    
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+/// Read a password, echoing '*' for each character; Esc cancels with an empty string.
+/// The returned buffer is zeroed when dropped
+pub fn read_password(prompt: &str, color_scheme: &ColorScheme) -> Result<PasswordBuffer> {
+    color_scheme.print_colored(&format!("  [>] {}: ", prompt))?;
+    io::stdout().flush()?;
+    
+    crossterm::terminal::enable_raw_mode()?;
+    let mut password = password_buffer();
+    loop {
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Enter => break,
+                KeyCode::Esc => {
+                    password.zeroize();
+                    break;
+                }
+                KeyCode::Backspace if !password.is_empty() => {
+                    password.pop();
+                    print!("\x08 \x08");
+                }
+                KeyCode::Char(c) if password.len() < MAX_PASSWORD_LEN => {
+                    password.push(c);
+                    print!("*");
+                }
+                _ => {}
+            }
+            io::stdout().flush()?;
+        }
+    }
+    crossterm::terminal::disable_raw_mode()?;
+    
+    println!();
+    Ok(password)
+}
+
+/// Wait for any key before continuing
+pub fn wait_for_key(color_scheme: &ColorScheme) -> Result<()> {
+    color_scheme.print_dim("\n  Press any key to continue...")?;
+    io::stdout().flush()?;
+    
+    crossterm::terminal::enable_raw_mode()?;
+    while !matches!(event::read()?, Event::Key(_)) {}
+    crossterm::terminal::disable_raw_mode()?;
+    
+    println!();
+    Ok(())
+}
+
 /// Run menu interaction loop (optimized with alternate screen buffer)
 pub async fn run_menu(menu: &mut MainMenu, color_scheme: &ColorScheme) -> Result<MenuAction> {
     // Enter alternate screen buffer to prevent flickering
@@ -382,15 +527,23 @@ pub async fn run_menu(menu: &mut MainMenu, color_scheme: &ColorScheme) -> Result
     
     let result = loop {
         if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                let action = menu.handle_input(key);
-                
-                if action == MenuAction::Continue {
-                    // Redraw only when navigating
+            match event::read()? {
+                Event::Key(key) => {
+                    let action = menu.handle_input(key);
+                    
+                    if action == MenuAction::Continue {
+                        // Redraw only when navigating
+                        menu.display(color_scheme).await?;
+                    } else {
+                        break action;
+                    }
+                }
+                Event::Resize(..) => {
+                    // Drawing starts at the top left, so clear what the old size left behind
+                    execute!(io::stdout(), Clear(ClearType::All))?;
                     menu.display(color_scheme).await?;
-                } else {
-                    break action;
                 }
+                _ => {}
             }
         }
     };
@@ -420,6 +573,35 @@ mod tests {
         assert_eq!(menu.title, "SYSTEM ACCESS");
     }
 
+    #[test]
+    fn test_admin_menu_exits_back() {
+        let mut menu = MainMenu::admin_menu();
+        assert_eq!(menu.title, "ADMIN CONSOLE");
+        assert_eq!(menu.handle_input(KeyEvent::from(KeyCode::Enter)), MenuAction::AdminListUsers);
+        
+        menu.handle_input(KeyEvent::from(KeyCode::Up));
+        assert_eq!(menu.handle_input(KeyEvent::from(KeyCode::Enter)), MenuAction::Back);
+    }
+
+    #[test]
+    fn test_prompt_shows_connected_host() {
+        let mut prompt = CommandPrompt::new("agent".to_string());
+        assert_eq!(prompt.label(), "agent@crimson");
+        prompt.set_host(Some("10.0.0.5"));
+        assert_eq!(prompt.label(), "agent@10.0.0.5");
+        prompt.set_host(None);
+        assert_eq!(prompt.label(), "agent@crimson");
+    }
+
+    #[test]
+    fn test_prompt_flags_stealth() {
+        let mut prompt = CommandPrompt::new("agent".to_string());
+        assert_eq!(prompt.tag(), None);
+        prompt.set_stealth(true);
+        assert_eq!(prompt.tag(), Some("[stealth] "));
+        assert_eq!(prompt.label(), "agent@crimson");
+    }
+
     #[test]
     fn test_command_prompt() {
         let mut prompt = CommandPrompt::new("testuser".to_string());
@@ -2,20 +2,125 @@
 
 pub mod animations;
 pub mod ascii_art;
+pub mod card;
 pub mod colors;
 pub mod menu;
+pub mod pager;
 
 pub use colors::ColorScheme;
 pub use menu::{MainMenu, MenuOption};
-pub use animations::{show_intro, show_loading, show_processing};
+pub use animations::{show_intro, show_loading, show_processing, with_spinner};
 
 use crossterm::{
-    terminal::{Clear, ClearType, size},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, size},
+    style::ResetColor,
     cursor,
     execute,
 };
+use crossterm::event::KeyEvent;
+use std::collections::VecDeque;
 use std::io::{self, Write};
+use std::sync::Mutex;
 use anyhow::Result;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Keeps the alternate screen active; leaving it and showing the cursor on drop,
+/// so an error or early return never strands the player on a blank screen
+pub struct AlternateScreenGuard<W: Write = io::Stdout> {
+    out: W,
+}
+
+impl AlternateScreenGuard {
+    /// Enter the alternate screen on stdout
+    pub fn enter() -> Result<Self> {
+        Self::enter_on(io::stdout())
+    }
+}
+
+impl<W: Write> AlternateScreenGuard<W> {
+    /// Enter the alternate screen on a specific writer
+    pub fn enter_on(mut out: W) -> Result<Self> {
+        execute!(out, EnterAlternateScreen, cursor::Hide)?;
+        Ok(AlternateScreenGuard { out })
+    }
+}
+
+impl<W: Write> Drop for AlternateScreenGuard<W> {
+    fn drop(&mut self) {
+        // Errors can't propagate from Drop; a failed restore is no worse than none
+        let _ = execute!(self.out, LeaveAlternateScreen, cursor::Show);
+    }
+}
+
+/// Put the terminal back the way the shell expects: cooked mode, main screen, visible cursor
+pub fn restore_terminal() {
+    // Best effort; each step is harmless if the terminal is already in that state
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(io::stdout(), ResetColor, LeaveAlternateScreen, cursor::Show);
+}
+
+/// Restores the terminal when dropped, covering early `?` returns and unwinding panics
+pub struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    /// Create the guard and install a panic hook that restores the terminal before
+    /// the panic message is printed, so it isn't lost on the alternate screen
+    pub fn install() -> Self {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            default_hook(info);
+        }));
+        TerminalGuard { _private: () }
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Keys read while an animation polled for Esc, waiting to be replayed to the prompt
+static TYPEAHEAD: Mutex<VecDeque<KeyEvent>> = Mutex::new(VecDeque::new());
+
+/// Keep a key typed ahead so the next prompt still sees it
+pub fn save_typeahead(key: KeyEvent) {
+    TYPEAHEAD.lock().unwrap_or_else(|e| e.into_inner()).push_back(key);
+}
+
+/// The oldest key typed ahead, if any
+pub fn next_typeahead() -> Option<KeyEvent> {
+    TYPEAHEAD.lock().unwrap_or_else(|e| e.into_inner()).pop_front()
+}
+
+/// Holds raw mode for a key read and puts back the mode that was on before when
+/// dropped, so a caller already in raw mode stays there and a cooked one returns to it
+pub struct RawModeGuard {
+    was_raw: bool,
+}
+
+impl RawModeGuard {
+    /// Switch to raw mode, remembering the current mode
+    pub fn enable() -> Result<Self> {
+        let was_raw = terminal::is_raw_mode_enabled()?;
+        if !was_raw {
+            terminal::enable_raw_mode()?;
+        }
+        Ok(RawModeGuard { was_raw })
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        if !self.was_raw {
+            // Errors can't propagate from Drop; the terminal is restored on exit regardless
+            let _ = terminal::disable_raw_mode();
+        }
+    }
+}
 
 /// Main UI handler for CRIMSON-REDLINE
 pub struct RedlineUI {
@@ -82,75 +187,54 @@ pub async fn initialize(&mut self) -> Result<()> {
     }
 
     /// Update color scheme
-    pub fn set_color_scheme(&mut self, theme: crate::utils::config::ColorTheme) {
-        self.color_scheme = ColorScheme::from_theme(theme);
+    pub fn set_color_scheme(&mut self, color_scheme: ColorScheme) {
+        self.color_scheme = color_scheme;
     }
 
     /// Draw a bordered box
     pub fn draw_box(&self, x: u16, y: u16, width: u16, height: u16, title: Option<&str>) -> Result<()> {
+        let inner = width.saturating_sub(2) as usize;
+        
         // Top border
         execute!(io::stdout(), cursor::MoveTo(x, y))?;
-        self.color_scheme.print_colored("╔")?;
-        
-        if let Some(title) = title {
-            let title_formatted = format!("═[ {} ]═", title);
-            let padding = width.saturating_sub(title_formatted.len() as u16 + 2);
-            self.color_scheme.print_colored(&title_formatted)?;
-            for _ in 0..padding {
-                self.color_scheme.print_colored("═")?;
-            }
-        } else {
-            for _ in 0..width-2 {
-                self.color_scheme.print_colored("═")?;
-            }
-        }
-        self.color_scheme.print_colored("╗")?;
+        self.color_scheme.print_colored(&format!("╔{}╗", top_border(inner, title)))?;
 
         // Side borders
-        for i in 1..height-1 {
-            execute!(io::stdout(), cursor::MoveTo(x, y + i))?;
+        let right = x.saturating_add(width.saturating_sub(1));
+        for i in 1..height.saturating_sub(1) {
+            execute!(io::stdout(), cursor::MoveTo(x, y.saturating_add(i)))?;
             self.color_scheme.print_colored("║")?;
-            execute!(io::stdout(), cursor::MoveTo(x + width - 1, y + i))?;
+            execute!(io::stdout(), cursor::MoveTo(right, y.saturating_add(i)))?;
             self.color_scheme.print_colored("║")?;
         }
 
         // Bottom border
-        execute!(io::stdout(), cursor::MoveTo(x, y + height - 1))?;
-        self.color_scheme.print_colored("╚")?;
-        for _ in 0..width-2 {
-            self.color_scheme.print_colored("═")?;
-        }
-        self.color_scheme.print_colored("╝")?;
+        execute!(io::stdout(), cursor::MoveTo(x, y.saturating_add(height.saturating_sub(1))))?;
+        self.color_scheme.print_colored(&format!("╚{}╝", "═".repeat(inner)))?;
 
         Ok(())
     }
 
     /// Print centered text
     pub fn print_centered(&self, text: &str, y: u16) -> Result<()> {
-        let x = self.terminal_width.saturating_sub(text.len() as u16) / 2;
+        let x = self.terminal_width.saturating_sub(columns(text)) / 2;
         execute!(io::stdout(), cursor::MoveTo(x, y))?;
         self.color_scheme.print_colored(text)?;
         Ok(())
     }
 
-    /// Show a notification message
+    /// Show a notification message in a box printed after the current output,
+    /// so nothing already on screen or in scrollback is written over
     pub async fn show_notification(&self, message: &str, duration_ms: u64) -> Result<()> {
-        let box_width = (message.len() + 6).min(self.terminal_width as usize - 4) as u16;
-        let box_height = 5;
-        let x = (self.terminal_width - box_width) / 2;
-        let y = self.terminal_height / 2 - box_height / 2;
-
-        // Draw notification box
-        self.draw_box(x, y, box_width, box_height, Some("NOTIFICATION"))?;
-        
-        // Print message
-        execute!(io::stdout(), cursor::MoveTo(x + 2, y + 2))?;
-        self.color_scheme.print_colored(message)?;
-        
+        let box_width = columns(message).saturating_add(6).min(self.terminal_width.saturating_sub(4));
+        let indent = " ".repeat((self.terminal_width.saturating_sub(box_width) / 2) as usize);
+        for line in notification_box(message, box_width as usize) {
+            self.color_scheme.print_colored(&format!("{}{}\n", indent, line))?;
+        }
         io::stdout().flush()?;
         
         // Wait
-        tokio::time::sleep(tokio::time::Duration::from_millis(duration_ms)).await;
+        animations::pause(tokio::time::Duration::from_millis(duration_ms), &self.color_scheme).await;
         
         Ok(())
     }
@@ -158,11 +242,11 @@ pub async fn initialize(&mut self) -> Result<()> {
     /// Show a progress bar
     pub async fn show_progress(&self, title: &str, progress: f32) -> Result<()> {
         let bar_width = 40;
-        let filled = (bar_width as f32 * progress) as usize;
+        let filled = (bar_width as f32 * progress.clamp(0.0, 1.0)) as usize;
         let empty = bar_width - filled;
         
         let y = self.terminal_height / 2;
-        let x = (self.terminal_width - bar_width as u16 - 10) / 2;
+        let x = self.terminal_width.saturating_sub(bar_width as u16 + 10) / 2;
         
         execute!(io::stdout(), cursor::MoveTo(x, y))?;
         self.color_scheme.print_colored(title)?;
@@ -223,6 +307,87 @@ pub async fn initialize(&mut self) -> Result<()> {
         self.terminal_height = height;
         Ok(())
     }
+
+    /// Record a new terminal size reported by a resize event
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.terminal_width = width;
+        self.terminal_height = height;
+    }
+}
+
+/// Display width of text in terminal columns, saturating at `u16::MAX`
+fn columns(text: &str) -> u16 {
+    u16::try_from(text.width()).unwrap_or(u16::MAX)
+}
+
+/// Cut text to at most `max` columns, ending in '…' when anything was dropped
+pub fn fit_width(text: &str, max: usize) -> String {
+    if text.width() <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut fitted = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > max - 1 {
+            break;
+        }
+        fitted.push(c);
+        used += w;
+    }
+    fitted.push('…');
+    fitted
+}
+
+/// Remove the last typed character along with any combining marks after it,
+/// returning the columns it took up on screen
+pub fn pop_char(text: &mut String) -> usize {
+    let mut columns = 0;
+    while let Some(c) = text.pop() {
+        columns += c.width().unwrap_or(0);
+        if columns > 0 {
+            break;
+        }
+    }
+    columns
+}
+
+/// Terminal output that erases the `columns` columns before the cursor
+pub fn erase_columns(columns: usize) -> String {
+    format!("{0}{1}{0}", "\x08".repeat(columns), " ".repeat(columns))
+}
+
+/// Lines of a notification box `width` columns wide, the message cut to fit inside
+fn notification_box(message: &str, width: usize) -> Vec<String> {
+    let inner = width.saturating_sub(2);
+    let text = fit_width(message, inner.saturating_sub(2));
+    let padding = inner.saturating_sub(text.width() + 1);
+    let blank = format!("║{}║", " ".repeat(inner));
+    vec![
+        format!("╔{}╗", top_border(inner, Some("NOTIFICATION"))),
+        blank.clone(),
+        format!("║ {}{}║", text, " ".repeat(padding)),
+        blank,
+        format!("╚{}╝", "═".repeat(inner)),
+    ]
+}
+
+/// Top border between the corners, `inner` columns wide, with the title if it fits
+pub(crate) fn top_border(inner: usize, title: Option<&str>) -> String {
+    let Some(title) = title else {
+        return "═".repeat(inner);
+    };
+
+    // "═[ " and " ]═" take six columns around the title
+    let formatted = format!("═[ {} ]═", fit_width(title, inner.saturating_sub(6)));
+    match inner.checked_sub(formatted.width()) {
+        Some(padding) => format!("{}{}", formatted, "═".repeat(padding)),
+        None => "═".repeat(inner),
+    }
 }
 
 /// Line drawing styles
@@ -253,23 +418,101 @@ pub fn create_glitch(text: &str, intensity: f32) -> String {
 
 /// Type text effect character by character
 pub async fn type_text(text: &str, delay_ms: u64, color_scheme: &ColorScheme) -> Result<()> {
-    for ch in text.chars() {
-        color_scheme.print_colored(&ch.to_string())?;
-        io::stdout().flush()?;
-        tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
-    }
-    Ok(())
+    animations::type_text_effect(text, delay_ms, color_scheme).await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn narrow_ui() -> RedlineUI {
+        RedlineUI {
+            color_scheme: ColorScheme::new(),
+            terminal_width: 10,
+            terminal_height: 3,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_narrow_terminal_does_not_panic() {
+        let ui = narrow_ui();
+        ui.print_centered("ＷＩＤＥ ＣＥＮＴＥＲＥＤ ＴＥＸＴ", 0).unwrap();
+        ui.draw_box(0, 0, 10, 3, Some("ＮＯＴＩＦＩＣＡＴＩＯＮ")).unwrap();
+        ui.draw_box(8, 0, 1, 0, Some("tiny")).unwrap();
+        ui.show_notification("a notification far wider than ten columns", 0).await.unwrap();
+        ui.show_progress("Uploading", 1.5).await.unwrap();
+    }
+
+    #[test]
+    fn test_backspace_removes_whole_characters() {
+        let mut text = "ls 日本".to_string();
+        assert_eq!(pop_char(&mut text), 2);
+        assert_eq!(text, "ls 日");
+
+        // A combining accent goes with the letter it sits on
+        let mut text = "cafe\u{301}".to_string();
+        assert_eq!(pop_char(&mut text), 1);
+        assert_eq!(text, "caf");
+
+        assert_eq!(pop_char(&mut String::new()), 0);
+        assert_eq!(erase_columns(2), "\x08\x08  \x08\x08");
+    }
+
+    #[test]
+    fn test_borders_measure_columns() {
+        assert_eq!(top_border(12, Some("OPS")).width(), 12);
+        assert_eq!(top_border(12, Some("ÉTAT")), "═[ ÉTAT ]═══");
+        // Wide characters take two columns each, so the title is cut with an ellipsis
+        let border = top_border(12, Some("ＮＯＴＩＦＹ"));
+        assert_eq!(border.width(), 12);
+        assert!(border.contains('…'));
+        // Too narrow for any title
+        assert_eq!(top_border(4, Some("OPS")), "════");
+        assert_eq!(fit_width("ＷＩＤＥ", 5), "ＷＩ…");
+    }
+
     #[test]
     fn test_glitch_creation() {
         let original = "HELLO WORLD";
         let glitched = create_glitch(original, 0.5);
-        assert_eq!(glitched.len(), original.len());
+        assert_eq!(glitched.chars().count(), original.chars().count());
+    }
+
+    #[test]
+    fn test_alternate_screen_left_on_error() {
+        let mut out = Vec::new();
+        let result: Result<()> = (|| {
+            let _guard = AlternateScreenGuard::enter_on(&mut out)?;
+            anyhow::bail!("intro failed")
+        })();
+        
+        assert!(result.is_err());
+        let written = String::from_utf8(out).unwrap();
+        let entered = written.find("\x1b[?1049h").unwrap();
+        let left = written.find("\x1b[?1049l").unwrap();
+        assert!(left > entered);
+        assert!(written.ends_with("\x1b[?25h"));
+    }
+
+    #[test]
+    fn test_notification_box_lines_line_up() {
+        let lines = notification_box("ACHIEVEMENT UNLOCKED: First Blood", 39);
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().all(|line| line.width() == 39));
+        assert!(lines[2].contains("First Blood"));
+        
+        let cut = notification_box("a notification far wider than ten columns", 10);
+        assert!(cut.iter().all(|line| line.width() == 10));
+    }
+
+    #[test]
+    fn test_typeahead_is_replayed_in_order() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+        save_typeahead(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE));
+        save_typeahead(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE));
+        assert_eq!(next_typeahead().map(|k| k.code), Some(KeyCode::Char('l')));
+        assert_eq!(next_typeahead().map(|k| k.code), Some(KeyCode::Char('s')));
+        assert_eq!(next_typeahead(), None);
     }
 
     #[test]
@@ -0,0 +1,215 @@
+//! Paged output for CRIMSON-REDLINE
+//!
+//! Long listings are buffered and shown a screen at a time behind a
+//! `-- MORE --` prompt, since raw mode gets in the way of native scrollback.
+
+use crate::ui::ColorScheme;
+use crate::ui::colors::Tone;
+use anyhow::Result;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{Clear, ClearType},
+};
+use std::io::{self, Write};
+
+/// What the player asked for at the `-- MORE --` prompt
+#[derive(Debug, PartialEq)]
+enum More {
+    Page,
+    Line,
+    Quit,
+}
+
+/// Buffered output, shown a page at a time when it won't fit the terminal
+#[derive(Debug)]
+pub struct Pager {
+    /// Lines of styled segments; the last one may be unfinished
+    lines: Vec<Vec<(Tone, String)>>,
+    /// Terminal height in rows; 0 never pages
+    height: u16,
+}
+
+impl Pager {
+    /// Create an empty pager for a terminal `height` rows tall
+    pub fn new(height: u16) -> Self {
+        Pager {
+            lines: vec![Vec::new()],
+            height,
+        }
+    }
+
+    /// Buffer text in a tone; newlines start new lines
+    pub fn push(&mut self, tone: Tone, text: &str) {
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.lines.push(Vec::new());
+            }
+            if !part.is_empty() {
+                if let Some(line) = self.lines.last_mut() {
+                    line.push((tone, part.to_string()));
+                }
+            }
+        }
+    }
+
+    /// Buffer text in the primary color
+    pub fn colored(&mut self, text: &str) {
+        self.push(Tone::Primary, text);
+    }
+
+    /// Buffer text in the secondary color
+    pub fn secondary(&mut self, text: &str) {
+        self.push(Tone::Secondary, text);
+    }
+
+    /// Buffer bright text
+    pub fn bright(&mut self, text: &str) {
+        self.push(Tone::Bright, text);
+    }
+
+    /// Buffer dim text
+    pub fn dim(&mut self, text: &str) {
+        self.push(Tone::Dim, text);
+    }
+
+    /// Buffer success text
+    pub fn success(&mut self, text: &str) {
+        self.push(Tone::Success, text);
+    }
+
+    /// Buffer warning text
+    pub fn warning(&mut self, text: &str) {
+        self.push(Tone::Warning, text);
+    }
+
+    /// Buffer error text
+    pub fn error(&mut self, text: &str) {
+        self.push(Tone::Error, text);
+    }
+
+    /// Buffer an empty line
+    pub fn blank(&mut self) {
+        self.push(Tone::Primary, "\n");
+    }
+
+    /// Lines to show, ignoring an empty unfinished last line
+    fn visible_lines(&self) -> &[Vec<(Tone, String)>] {
+        match self.lines.split_last() {
+            Some((last, rest)) if last.is_empty() => rest,
+            _ => &self.lines,
+        }
+    }
+
+    /// Number of lines buffered
+    pub fn line_count(&self) -> usize {
+        self.visible_lines().len()
+    }
+
+    /// Lines per page, leaving a row for the prompt; `None` when everything fits
+    pub fn page_size(&self) -> Option<usize> {
+        let page = (self.height as usize).saturating_sub(1);
+        (page > 0 && self.line_count() > page).then_some(page)
+    }
+
+    /// Print the buffered output, pausing at `-- MORE --` between pages
+    pub fn show(&self, color_scheme: &ColorScheme) -> Result<()> {
+        let lines = self.visible_lines();
+        let Some(page) = self.page_size() else {
+            for line in lines {
+                print_line(line, color_scheme)?;
+            }
+            return Ok(());
+        };
+
+        let mut shown = 0;
+        let mut until = page;
+        loop {
+            while shown < until.min(lines.len()) {
+                print_line(&lines[shown], color_scheme)?;
+                shown += 1;
+            }
+            if shown >= lines.len() {
+                break;
+            }
+            match prompt_more(color_scheme)? {
+                More::Page => until = shown + page,
+                More::Line => until = shown + 1,
+                More::Quit => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Print one line's segments in their tones
+fn print_line(line: &[(Tone, String)], color_scheme: &ColorScheme) -> Result<()> {
+    let mut out = io::stdout().lock();
+    for (tone, text) in line {
+        color_scheme.queue_tone(&mut out, *tone, text)?;
+    }
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Show `-- MORE --` and wait for space, enter or q, then erase the prompt
+fn prompt_more(color_scheme: &ColorScheme) -> Result<More> {
+    color_scheme.print_dim("-- MORE -- (space: page, enter: line, q: quit)")?;
+    io::stdout().flush()?;
+
+    let choice = {
+        let _raw_mode = crate::ui::RawModeGuard::enable()?;
+        loop {
+            if let Event::Key(key) = event::read()? {
+                match key.code {
+                    KeyCode::Char(' ') => break More::Page,
+                    KeyCode::Enter => break More::Line,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break More::Quit,
+                    KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => break More::Quit,
+                    _ => {}
+                }
+            }
+        }
+    };
+
+    execute!(io::stdout(), cursor::MoveToColumn(0), Clear(ClearType::CurrentLine))?;
+    Ok(choice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_splits_lines_and_keeps_segments() {
+        let mut pager = Pager::new(24);
+        pager.bright("  scan        ");
+        pager.colored(" - Scan the network\n");
+        pager.blank();
+        pager.dim("one\ntwo\n");
+
+        assert_eq!(pager.line_count(), 4);
+        assert_eq!(pager.lines[0], vec![
+            (Tone::Bright, "  scan        ".to_string()),
+            (Tone::Primary, " - Scan the network".to_string()),
+        ]);
+        assert!(pager.lines[1].is_empty());
+        assert_eq!(pager.lines[3], vec![(Tone::Dim, "two".to_string())]);
+    }
+
+    #[test]
+    fn test_pages_only_when_output_overflows() {
+        let mut pager = Pager::new(5);
+        pager.colored("1\n2\n3\n4\n");
+        assert_eq!(pager.page_size(), None);
+
+        pager.colored("5");
+        assert_eq!(pager.page_size(), Some(4));
+
+        // Height 0 means the terminal size is unknown, so never page
+        let mut unsized_pager = Pager::new(0);
+        unsized_pager.colored(&"line\n".repeat(100));
+        assert_eq!(unsized_pager.page_size(), None);
+    }
+}
@@ -0,0 +1,50 @@
+//! Admin audit log for CRIMSON-REDLINE
+
+use anyhow::Result;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Get the audit log path
+pub fn audit_log_path() -> Result<PathBuf> {
+    let data_dir = crate::utils::get_data_dir()?;
+    Ok(data_dir.join("audit.log"))
+}
+
+/// Append a timestamped entry to an audit log
+pub fn append_entry(path: &Path, actor: &str, action: &str) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "{} [{}] {}",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+        actor,
+        action
+    )?;
+    Ok(())
+}
+
+/// Record an admin action in the audit log
+pub fn log_admin_action(actor: &str, action: &str) -> Result<()> {
+    append_entry(&audit_log_path()?, actor, action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_are_appended() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+
+        append_entry(&path, "admin", "locked user 'student'").unwrap();
+        append_entry(&path, "admin", "deleted user 'ghost'").unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("[admin] locked user 'student'"));
+        assert!(lines[1].ends_with("[admin] deleted user 'ghost'"));
+    }
+}
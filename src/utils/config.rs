@@ -1,57 +1,174 @@
 //! Configuration management for CRIMSON-REDLINE
 
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::{Path, PathBuf};
 use anyhow::Result;
 
 /// Main configuration structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub display: DisplayConfig,
     pub security: SecurityConfig,
     pub game: GameConfig,
+    pub network: NetworkConfig,
+    pub logging: LoggingConfig,
 }
 
 /// Display-related configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DisplayConfig {
     pub typing_speed_ms: u64,
     pub glitch_intensity: f32,
     pub use_animations: bool,
     pub color_theme: ColorTheme,
+    /// Palette used by the custom theme
+    #[serde(deserialize_with = "lenient_custom_colors")]
+    pub custom_colors: Option<CustomColors>,
+    pub show_motd: bool,
+    /// Draw with plain ASCII only, for terminals without Unicode
+    pub ascii_only: bool,
+}
+
+/// Colors for `ColorTheme::Custom`, each as `[r, g, b]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomColors {
+    pub primary: [u8; 3],
+    pub secondary: [u8; 3],
+    pub error: [u8; 3],
+    pub warning: [u8; 3],
+    pub success: [u8; 3],
+    pub dim: [u8; 3],
+    pub bright: [u8; 3],
+}
+
+/// Read `custom_colors`, treating a malformed table as missing so the rest of the config survives
+fn lenient_custom_colors<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<CustomColors>, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
+}
+
+/// The `custom_colors` saved at a path, when present but not a valid palette
+fn unreadable_custom_colors(config_path: &Path) -> Option<serde_json::Value> {
+    let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(config_path).ok()?).ok()?;
+    let raw = saved.get("display")?.get("custom_colors")?;
+    (!raw.is_null() && serde_json::from_value::<CustomColors>(raw.clone()).is_err()).then(|| raw.clone())
 }
 
 /// Security configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SecurityConfig {
     pub min_password_length: usize,
     pub require_special_chars: bool,
     pub max_login_attempts: u32,
     pub session_timeout_minutes: u32,
     pub bcrypt_cost: u32,
+    /// bcrypt hash of the admin console master password, set by an admin from the console
+    pub admin_password_hash: Option<String>,
+    /// Let agents turn on two-factor login with `2fa enable`; agents who already
+    /// have it keep needing codes if this is switched off
+    pub enable_2fa: bool,
+    /// Previous passwords an agent can't switch back to; 0 only blocks the current one
+    pub password_history: usize,
+    /// Names no one may register
+    pub reserved_usernames: Vec<String>,
 }
 
 /// Game configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GameConfig {
     pub starting_reputation: i32,
     pub max_heat_level: u32,
+    /// Share of heat kept per minute of real time
     pub heat_decay_rate: f32,
     pub enable_random_events: bool,
+    /// Base chance of a random event on each tick, before difficulty scaling
+    pub event_chance: f32,
+    /// Minimum seconds between random events
+    pub event_cooldown_secs: u64,
     pub difficulty: Difficulty,
+    /// Nudge event frequency and success rates based on how the player is doing
+    pub adaptive_difficulty: bool,
+    pub auto_hints: bool,
+    pub autosave_before_events: bool,
+    /// Autosave after this many commands (0 disables)
+    pub autosave_every_actions: u32,
+    /// Autosave after this many minutes of play (0 disables)
+    pub autosave_interval_minutes: u32,
+    pub scan_min_devices: usize,
+    pub scan_max_devices: usize,
+    /// Ask before logging out when Esc is pressed at the prompt
+    pub confirm_logout_on_esc: bool,
+    /// Ask before logging out via the logout command
+    pub confirm_logout_command: bool,
+    /// Milliseconds between wake-ups of the input loop while waiting for a key
+    pub input_tick_ms: u64,
+    /// Base reputation awards; override individual values to rebalance progression
+    pub reputation_formula: crate::game::ReputationFormula,
 }
 
-/// Color themes
+/// Simulated network configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Round-trip latency to remote hosts in milliseconds
+    pub remote_latency_ms: u64,
+}
+
+/// Session activity logging
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Log commands, events and state changes to the data directory
+    pub enabled: bool,
+    /// Size in KB at which the log is rotated
+    pub max_size_kb: u64,
+    /// Rotated logs kept alongside the current one
+    pub keep_rotated: usize,
+}
+
+/// Color themes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ColorTheme {
     Crimson,      // Default red theme
     Blood,        // Darker red
     Neon,         // Bright red/pink
     Terminal,     // Classic green (easter egg)
+    Custom,       // Read from display.custom_colors
+}
+
+impl ColorTheme {
+    /// Every selectable theme, in listing order
+    pub const ALL: [ColorTheme; 5] = [
+        ColorTheme::Crimson,
+        ColorTheme::Blood,
+        ColorTheme::Neon,
+        ColorTheme::Terminal,
+        ColorTheme::Custom,
+    ];
+
+    /// Lowercase name used by the `theme` command
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorTheme::Crimson => "crimson",
+            ColorTheme::Blood => "blood",
+            ColorTheme::Neon => "neon",
+            ColorTheme::Terminal => "terminal",
+            ColorTheme::Custom => "custom",
+        }
+    }
+
+    /// Look up a theme by name, ignoring case
+    pub fn from_name(name: &str) -> Option<ColorTheme> {
+        Self::ALL.into_iter().find(|theme| theme.name().eq_ignore_ascii_case(name))
+    }
 }
 
 /// Game difficulty levels
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Difficulty {
     Script,       // Easy mode
     Hacker,       // Normal mode
@@ -59,29 +176,179 @@ pub enum Difficulty {
     Phantom,      // Extreme mode
 }
 
-impl Default for Config {
+impl Difficulty {
+    /// Every difficulty, easiest first
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Script,
+        Difficulty::Hacker,
+        Difficulty::Ghost,
+        Difficulty::Phantom,
+    ];
+
+    /// Name as written in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Script => "Script",
+            Difficulty::Hacker => "Hacker",
+            Difficulty::Ghost => "Ghost",
+            Difficulty::Phantom => "Phantom",
+        }
+    }
+
+    /// Look up a difficulty by name, ignoring case
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        Self::ALL.into_iter().find(|difficulty| difficulty.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl Default for DisplayConfig {
     fn default() -> Self {
         Self {
-            display: DisplayConfig {
-                typing_speed_ms: 15,
-                glitch_intensity: 0.1,
-                use_animations: true,
-                color_theme: ColorTheme::Crimson,
-            },
-            security: SecurityConfig {
-                min_password_length: 8,
-                require_special_chars: true,
-                max_login_attempts: 3,
-                session_timeout_minutes: 30,
-                bcrypt_cost: 12,
-            },
-            game: GameConfig {
-                starting_reputation: 0,
-                max_heat_level: 100,
-                heat_decay_rate: 0.95,
-                enable_random_events: true,
-                difficulty: Difficulty::Hacker,
-            },
+            typing_speed_ms: 15,
+            glitch_intensity: 0.1,
+            use_animations: true,
+            color_theme: ColorTheme::Crimson,
+            custom_colors: None,
+            show_motd: true,
+            ascii_only: false,
+        }
+    }
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            min_password_length: 8,
+            require_special_chars: true,
+            max_login_attempts: 3,
+            session_timeout_minutes: 30,
+            bcrypt_cost: 12,
+            admin_password_hash: None,
+            enable_2fa: false,
+            password_history: 3,
+            reserved_usernames: DEFAULT_RESERVED_USERNAMES.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            starting_reputation: 0,
+            max_heat_level: 100,
+            heat_decay_rate: 0.95,
+            enable_random_events: true,
+            event_chance: 0.1,
+            event_cooldown_secs: 30,
+            difficulty: Difficulty::Hacker,
+            adaptive_difficulty: false,
+            auto_hints: true,
+            autosave_before_events: true,
+            autosave_every_actions: 10,
+            autosave_interval_minutes: 3,
+            scan_min_devices: 5,
+            scan_max_devices: 14,
+            confirm_logout_on_esc: true,
+            confirm_logout_command: false,
+            input_tick_ms: 250,
+            reputation_formula: crate::game::ReputationFormula::default(),
+        }
+    }
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            remote_latency_ms: 120,
+        }
+    }
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_size_kb: 256,
+            keep_rotated: 3,
+        }
+    }
+}
+
+/// Settings the `config set` command may change in-session
+pub const SETTABLE_KEYS: [&str; 6] = [
+    "typing_speed_ms",
+    "glitch_intensity",
+    "use_animations",
+    "color_theme",
+    "difficulty",
+    "enable_random_events",
+];
+
+/// Slowest typing speed `config set` accepts, in milliseconds per character
+pub const MAX_TYPING_SPEED_MS: u64 = 200;
+
+/// Lowest work factor bcrypt accepts
+pub const MIN_BCRYPT_COST: u32 = 4;
+
+/// Highest work factor bcrypt accepts
+pub const MAX_BCRYPT_COST: u32 = 31;
+
+/// System-like names kept back from registration out of the box
+pub const DEFAULT_RESERVED_USERNAMES: &[&str] = &["admin", "administrator", "root", "system", "sysadmin", "operator"];
+
+impl SecurityConfig {
+    /// Work factor for new password hashes, clamped to the range bcrypt accepts
+    pub fn bcrypt_cost(&self) -> u32 {
+        self.bcrypt_cost.clamp(MIN_BCRYPT_COST, MAX_BCRYPT_COST)
+    }
+
+    /// Idle time before a session expires; `None` when the timeout is disabled (0)
+    pub fn session_timeout(&self) -> Option<std::time::Duration> {
+        match self.session_timeout_minutes {
+            0 => None,
+            minutes => Some(std::time::Duration::from_secs(u64::from(minutes) * 60)),
+        }
+    }
+}
+
+impl GameConfig {
+    /// Base event chance, clamped to a probability
+    pub fn event_chance(&self) -> f32 {
+        self.event_chance.clamp(0.0, 1.0)
+    }
+
+    /// Quiet time enforced after each random event
+    pub fn event_cooldown(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.event_cooldown_secs)
+    }
+
+    /// Commands between autosaves; `None` when disabled (0)
+    pub fn autosave_every_actions(&self) -> Option<u32> {
+        match self.autosave_every_actions {
+            0 => None,
+            actions => Some(actions),
+        }
+    }
+
+    /// Play time between autosaves; `None` when disabled (0)
+    pub fn autosave_interval(&self) -> Option<std::time::Duration> {
+        match self.autosave_interval_minutes {
+            0 => None,
+            minutes => Some(std::time::Duration::from_secs(u64::from(minutes) * 60)),
+        }
+    }
+
+    /// Whether to snapshot before irreversible event outcomes (never on Phantom)
+    pub fn pre_event_autosave_enabled(&self) -> bool {
+        self.autosave_before_events && !matches!(self.difficulty, Difficulty::Phantom)
+    }
+
+    /// Whether a logout should be confirmed, depending on how it was requested
+    pub fn confirm_logout(&self, via_escape: bool) -> bool {
+        if via_escape {
+            self.confirm_logout_on_esc
+        } else {
+            self.confirm_logout_command
         }
     }
 }
@@ -89,25 +356,117 @@ impl Default for Config {
 impl Config {
     /// Load configuration from file
     pub fn load() -> Result<Self> {
-        let config_path = Self::config_path()?;
-        
+        Self::load_from(&Self::config_path()?)
+    }
+
+    /// Load configuration from a specific path, recovering from a corrupt file
+    pub fn load_from(config_path: &Path) -> Result<Self> {
         if config_path.exists() {
-            let contents = std::fs::read_to_string(&config_path)?;
-            let config: Config = serde_json::from_str(&contents)?;
-            Ok(config)
+            let contents = std::fs::read_to_string(config_path)?;
+            match serde_json::from_str::<Config>(&contents) {
+                Ok(config) => {
+                    if config.security.bcrypt_cost != config.security.bcrypt_cost() {
+                        eprintln!(
+                            "[WARNING] bcrypt_cost {} is outside {}..={}; using {}.",
+                            config.security.bcrypt_cost,
+                            MIN_BCRYPT_COST,
+                            MAX_BCRYPT_COST,
+                            config.security.bcrypt_cost()
+                        );
+                    }
+                    Ok(config)
+                }
+                Err(e) => {
+                    // Keep the broken file around for the player to fix, then start fresh
+                    let backup_path = config_path.with_extension("json.bak");
+                    std::fs::rename(config_path, &backup_path)?;
+                    eprintln!(
+                        "[WARNING] Config file is corrupt ({}). Backed up to {} and restored defaults.",
+                        e,
+                        backup_path.display()
+                    );
+                    let config = Config::default();
+                    config.save_to(config_path)?;
+                    Ok(config)
+                }
+            }
         } else {
             // Create default config
             let config = Config::default();
-            config.save()?;
+            config.save_to(config_path)?;
             Ok(config)
         }
     }
 
+    /// Re-read the config file mid-session
+    pub fn reload() -> Result<Self> {
+        Self::reload_from(&Self::config_path()?)
+    }
+
+    /// Re-read configuration from a specific path. Unlike `load_from`, a file
+    /// that doesn't parse is an error and is left alone, since it's likely
+    /// being edited right now.
+    pub fn reload_from(config_path: &Path) -> Result<Self> {
+        if !config_path.exists() {
+            return Ok(Config::default());
+        }
+        let contents = std::fs::read_to_string(config_path)?;
+        serde_json::from_str(&contents).map_err(|e| anyhow::anyhow!("{} doesn't parse: {}", config_path.display(), e))
+    }
+
     /// Save configuration to file
     pub fn save(&self) -> Result<()> {
-        let config_path = Self::config_path()?;
-        let contents = serde_json::to_string_pretty(self)?;
-        std::fs::write(&config_path, contents)?;
+        self.save_to(&Self::config_path()?)
+    }
+
+    /// Save configuration to a specific path, carrying over a hand-written
+    /// `custom_colors` that couldn't be read so the player can still fix it
+    pub fn save_to(&self, config_path: &Path) -> Result<()> {
+        let contents = match unreadable_custom_colors(config_path).filter(|_| self.display.custom_colors.is_none()) {
+            Some(raw) => {
+                let mut value = serde_json::to_value(self)?;
+                value["display"]["custom_colors"] = raw;
+                serde_json::to_string_pretty(&value)?
+            }
+            None => serde_json::to_string_pretty(self)?,
+        };
+        std::fs::write(config_path, contents)?;
+        Ok(())
+    }
+
+    /// Change one of the `SETTABLE_KEYS` from its text form; errors list what is accepted
+    pub fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "typing_speed_ms" => {
+                self.display.typing_speed_ms = value
+                    .parse()
+                    .ok()
+                    .filter(|ms| *ms <= MAX_TYPING_SPEED_MS)
+                    .ok_or_else(|| anyhow::anyhow!("typing_speed_ms must be a whole number from 0 to {}", MAX_TYPING_SPEED_MS))?;
+            }
+            "glitch_intensity" => {
+                self.display.glitch_intensity = value
+                    .parse()
+                    .ok()
+                    .filter(|intensity: &f32| (0.0..=1.0).contains(intensity))
+                    .ok_or_else(|| anyhow::anyhow!("glitch_intensity must be a number from 0.0 to 1.0"))?;
+            }
+            "use_animations" => self.display.use_animations = parse_switch(key, value)?,
+            "enable_random_events" => self.game.enable_random_events = parse_switch(key, value)?,
+            "color_theme" => {
+                self.display.color_theme = ColorTheme::from_name(value).ok_or_else(|| {
+                    let names: Vec<&str> = ColorTheme::ALL.iter().map(ColorTheme::name).collect();
+                    anyhow::anyhow!("unknown color_theme '{}'; choose one of: {}", value, names.join(", "))
+                })?;
+            }
+            "difficulty" => {
+                self.game.difficulty = Difficulty::from_name(value).ok_or_else(|| {
+                    let names: Vec<&str> = Difficulty::ALL.iter().map(Difficulty::name).collect();
+                    anyhow::anyhow!("unknown difficulty '{}'; choose one of: {}", value, names.join(", "))
+                })?;
+            }
+            _ => anyhow::bail!("'{}' can't be set here; settable keys: {}", key, SETTABLE_KEYS.join(", ")),
+        }
         Ok(())
     }
 
@@ -161,10 +520,23 @@ impl Config {
             ColorTheme::Blood => (136, 8, 8),        // Dark blood red
             ColorTheme::Neon => (255, 16, 70),       // Neon red/pink
             ColorTheme::Terminal => (0, 255, 0),     // Classic terminal green
+            ColorTheme::Custom => match &self.display.custom_colors {
+                Some(colors) => colors.primary.into(),
+                None => (220, 20, 60),               // Falls back to crimson
+            },
         }
     }
 }
 
+/// Parse an on/off setting
+fn parse_switch(key: &str, value: &str) -> Result<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "on" | "yes" => Ok(true),
+        "false" | "off" | "no" => Ok(false),
+        _ => anyhow::bail!("{} must be true or false (on/off also work)", key),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,6 +548,32 @@ mod tests {
         assert!(config.security.require_special_chars);
     }
 
+    #[test]
+    fn test_set_value_validates_whitelisted_keys() {
+        let mut config = Config::default();
+        config.set_value("typing_speed_ms", "0").unwrap();
+        config.set_value("glitch_intensity", "0.5").unwrap();
+        config.set_value("use_animations", "off").unwrap();
+        config.set_value("color_theme", "NEON").unwrap();
+        config.set_value("difficulty", "ghost").unwrap();
+        config.set_value("enable_random_events", "false").unwrap();
+
+        assert_eq!(config.display.typing_speed_ms, 0);
+        assert_eq!(config.display.glitch_intensity, 0.5);
+        assert!(!config.display.use_animations);
+        assert_eq!(config.display.color_theme, ColorTheme::Neon);
+        assert_eq!(config.game.difficulty, Difficulty::Ghost);
+        assert!(!config.game.enable_random_events);
+
+        assert!(config.set_value("glitch_intensity", "1.5").is_err());
+        assert!(config.set_value("typing_speed_ms", "-3").is_err());
+        let err = config.set_value("difficulty", "insane").unwrap_err().to_string();
+        assert!(err.contains("Script, Hacker, Ghost, Phantom"));
+        let err = config.set_value("bcrypt_cost", "4").unwrap_err().to_string();
+        assert!(err.contains("typing_speed_ms"));
+        assert_eq!(config.security.bcrypt_cost, 12);
+    }
+
     #[test]
     fn test_password_validation() {
         let config = Config::default();
@@ -199,6 +597,15 @@ mod tests {
         assert!(config.validate_password("Password123!").is_ok());
     }
 
+    #[test]
+    fn test_session_timeout() {
+        let mut config = Config::default();
+        assert_eq!(config.security.session_timeout(), Some(std::time::Duration::from_secs(30 * 60)));
+
+        config.security.session_timeout_minutes = 0;
+        assert_eq!(config.security.session_timeout(), None);
+    }
+
     #[test]
     fn test_color_themes() {
         let mut config = Config::default();
@@ -209,4 +616,115 @@ mod tests {
         config.display.color_theme = ColorTheme::Blood;
         assert_eq!(config.get_color_rgb(), (136, 8, 8));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bcrypt_cost_is_clamped() {
+        let mut security = SecurityConfig::default();
+        assert_eq!(security.bcrypt_cost(), 12);
+        security.bcrypt_cost = 2;
+        assert_eq!(security.bcrypt_cost(), MIN_BCRYPT_COST);
+        security.bcrypt_cost = 40;
+        assert_eq!(security.bcrypt_cost(), MAX_BCRYPT_COST);
+    }
+
+    #[test]
+    fn test_theme_names_round_trip() {
+        for theme in ColorTheme::ALL {
+            assert_eq!(ColorTheme::from_name(theme.name()), Some(theme));
+        }
+        assert_eq!(ColorTheme::from_name("NEON"), Some(ColorTheme::Neon));
+        assert_eq!(ColorTheme::from_name("mauve"), None);
+    }
+
+    #[test]
+    fn test_custom_colors_parse_leniently() {
+        let valid = r#"{"display": {"color_theme": "Custom", "custom_colors": {
+            "primary": [1, 2, 3], "secondary": [0, 0, 0], "error": [0, 0, 0], "warning": [0, 0, 0],
+            "success": [0, 0, 0], "dim": [0, 0, 0], "bright": [0, 0, 0]}}}"#;
+        let config: Config = serde_json::from_str(valid).unwrap();
+        assert_eq!(config.get_color_rgb(), (1, 2, 3));
+
+        // A bad palette is dropped without taking the other settings with it
+        let malformed = r#"{"display": {"color_theme": "Custom", "typing_speed_ms": 3,
+            "custom_colors": {"primary": [999, 0]}}}"#;
+        let config: Config = serde_json::from_str(malformed).unwrap();
+        assert_eq!(config.display.custom_colors, None);
+        assert_eq!(config.display.typing_speed_ms, 3);
+        assert_eq!(config.get_color_rgb(), (220, 20, 60));
+    }
+
+    #[test]
+    fn test_saving_keeps_an_unreadable_palette() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, r#"{"display": {"color_theme": "Custom", "custom_colors": {"primary": [999, 0]}}}"#).unwrap();
+
+        let mut config = Config::load_from(&path).unwrap();
+        config.set_value("typing_speed_ms", "5").unwrap();
+        config.save_to(&path).unwrap();
+
+        let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["display"]["custom_colors"]["primary"], serde_json::json!([999, 0]));
+        assert_eq!(saved["display"]["typing_speed_ms"], 5);
+        assert_eq!(Config::load_from(&path).unwrap().display.typing_speed_ms, 5);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let config: Config = serde_json::from_str(r#"{"game": {"starting_reputation": 50}}"#).unwrap();
+        assert_eq!(config.game.starting_reputation, 50);
+        assert!(config.game.auto_hints);
+        assert_eq!(config.game.input_tick_ms, 250);
+        assert_eq!(config.security.bcrypt_cost, 12);
+    }
+
+    #[test]
+    fn test_corrupt_config_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, "{ \"display\": { broken").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.security.min_password_length, 8);
+
+        // Bad file is preserved and a valid default config is written back
+        let backup = std::fs::read_to_string(dir.path().join("config.json.bak")).unwrap();
+        assert!(backup.contains("broken"));
+        assert!(Config::load_from(&path).is_ok());
+        assert!(serde_json::from_str::<Config>(&std::fs::read_to_string(&path).unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_reload_leaves_a_broken_file_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, r#"{"display": {"color_theme": "Neon"}}"#).unwrap();
+        assert_eq!(Config::reload_from(&path).unwrap().display.color_theme, ColorTheme::Neon);
+
+        std::fs::write(&path, r#"{"display": {"color_theme": "#).unwrap();
+        assert!(Config::reload_from(&path).is_err());
+        assert!(path.exists());
+        assert!(!path.with_extension("json.bak").exists());
+    }
+
+    #[test]
+    fn test_pre_event_autosave_off_on_phantom() {
+        let mut config = Config::default();
+        assert!(config.game.pre_event_autosave_enabled());
+
+        config.game.difficulty = Difficulty::Phantom;
+        assert!(!config.game.pre_event_autosave_enabled());
+    }
+
+    #[test]
+    fn test_logout_confirmation() {
+        let mut game = GameConfig::default();
+        assert!(game.confirm_logout(true));
+        assert!(!game.confirm_logout(false));
+
+        game.confirm_logout_on_esc = false;
+        game.confirm_logout_command = true;
+        assert!(!game.confirm_logout(true));
+        assert!(game.confirm_logout(false));
+    }
+}
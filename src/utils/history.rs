@@ -0,0 +1,181 @@
+//! Per-agent command history for CRIMSON-REDLINE
+
+use anyhow::Result;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Most commands kept in a history file
+pub const MAX_HISTORY: usize = 500;
+
+/// Command history with up/down recall, optionally backed by a file
+#[derive(Debug, Default)]
+pub struct CommandHistory {
+    entries: Vec<String>,
+    path: Option<PathBuf>,
+    /// Entry currently recalled with up/down, if any
+    cursor: Option<usize>,
+}
+
+impl CommandHistory {
+    /// Load an agent's history from the data directory
+    pub fn load_for_user(username: &str) -> Result<Self> {
+        Self::open(Self::path_for_user(username)?)
+    }
+
+    /// Where an agent's history file lives
+    pub fn path_for_user(username: &str) -> Result<PathBuf> {
+        let dir = crate::utils::get_data_dir()?.join("history");
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir.join(format!("{}.history", username)))
+    }
+
+    /// Delete an agent's history file, if they have one
+    pub fn delete_for_user(username: &str) -> Result<()> {
+        match std::fs::remove_file(Self::path_for_user(username)?) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Move the history file to `path` and keep recording there
+    pub fn move_to(&mut self, path: PathBuf) -> Result<()> {
+        if let Some(old) = &self.path {
+            if old.exists() {
+                std::fs::rename(old, &path)?;
+            }
+        }
+        self.path = Some(path);
+        Ok(())
+    }
+
+    /// Load history from a file, starting empty if it doesn't exist yet
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let mut entries: Vec<String> = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents.lines().map(String::from).collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let excess = entries.len().saturating_sub(MAX_HISTORY);
+        entries.drain(..excess);
+
+        Ok(CommandHistory {
+            entries,
+            path: Some(path),
+            cursor: None,
+        })
+    }
+
+    /// Forget every command and delete the history file; nothing is recorded after
+    pub fn wipe(&mut self) -> Result<()> {
+        self.entries.clear();
+        self.cursor = None;
+        match self.path.take().map(std::fs::remove_file) {
+            Some(Err(e)) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record an executed command, appending it to the history file
+    pub fn push(&mut self, command: &str) -> Result<()> {
+        self.cursor = None;
+        let command = command.trim();
+        if command.is_empty() || self.entries.last().is_some_and(|last| last == command) {
+            return Ok(());
+        }
+
+        self.entries.push(command.to_string());
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if self.entries.len() > MAX_HISTORY {
+            // Rewrite the file rather than letting it grow without bound
+            let excess = self.entries.len() - MAX_HISTORY;
+            self.entries.drain(..excess);
+            std::fs::write(path, self.entries.join("\n") + "\n")?;
+        } else {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", command)?;
+        }
+        Ok(())
+    }
+
+    /// All recorded commands, oldest first
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Step back to an older command
+    pub fn older(&mut self) -> Option<&str> {
+        let index = match self.cursor {
+            None => self.entries.len().checked_sub(1)?,
+            Some(i) => i.saturating_sub(1),
+        };
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Step forward to a newer command; `None` once past the newest
+    pub fn newer(&mut self) -> Option<&str> {
+        let index = self.cursor? + 1;
+        if index >= self.entries.len() {
+            self.cursor = None;
+            return None;
+        }
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_persists_and_caps() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent.history");
+
+        let mut history = CommandHistory::open(path.clone()).unwrap();
+        for i in 0..MAX_HISTORY + 10 {
+            history.push(&format!("scan 10.0.0.{}", i)).unwrap();
+        }
+        history.push("   ").unwrap();
+
+        let reloaded = CommandHistory::open(path).unwrap();
+        assert_eq!(reloaded.entries().len(), MAX_HISTORY);
+        assert_eq!(reloaded.entries().first().unwrap(), "scan 10.0.0.10");
+        assert_eq!(reloaded.entries().last().unwrap(), &format!("scan 10.0.0.{}", MAX_HISTORY + 9));
+    }
+
+    #[test]
+    fn test_up_down_recall() {
+        let mut history = CommandHistory::default();
+        history.push("scan").unwrap();
+        history.push("exploit 10.0.0.5").unwrap();
+        history.push("exploit 10.0.0.5").unwrap();
+
+        assert_eq!(history.newer(), None);
+        assert_eq!(history.older(), Some("exploit 10.0.0.5"));
+        assert_eq!(history.older(), Some("scan"));
+        assert_eq!(history.older(), Some("scan"));
+        assert_eq!(history.newer(), Some("exploit 10.0.0.5"));
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn test_wiped_history_stays_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent.history");
+        let mut history = CommandHistory::open(path.clone()).unwrap();
+        history.push("scan").unwrap();
+
+        history.wipe().unwrap();
+        history.push("delete-account").unwrap();
+
+        assert!(!path.exists());
+        assert_eq!(history.older(), Some("delete-account"));
+        history.wipe().unwrap();
+    }
+}
@@ -1,6 +1,11 @@
 //! Utility modules for CRIMSON-REDLINE
 
+pub mod audit;
 pub mod config;
+pub mod history;
+pub mod motd;
+pub mod secret;
+pub mod session_log;
 
 pub use config::Config;
 
@@ -36,6 +41,26 @@ pub fn clear_screen() -> Result<()> {
     Ok(())
 }
 
+/// Format a duration as `HhMmSs`, dropping leading zero units
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}h{:02}m{:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m{:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Hash that stays the same across runs and builds (FNV-1a), for seeding generated content
+pub fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
 /// Generate a random delay in milliseconds for dramatic effect
 pub fn random_delay() -> u64 {
     use rand::Rng;
@@ -51,7 +76,7 @@ pub async fn dramatic_pause() {
 pub fn glitch_text(text: &str, intensity: f32) -> String {
     use rand::Rng;
     let mut rng = rand::thread_rng();
-    let glitch_chars = vec!['█', '▓', '▒', '░', '╬', '╪', '┼', '╫'];
+    let glitch_chars = ['█', '▓', '▒', '░', '╬', '╪', '┼', '╫'];
     
     text.chars()
         .map(|c| {
@@ -70,6 +95,14 @@ pub fn glitch_text(text: &str, intensity: f32) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_duration() {
+        use std::time::Duration;
+        assert_eq!(format_duration(Duration::from_secs(7)), "7s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m05s");
+        assert_eq!(format_duration(Duration::from_secs(3 * 3600 + 61)), "3h01m01s");
+    }
+
     #[test]
     fn test_glitch_text() {
         let original = "HELLO";
@@ -83,6 +116,6 @@ mod tests {
     #[test]
     fn test_random_delay() {
         let delay = random_delay();
-        assert!(delay >= 50 && delay < 200);
+        assert!((50..200).contains(&delay));
     }
 }
\ No newline at end of file
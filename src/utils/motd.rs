@@ -0,0 +1,71 @@
+//! Message of the day for CRIMSON-REDLINE
+
+use anyhow::Result;
+use rand::Rng;
+use std::path::{Path, PathBuf};
+
+/// Built-in quotes used when no motd.txt is present
+const DEFAULT_QUOTES: &[&str] = &[
+    "The quieter you become, the more you are able to hear.",
+    "There is no patch for human stupidity.",
+    "Every system has a backdoor. Find it before they find you.",
+    "Heat is temporary. Logs are forever. Scrub both.",
+    "Trust no packet you didn't forge yourself.",
+    "The best exploit is the one nobody notices.",
+    "Root is a state of mind.",
+];
+
+/// Get the motd file path
+pub fn motd_path() -> Result<PathBuf> {
+    let data_dir = crate::utils::get_data_dir()?;
+    Ok(data_dir.join("motd.txt"))
+}
+
+/// Read a custom message of the day, if the file exists and isn't blank
+pub fn read_motd(path: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let trimmed = contents.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Get the message of the day, falling back to a random built-in quote
+pub fn message_of_the_day() -> String {
+    motd_path()
+        .ok()
+        .and_then(|path| read_motd(&path))
+        .unwrap_or_else(random_quote)
+}
+
+/// Pick a random built-in quote
+fn random_quote() -> String {
+    let mut rng = rand::thread_rng();
+    DEFAULT_QUOTES[rng.gen_range(0..DEFAULT_QUOTES.len())].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_custom_motd() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("motd.txt");
+        std::fs::write(&path, "  Server maintenance at midnight\n").unwrap();
+        assert_eq!(read_motd(&path), Some("Server maintenance at midnight".to_string()));
+    }
+
+    #[test]
+    fn test_missing_or_blank_motd() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("motd.txt");
+        assert_eq!(read_motd(&path), None);
+
+        std::fs::write(&path, "\n   \n").unwrap();
+        assert_eq!(read_motd(&path), None);
+        assert!(DEFAULT_QUOTES.contains(&random_quote().as_str()));
+    }
+}
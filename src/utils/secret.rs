@@ -0,0 +1,34 @@
+//! Zeroized buffers for passwords typed into CRIMSON-REDLINE
+
+use zeroize::Zeroizing;
+
+/// Longest password, in bytes, the input screens accept
+pub const MAX_PASSWORD_LEN: usize = 50;
+
+/// Password text that is overwritten on drop, and by `zeroize()` instead of `clear()`
+pub type PasswordBuffer = Zeroizing<String>;
+
+/// Empty password buffer with room for the longest password plus one multi-byte character,
+/// so typing never reallocates and leaves stray copies behind
+pub fn password_buffer() -> PasswordBuffer {
+    Zeroizing::new(String::with_capacity(MAX_PASSWORD_LEN + 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zeroize::Zeroize;
+
+    #[test]
+    fn test_buffer_never_reallocates() {
+        let mut buffer = password_buffer();
+        let capacity = buffer.capacity();
+        while buffer.len() < MAX_PASSWORD_LEN {
+            buffer.push('é');
+        }
+        assert_eq!(buffer.capacity(), capacity);
+
+        buffer.zeroize();
+        assert!(buffer.is_empty());
+    }
+}
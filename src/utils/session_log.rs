@@ -0,0 +1,178 @@
+//! Session activity log for CRIMSON-REDLINE
+//!
+//! A plain append-only file per agent, rotated by size. These are the logs
+//! the logout sequence talks about wiping.
+
+use crate::utils::config::LoggingConfig;
+use anyhow::Result;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Flags whose following argument is a secret and never logged
+const SECRET_FLAGS: &[&str] = &["-k", "--key"];
+
+/// Rotating activity log for one agent
+#[derive(Debug)]
+pub struct SessionLog {
+    path: PathBuf,
+    max_bytes: u64,
+    keep_rotated: usize,
+}
+
+impl SessionLog {
+    /// Open the agent's log in the data directory; `None` when logging is disabled
+    pub fn for_user(username: &str, config: &LoggingConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        Ok(Some(Self::new(Self::path_for_user(username)?, config.max_size_kb * 1024, config.keep_rotated)))
+    }
+
+    /// Where an agent's log lives in the data directory
+    pub fn path_for_user(username: &str) -> Result<PathBuf> {
+        let dir = crate::utils::get_data_dir()?.join("logs");
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir.join(format!("{}.log", username)))
+    }
+
+    /// Log to `path`, rotating once it reaches `max_bytes`
+    pub fn new(path: PathBuf, max_bytes: u64, keep_rotated: usize) -> Self {
+        SessionLog { path, max_bytes, keep_rotated }
+    }
+
+    /// Append a timestamped entry of the given kind (`cmd`, `event`, `state`, ...)
+    pub fn record(&self, kind: &str, message: &str) -> Result<()> {
+        if std::fs::metadata(&self.path).is_ok_and(|m| m.len() >= self.max_bytes) {
+            self.rotate()?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(
+            file,
+            "{} [{}] {}",
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+            kind,
+            message
+        )?;
+        Ok(())
+    }
+
+    /// Delete the log and every rotated copy, returning how many files went
+    pub fn wipe(&self) -> Result<usize> {
+        let mut removed = 0;
+        for path in std::iter::once(self.path.clone()).chain((1..=self.keep_rotated).map(|n| self.rotated(n))) {
+            match std::fs::remove_file(&path) {
+                Ok(()) => removed += 1,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Move the log and its rotated copies to `path`, logging there from now on
+    pub fn move_to(&mut self, path: PathBuf) -> Result<()> {
+        let old = std::mem::replace(self, Self::new(path, self.max_bytes, self.keep_rotated));
+        for n in 1..=self.keep_rotated {
+            if old.rotated(n).exists() {
+                std::fs::rename(old.rotated(n), self.rotated(n))?;
+            }
+        }
+        if old.path.exists() {
+            std::fs::rename(&old.path, &self.path)?;
+        }
+        Ok(())
+    }
+
+    /// Path of the log itself
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of the `n`th most recent rotated log
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    /// Shift each rotated log back one place, dropping the oldest
+    fn rotate(&self) -> Result<()> {
+        if self.keep_rotated == 0 {
+            std::fs::remove_file(&self.path)?;
+            return Ok(());
+        }
+        for n in (1..self.keep_rotated).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                std::fs::rename(&from, self.rotated(n + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, self.rotated(1))?;
+        Ok(())
+    }
+}
+
+/// Mask arguments that carry secrets, such as decryption keys
+pub fn redact_command(input: &str) -> String {
+    let mut words: Vec<&str> = input.split_whitespace().collect();
+    for i in 1..words.len() {
+        if SECRET_FLAGS.contains(&words[i - 1]) {
+            words[i] = "***";
+        }
+    }
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_rotates_and_keeps_the_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = SessionLog::new(dir.path().join("agent.log"), 64, 2);
+
+        for i in 0..12 {
+            log.record("cmd", &format!("scan 10.0.0.{}", i)).unwrap();
+        }
+
+        let current = std::fs::read_to_string(log.path()).unwrap();
+        assert!(current.ends_with("[cmd] scan 10.0.0.11\n"));
+        assert!(log.rotated(1).exists() && log.rotated(2).exists());
+        assert!(!log.rotated(3).exists());
+
+        assert_eq!(log.wipe().unwrap(), 3);
+        assert!(!log.path().exists());
+        assert_eq!(log.wipe().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_moved_log_takes_its_rotations() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = SessionLog::new(dir.path().join("ghsot.log"), 64, 2);
+        for i in 0..6 {
+            log.record("cmd", &format!("scan 10.0.0.{}", i)).unwrap();
+        }
+
+        log.move_to(dir.path().join("ghost.log")).unwrap();
+        log.record("session", "renamed").unwrap();
+
+        assert!(!dir.path().join("ghsot.log").exists() && !dir.path().join("ghsot.log.1").exists());
+        assert!(log.rotated(1).exists());
+        assert!(std::fs::read_to_string(log.path()).unwrap().ends_with("[session] renamed\n"));
+    }
+
+    #[test]
+    fn test_secrets_are_redacted() {
+        assert_eq!(redact_command("decrypt 4f2a -k hunter2 -m aes"), "decrypt 4f2a -k *** -m aes");
+        assert_eq!(redact_command("decrypt 4f2a --key"), "decrypt 4f2a --key");
+        assert_eq!(redact_command("  scan   10.0.0.5 "), "scan 10.0.0.5");
+    }
+
+    #[test]
+    fn test_disabled_logging_opens_nothing() {
+        assert!(SessionLog::for_user("agent", &LoggingConfig::default()).unwrap().is_none());
+    }
+}